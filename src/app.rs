@@ -1,4 +1,5 @@
 use clap::{App, Arg};
+use unicode_width::UnicodeWidthStr;
 
 pub fn build() -> App<'static, 'static> {
     App::new("lsd")
@@ -32,6 +33,27 @@ pub fn build() -> App<'static, 'static> {
                 .number_of_values(1)
                 .help("When to use terminal colours"),
         )
+        .arg(
+            Arg::with_name("color-theme")
+                .long("color-theme")
+                .possible_value("dark")
+                .possible_value("fixed")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Which colors to use, fixed ignoring LS_COLORS [default: dark]"),
+        )
+        .arg(
+            Arg::with_name("collapse-repeats")
+                .long("collapse-repeats")
+                .multiple(true)
+                .help("Leave the permissions, user and group blank when they repeat the ones of the previous row"),
+        )
+        .arg(
+            Arg::with_name("color-by-owner")
+                .long("color-by-owner")
+                .multiple(true)
+                .help("Color each user name by a hash of it, so that the files of an owner share a color"),
+        )
         .arg(
             Arg::with_name("icon")
                 .long("icon")
@@ -53,6 +75,56 @@ pub fn build() -> App<'static, 'static> {
                 .number_of_values(1)
                 .help("Whether to use fancy or unicode icons"),
         )
+        .arg(
+            Arg::with_name("theme")
+                .long("theme")
+                .multiple(true)
+                .number_of_values(1)
+                .help("A bundled pair of color and icon themes among dark-fancy, dark-unicode, fixed-fancy and fixed-unicode, overridden by --color-theme and --icon-theme, or else the path of a YAML file of RGB colors"),
+        )
+        .arg(
+            Arg::with_name("ls-colors-file")
+                .long("ls-colors-file")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("path")
+                .help("Read the LS_COLORS from the given dircolors database instead of the environment"),
+        )
+        .arg(
+            Arg::with_name("icon-separator")
+                .long("icon-separator")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("separator")
+                .empty_values(false)
+                .validator(|arg| validate_icon_separator(&arg))
+                .help("The string between the icons and the names, one or two columns wide"),
+        )
+        .arg(
+            Arg::with_name("icon-spacing")
+                .long("icon-spacing")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("num")
+                .validator(validate_icon_spacing)
+                .help("The number of spaces between the icons and the names, in place of the separator"),
+        )
+        .arg(
+            Arg::with_name("font-check")
+                .long("font-check")
+                .multiple(true)
+                .help("Hint on stderr when the terminal does not seem to have the Nerd Font of the fancy icons"),
+        )
+        .arg(
+            Arg::with_name("hyperlink")
+                .long("hyperlink")
+                .possible_value("always")
+                .possible_value("auto")
+                .possible_value("never")
+                .multiple(true)
+                .number_of_values(1)
+                .help("When to make the names links to the files [default: never]"),
+        )
         .arg(
             Arg::with_name("indicators")
                 .short("F")
@@ -67,6 +139,84 @@ pub fn build() -> App<'static, 'static> {
                 .multiple(true)
                 .help("Display extended file metadata as a table"),
         )
+        .arg(
+            Arg::with_name("legend")
+                .long("legend")
+                .multiple(true)
+                .help("Print the meaning of the colors and icons and exit"),
+        )
+        .arg(
+            Arg::with_name("only-dirs")
+                .long("only-dirs")
+                .multiple(true)
+                .overrides_with("only-files")
+                .help("List the directories only, the symlinks by their target unless --no-symlink"),
+        )
+        .arg(
+            Arg::with_name("only-files")
+                .long("only-files")
+                .multiple(true)
+                .overrides_with("only-dirs")
+                .help("List the entries other than the directories only, the symlinks by their target unless --no-symlink"),
+        )
+        .arg(
+            Arg::with_name("type-prefix")
+                .long("type-prefix")
+                .multiple(true)
+                .help("Prefix each name with the letter of its file type, like d for the directories, to tell the types apart without colors"),
+        )
+        .arg(
+            Arg::with_name("type-histogram")
+                .long("type-histogram")
+                .multiple(true)
+                .help("Print the count of the listed entries of each extension or file type after the listing"),
+        )
+        .arg(
+            Arg::with_name("line-ending")
+                .long("line-ending")
+                .possible_value("lf")
+                .possible_value("crlf")
+                .default_value("lf")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Which line ending to terminate the output lines with"),
+        )
+        .arg(
+            Arg::with_name("columns")
+                .long("columns")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("num")
+                .validator(validate_columns)
+                .help("Lay the grid out in exactly the given number of columns, regardless of the terminal width"),
+        )
+        .arg(
+            Arg::with_name("min-column-width")
+                .long("min-column-width")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("num")
+                .validator(validate_min_column_width)
+                .help("Pad the entries so that each column of the grid is at least the given number of cells wide"),
+        )
+        .arg(
+            Arg::with_name("max-lines")
+                .long("max-lines")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("num")
+                .validator(validate_max_lines)
+                .help("Stop the output after the given number of lines, noting that it was truncated"),
+        )
+        .arg(
+            Arg::with_name("top")
+                .long("top")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("num")
+                .validator(validate_top)
+                .help("List only the given number of largest files of the whole trees, by decreasing apparent size, down to --depth when given"),
+        )
         .arg(
             Arg::with_name("ignore-config")
                 .long("ignore-config")
@@ -87,6 +237,12 @@ pub fn build() -> App<'static, 'static> {
                 .conflicts_with("tree")
                 .help("Recurse into directories"),
         )
+        .arg(
+            Arg::with_name("show-relative-prefix")
+                .long("show-relative-prefix")
+                .multiple(true)
+                .help("Prefix the names listed by --recursive with their dimmed directory, relative to the listed input"),
+        )
         .arg(
             Arg::with_name("human_readable")
                 .short("h")
@@ -100,14 +256,42 @@ pub fn build() -> App<'static, 'static> {
                 .conflicts_with("recursive")
                 .help("Recurse into directories and present the result as a tree"),
         )
+        .arg(
+            Arg::with_name("tree-name-width")
+                .long("tree-name-width")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("num")
+                .validator(validate_tree_name_width)
+                .help("Wrap the tree names wider than the given number of columns onto continuation lines"),
+        )
         .arg(
             Arg::with_name("depth")
                 .long("depth")
                 .multiple(true)
                 .takes_value(true)
+                .number_of_values(1)
                 .value_name("num")
                 .help("Stop recursing into directories after reaching specified depth"),
         )
+        .arg(
+            Arg::with_name("mark-mounts")
+                .long("mark-mounts")
+                .multiple(true)
+                .help("Mark the directories whose filesystem differs from their parent's when recursing"),
+        )
+        .arg(
+            Arg::with_name("dim-hidden")
+                .long("dim-hidden")
+                .multiple(true)
+                .help("Dim the icons of the hidden files"),
+        )
+        .arg(
+            Arg::with_name("dir-child-count")
+                .long("dir-child-count")
+                .multiple(true)
+                .help("Append the number of their entries to the names of the directories in the grid, like src (12)"),
+        )
         .arg(
             Arg::with_name("directory-only")
                 .short("d")
@@ -129,12 +313,62 @@ pub fn build() -> App<'static, 'static> {
                 .number_of_values(1)
                 .help("How to display size"),
         )
+        .arg(
+            Arg::with_name("bytes")
+                .short("B")
+                .long("bytes")
+                .multiple(true)
+                .overrides_with("size")
+                .help("Display the sizes as exact numbers of bytes (same as --size bytes)"),
+        )
+        .arg(
+            Arg::with_name("size-width")
+                .long("size-width")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("num")
+                .validator(validate_size_width)
+                .help("Right-align the sizes within at least the given number of columns"),
+        )
+        .arg(
+            Arg::with_name("si")
+                .long("si")
+                .multiple(true)
+                .help("Display the sizes in powers of 1000, like 1.5 kB, rather than of 1024"),
+        )
+        .arg(
+            Arg::with_name("total")
+                .long("total")
+                .multiple(true)
+                .help("Print the total disk space allocated to the entries above the listing of each directory, like ls -l"),
+        )
         .arg(
             Arg::with_name("total-size")
                 .long("total-size")
                 .multiple(true)
                 .help("Display the total size of directories"),
         )
+        .arg(
+            Arg::with_name("scan-budget")
+                .long("scan-budget")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("size")
+                .validator(validate_scan_budget)
+                .help("Stop the recursion once the scanned files reach the given size, like 100MiB, noting that the listing is partial"),
+        )
+        .arg(
+            Arg::with_name("progress")
+                .long("progress")
+                .multiple(true)
+                .help("Print the count of the scanned files to stderr during the scans, if it is a tty"),
+        )
+        .arg(
+            Arg::with_name("highlight-sparse")
+                .long("highlight-sparse")
+                .multiple(true)
+                .help("Highlight the size of the sparse files, using less disk space than their size"),
+        )
         .arg(
             Arg::with_name("date")
                 .long("date")
@@ -144,6 +378,31 @@ pub fn build() -> App<'static, 'static> {
                 .number_of_values(1)
                 .help("How to display date [possible values: date, relative, +date-time-format]"),
         )
+        .arg(
+            Arg::with_name("time")
+                .long("time")
+                .possible_value("modified")
+                .possible_value("accessed")
+                .possible_value("created")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Which time stamp to display and to sort by"),
+        )
+        .arg(
+            Arg::with_name("time-zone")
+                .long("time-zone")
+                .possible_value("local")
+                .possible_value("utc")
+                .multiple(true)
+                .number_of_values(1)
+                .help("In which time zone to display the dates [default: local]"),
+        )
+        .arg(
+            Arg::with_name("date-color-scale")
+                .long("date-color-scale")
+                .multiple(true)
+                .help("Colour the dates along a gradient from the newest to the oldest entry of the listing"),
+        )
         .arg(
             Arg::with_name("timesort")
                 .short("t")
@@ -153,7 +412,7 @@ pub fn build() -> App<'static, 'static> {
                 .overrides_with("versionsort")
                 .overrides_with("sort")
                 .multiple(true)
-                .help("Sort by time modified"),
+                .help("Sort by time modified, or by the time stamp given to --time"),
         )
         .arg(
             Arg::with_name("sizesort")
@@ -192,7 +451,7 @@ pub fn build() -> App<'static, 'static> {
             Arg::with_name("sort")
                 .long("sort")
                 .multiple(true)
-                .possible_values(&["size", "time", "version", "extension"])
+                .possible_values(&["size", "time", "created", "version", "extension"])
                 .takes_value(true)
                 .value_name("WORD")
                 .overrides_with("timesort")
@@ -201,6 +460,14 @@ pub fn build() -> App<'static, 'static> {
                 .overrides_with("versionsort")
                 .help("sort by WORD instead of name")
         )
+        .arg(
+            Arg::with_name("sort-command")
+                .long("sort-command")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("cmd")
+                .help("Sort the entries with an external command, reading their names on stdin and writing them back sorted on stdout"),
+        )
         .arg(
             Arg::with_name("reverse")
                 .short("r")
@@ -208,6 +475,56 @@ pub fn build() -> App<'static, 'static> {
                 .multiple(true)
                 .help("Reverse the order of the sort"),
         )
+        .arg(
+            Arg::with_name("rtl")
+                .long("rtl")
+                .multiple(true)
+                .help("Lay the columns out from right to left, with the entries aligned to the right"),
+        )
+        .arg(
+            Arg::with_name("format")
+                .long("format")
+                .possible_value("text")
+                .possible_value("csv")
+                .possible_value("json")
+                .possible_value("raw")
+                .possible_value("html")
+                .default_value("text")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Print the listing for the terminal, or as a header row and one comma separated row per entry, in the order of the blocks, or as a JSON array, or as one literal name per line, or as an HTML table styled by inline CSS"),
+        )
+        .arg(
+            Arg::with_name("json")
+                .long("json")
+                .multiple(true)
+                .help("Print the listing as a JSON array of the entries, nesting the content of the directories (same as --format json)"),
+        )
+        .arg(
+            Arg::with_name("raw")
+                .long("raw")
+                .multiple(true)
+                .help("Print one literal name per line, without colors, icons, escapes nor alignment (same as --format raw)"),
+        )
+        .arg(
+            Arg::with_name("null")
+                .short("0")
+                .long("null")
+                .multiple(true)
+                .help("Print the literal names like --raw, each terminated by a NUL byte rather than a line ending, for xargs -0"),
+        )
+        .arg(
+            Arg::with_name("full-path")
+                .long("full-path")
+                .multiple(true)
+                .help("Print the paths of the entries with --raw or --null, rather than their names relative to the listed directories"),
+        )
+        .arg(
+            Arg::with_name("group-by-date")
+                .long("group-by-date")
+                .multiple(true)
+                .help("Split the entries of the directories into sections by their date: Future, Today, Yesterday, This week and Older"),
+        )
         .arg(
             Arg::with_name("group-dirs")
                 .long("group-dirs")
@@ -219,35 +536,78 @@ pub fn build() -> App<'static, 'static> {
                 .number_of_values(1)
                 .help("Sort the directories then the files"),
         )
+        .arg(
+            Arg::with_name("group-directories-last")
+                .long("group-directories-last")
+                .multiple(true)
+                .overrides_with("group-dirs")
+                .help("Sort the files then the directories (same as --group-dirs last)"),
+        )
+        .arg(
+            Arg::with_name("sort-dotfiles")
+                .long("sort-dotfiles")
+                .possible_value("first")
+                .possible_value("last")
+                .possible_value("mixed")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Place the dotfiles first, last or among the other files"),
+        )
         .arg(
             Arg::with_name("blocks")
                 .long("blocks")
                 .multiple(true)
                 .number_of_values(1)
-                .require_delimiter(true)
-                .possible_values(&[
-                    "permission",
-                    "user",
-                    "group",
-                    "size",
-                    "date",
-                    "name",
-                    "inode",
-                    "links",
-                ])
-                .help("Specify the blocks that will be displayed and in what order"),
+                .value_name("blocks")
+                .help("Specify the blocks that will be displayed and in what order, separated by commas, semicolons or spaces. More than one can be specified by repeating the argument [possible values: permission, user, group, size, date, name, inode, links, depth, free_inodes, size_percent, size_bar, git_dir_summary, git, age_gap, device, realpath, file_count]"),
+        )
+        .arg(
+            Arg::with_name("bundle-extension")
+                .long("bundle-extension")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("extension")
+                .help("Display the directories with the given extension as single entries, without listing their content. More than one can be specified by repeating the argument"),
         )
         .arg(
             Arg::with_name("classic")
             .long("classic")
             .help("Enable classic mode (display output similar to ls)"),
         )
+        .arg(
+            Arg::with_name("no-metadata")
+                .long("no-metadata")
+                .multiple(true)
+                .help("List the names only, with the file types the directories tell, without reading the metadata of the files"),
+        )
         .arg(
             Arg::with_name("no-symlink")
                 .long("no-symlink")
                 .multiple(true)
                 .help("Do not display symlink target"),
         )
+        .arg(
+            Arg::with_name("styled-link-target")
+                .long("styled-link-target")
+                .multiple(true)
+                .help("Color the symlink targets and prefix their icon, as if they were listed"),
+        )
+        .arg(
+            Arg::with_name("checksum-verify")
+                .long("checksum-verify")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("manifest")
+                .help("Verify the files against the SHA-256 sums of a sha256sums-style manifest, marking them as matching, mismatching or missing"),
+        )
+        .arg(
+            Arg::with_name("symlink-root")
+                .long("symlink-root")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("dir")
+                .help("Resolve the absolute symlink targets against the given directory, as if it was the root of the file system"),
+        )
         .arg(
             Arg::with_name("ignore-glob")
                 .short("I")
@@ -256,7 +616,23 @@ pub fn build() -> App<'static, 'static> {
                 .number_of_values(1)
                 .value_name("pattern")
                 .default_value("")
-                .help("Do not display files/directories with names matching the glob pattern(s). More than one can be specified by repeating the argument"),
+                .help("Do not display files/directories with names matching the glob pattern(s), or with paths relative to the listed directory matching the ones holding a `/`. More than one can be specified by repeating the argument"),
+        )
+        .arg(
+            Arg::with_name("newer-than")
+                .long("newer-than")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("time")
+                .help("Only display the entries modified after a date like 2021-03-07 [13:45[:30]], or within a duration like 30m, 2h, 3d or 1w"),
+        )
+        .arg(
+            Arg::with_name("older-than")
+                .long("older-than")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("time")
+                .help("Only display the entries modified before a date like 2021-03-07 [13:45[:30]], or longer ago than a duration like 30m, 2h, 3d or 1w"),
         )
         .arg(
             Arg::with_name("inode")
@@ -265,6 +641,85 @@ pub fn build() -> App<'static, 'static> {
                 .multiple(true)
                 .help("Display the index number of each file"),
         )
+        .arg(
+            Arg::with_name("inode-hex")
+                .long("inode-hex")
+                .multiple(true)
+                .help("Display the index numbers in hexadecimal when showing them"),
+        )
+        .arg(
+            Arg::with_name("links-only-multi")
+                .long("links-only-multi")
+                .multiple(true)
+                .help("Dim the link count of the files having a single hard link, to highlight the others"),
+        )
+        .arg(
+            Arg::with_name("access-check")
+                .long("access-check")
+                .multiple(true)
+                .help("Display after the permissions whether the current user can actually read, write or execute each file"),
+        )
+        .arg(
+            Arg::with_name("permission")
+                .long("permission")
+                .possible_value("rwx")
+                .possible_value("octal")
+                .multiple(true)
+                .number_of_values(1)
+                .help("How to display the permissions, as rwx letters or as four octal digits like 0755"),
+        )
+        .arg(
+            Arg::with_name("permission-heatmap")
+                .long("permission-heatmap")
+                .multiple(true)
+                .help("Color the background of each permission triad by its access level"),
+        )
+        .arg(
+            Arg::with_name("anonymize")
+                .long("anonymize")
+                .multiple(true)
+                .help("Display placeholders instead of the user and group names, for sharing the output"),
+        )
+        .arg(
+            Arg::with_name("numeric")
+                .short("n")
+                .long("numeric")
+                .multiple(true)
+                .help("Display the user and group ids instead of their names"),
+        )
+        .arg(
+            Arg::with_name("archive")
+                .long("archive")
+                .multiple(true)
+                .help("List the content of the tar archives (.tar, .tar.gz, .tgz) instead of the archives themselves"),
+        )
+        .arg(
+            Arg::with_name("owner-lookup-timeout")
+                .long("owner-lookup-timeout")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("ms")
+                .validator(validate_owner_lookup_timeout)
+                .help("Stop waiting for user and group names after the given milliseconds and display the numeric ids instead"),
+        )
+        .arg(
+            Arg::with_name("debug-layout")
+                .long("debug-layout")
+                .multiple(true)
+                .help("Print the number of columns and the terminal width used by the grid layout on the standard error"),
+        )
+        .arg(
+            Arg::with_name("emphasize-extension")
+                .long("emphasize-extension")
+                .multiple(true)
+                .help("Dim the extensions of the file names, to set them apart from the stems"),
+        )
+        .arg(
+            Arg::with_name("expand-paths")
+                .long("expand-paths")
+                .multiple(true)
+                .help("Expand a leading ~ and the environment variables in the path arguments"),
+        )
         .arg(
             Arg::with_name("dereference")
                 .short("L")
@@ -272,6 +727,30 @@ pub fn build() -> App<'static, 'static> {
                 .multiple(true)
                 .help("When showing file information for a symbolic link, show information for the file the link references rather than for the link itself"),
         )
+        .arg(
+            Arg::with_name("dedup")
+                .long("dedup")
+                .multiple(true)
+                .help("List only once the arguments that are the same file, sharing their device and inode"),
+        )
+        .arg(
+            Arg::with_name("content-on-trailing-slash")
+                .long("content-on-trailing-slash")
+                .multiple(true)
+                .help("List the content of the directories given as arguments only when they end with a slash, and the directories themselves otherwise"),
+        )
+        .arg(
+            Arg::with_name("dereference-command-line-symlink-to-dir")
+                .long("dereference-command-line-symlink-to-dir")
+                .multiple(true)
+                .help("List the content of the symbolic links to directories given as arguments, under the name of the links"),
+        )
+        .arg(
+            Arg::with_name("follow-dir-symlinks")
+                .long("follow-dir-symlinks")
+                .multiple(true)
+                .help("Dereference symbolic links pointing to directories and list their content with --long, leaving other symbolic links untouched. The other layouts already list this content, the flag showing the links as directories and stopping on the links back to a listed directory"),
+        )
 }
 
 fn validate_date_argument(arg: String) -> Result<(), String> {
@@ -284,6 +763,101 @@ fn validate_date_argument(arg: String) -> Result<(), String> {
     }
 }
 
+fn validate_owner_lookup_timeout(arg: String) -> Result<(), String> {
+    match arg.parse::<u64>() {
+        Ok(_) => Ok(()),
+        Err(_) => Err("the timeout has to be a number of milliseconds".to_owned()),
+    }
+}
+
+fn validate_columns(arg: String) -> Result<(), String> {
+    match arg.parse::<usize>() {
+        Ok(columns) if columns > 0 => Ok(()),
+        _ => Err("the number of columns has to be a strictly positive integer".to_owned()),
+    }
+}
+
+fn validate_min_column_width(arg: String) -> Result<(), String> {
+    match arg.parse::<usize>() {
+        Ok(_) => Ok(()),
+        Err(_) => Err("the width has to be a positive integer".to_owned()),
+    }
+}
+
+fn validate_max_lines(arg: String) -> Result<(), String> {
+    match arg.parse::<usize>() {
+        Ok(lines) if lines > 0 => Ok(()),
+        _ => Err("the number of lines has to be a strictly positive integer".to_owned()),
+    }
+}
+
+fn validate_top(arg: String) -> Result<(), String> {
+    match arg.parse::<usize>() {
+        Ok(count) if count > 0 => Ok(()),
+        _ => Err("the number of files has to be a strictly positive integer".to_owned()),
+    }
+}
+
+fn validate_size_width(arg: String) -> Result<(), String> {
+    match arg.parse::<usize>() {
+        Ok(_) => Ok(()),
+        _ => Err("the size width has to be a positive integer".to_owned()),
+    }
+}
+
+fn validate_icon_spacing(arg: String) -> Result<(), String> {
+    match arg.parse::<usize>() {
+        Ok(_) => Ok(()),
+        _ => Err("the number of spaces has to be a positive integer".to_owned()),
+    }
+}
+
+fn validate_tree_name_width(arg: String) -> Result<(), String> {
+    match arg.parse::<usize>() {
+        Ok(width) if width > 0 => Ok(()),
+        _ => Err("the name width has to be a strictly positive integer".to_owned()),
+    }
+}
+
+fn validate_scan_budget(arg: String) -> Result<(), String> {
+    match parse_byte_size(&arg) {
+        Some(bytes) if bytes > 0 => Ok(()),
+        _ => Err("the budget has to be a strictly positive size, like 512K or 100MiB".to_owned()),
+    }
+}
+
+/// Parse a size in bytes with an optional unit, where K, M, G and T and their KiB forms are
+/// powers of 1024 while the KB forms are powers of 1000.
+pub fn parse_byte_size(arg: &str) -> Option<u64> {
+    let split = arg.find(|c: char| !c.is_ascii_digit()).unwrap_or(arg.len());
+    let (value, unit) = arg.split_at(split);
+    let value: u64 = value.parse().ok()?;
+
+    let multiplier: u64 = match unit.to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KIB" => 1 << 10,
+        "M" | "MIB" => 1 << 20,
+        "G" | "GIB" => 1 << 30,
+        "T" | "TIB" => 1 << 40,
+        "KB" => 1_000,
+        "MB" => 1_000_000,
+        "GB" => 1_000_000_000,
+        "TB" => 1_000_000_000_000,
+        _ => return None,
+    };
+    value.checked_mul(multiplier)
+}
+
+pub fn validate_icon_separator(separator: &str) -> Result<(), String> {
+    match UnicodeWidthStr::width(separator) {
+        1 | 2 => Ok(()),
+        width => Err(format!(
+            "the separator has to be one or two columns wide, but '{}' is {}",
+            separator, width
+        )),
+    }
+}
+
 pub fn validate_time_format(formatter: &str) -> Result<(), String> {
     let mut chars = formatter.chars();
     loop {
@@ -1,6 +1,8 @@
+use crate::print_error;
 use ansi_term::{ANSIString, Colour, Style};
 use lscolors::{Indicator, LsColors};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
 use std::path::Path;
 
 #[allow(dead_code)]
@@ -13,6 +15,7 @@ pub enum Elem {
     },
     SymLink,
     BrokenSymLink,
+    MissingTarget,
     Dir {
         uid: bool,
     },
@@ -28,6 +31,12 @@ pub enum Elem {
     Exec,
     ExecSticky,
     NoAccess,
+    Octal,
+
+    /// Permission triads of --permission-heatmap
+    TriadFull,
+    TriadPartial,
+    TriadNone,
 
     /// Last Time Modified
     DayOld,
@@ -43,6 +52,8 @@ pub enum Elem {
     FileLarge,
     FileMedium,
     FileSmall,
+    SizeUnit,
+    SparseFile,
 
     /// INode
     INode {
@@ -52,6 +63,41 @@ pub enum Elem {
     Links {
         valid: bool,
     },
+    /// Single link count dimmed by --links-only-multi
+    SingleLink,
+
+    /// Recursion depth
+    Depth,
+
+    /// Time between the creation and the last modification
+    AgeGap,
+
+    /// Id of the device holding the file
+    Device {
+        valid: bool,
+    },
+
+    /// Canonical absolute path
+    RealPath,
+
+    /// Number of the entries of a directory
+    FileCount,
+
+    /// Git status summary
+    GitStatus {
+        clean: bool,
+    },
+
+    /// Git status of the files
+    GitStaged,
+    GitModified,
+    GitUntracked,
+    GitIgnored,
+
+    /// Checksum verification
+    ChecksumMatch,
+    ChecksumMismatch,
+    ChecksumMissing,
 
     TreeEdge,
 }
@@ -60,6 +106,83 @@ impl Elem {
     pub fn has_suid(&self) -> bool {
         matches!(self, Elem::Dir { uid: true } | Elem::File { uid: true, .. })
     }
+
+    /// Get the elements colored by the `key` of a theme file.
+    fn from_theme_key(key: &str) -> Option<Vec<Self>> {
+        let elems = match key {
+            "user" => vec![Elem::User],
+            "group" => vec![Elem::Group],
+            "read" => vec![Elem::Read],
+            "write" => vec![Elem::Write],
+            "exec" => vec![Elem::Exec],
+            "exec-sticky" => vec![Elem::ExecSticky],
+            "no-access" => vec![Elem::NoAccess],
+            "octal" => vec![Elem::Octal],
+            "triad-full" => vec![Elem::TriadFull],
+            "triad-partial" => vec![Elem::TriadPartial],
+            "triad-none" => vec![Elem::TriadNone],
+            "file" => vec![
+                Elem::File {
+                    exec: false,
+                    uid: false,
+                },
+                Elem::File {
+                    exec: false,
+                    uid: true,
+                },
+            ],
+            "executable" => vec![
+                Elem::File {
+                    exec: true,
+                    uid: false,
+                },
+                Elem::File {
+                    exec: true,
+                    uid: true,
+                },
+            ],
+            "dir" => vec![Elem::Dir { uid: false }, Elem::Dir { uid: true }],
+            "symlink" => vec![Elem::SymLink],
+            "broken-symlink" => vec![Elem::BrokenSymLink],
+            "missing-target" => vec![Elem::MissingTarget],
+            "pipe" => vec![Elem::Pipe],
+            "block-device" => vec![Elem::BlockDevice],
+            "char-device" => vec![Elem::CharDevice],
+            "socket" => vec![Elem::Socket],
+            "special" => vec![Elem::Special],
+            "hour-old" => vec![Elem::HourOld],
+            "day-old" => vec![Elem::DayOld],
+            "older" => vec![Elem::Older],
+            "non-file" => vec![Elem::NonFile],
+            "file-small" => vec![Elem::FileSmall],
+            "file-medium" => vec![Elem::FileMedium],
+            "file-large" => vec![Elem::FileLarge],
+            "size-unit" => vec![Elem::SizeUnit],
+            "sparse-file" => vec![Elem::SparseFile],
+            "inode" => vec![Elem::INode { valid: true }],
+            "no-inode" => vec![Elem::INode { valid: false }],
+            "links" => vec![Elem::Links { valid: true }],
+            "no-links" => vec![Elem::Links { valid: false }],
+            "single-link" => vec![Elem::SingleLink],
+            "depth" => vec![Elem::Depth],
+            "age-gap" => vec![Elem::AgeGap],
+            "device" => vec![Elem::Device { valid: true }],
+            "no-device" => vec![Elem::Device { valid: false }],
+            "realpath" => vec![Elem::RealPath],
+            "file-count" => vec![Elem::FileCount],
+            "git-clean" => vec![Elem::GitStatus { clean: true }],
+            "git-dirty" => vec![Elem::GitStatus { clean: false }],
+            "git-staged" => vec![Elem::GitStaged],
+            "git-modified" => vec![Elem::GitModified],
+            "git-untracked" => vec![Elem::GitUntracked],
+            "git-ignored" => vec![Elem::GitIgnored],
+            "checksum-match" => vec![Elem::ChecksumMatch],
+            "checksum-mismatch" => vec![Elem::ChecksumMismatch],
+            "checksum-missing" => vec![Elem::ChecksumMissing],
+            _ => return None,
+        };
+        Some(elems)
+    }
 }
 
 pub type ColoredString<'a> = ANSIString<'a>;
@@ -93,10 +216,117 @@ impl Colors {
         Self { colors, lscolors }
     }
 
+    /// Override the colors by the RGB ones of the theme file at `path`, the elements it misses
+    /// keeping theirs. An unreadable file and the unknown elements are skipped with a warning.
+    pub fn with_theme_file(mut self, path: &Path) -> Self {
+        let colors = match &mut self.colors {
+            Some(colors) => colors,
+            None => return self,
+        };
+
+        let yaml = match fs::read_to_string(path) {
+            Ok(yaml) => yaml,
+            Err(e) => {
+                print_error!(
+                    "Theme {} is not a bundled theme nor a readable file: {}.",
+                    path.display(),
+                    e
+                );
+                return self;
+            }
+        };
+        match Self::theme_file_colours(&yaml) {
+            Ok(theme) => colors.extend(theme),
+            Err(e) => print_error!("Theme file {} format error, {}.", path.display(), e),
+        }
+        self
+    }
+
+    /// Replace the definitions of the `LS_COLORS` environment variable by the ones of the dircolors
+    /// database at `path`, the themes ignoring `LS_COLORS` staying so. An unreadable file is
+    /// skipped with a warning.
+    pub fn with_ls_colors_file(mut self, path: &Path) -> Self {
+        if self.lscolors.is_none() {
+            return self;
+        }
+
+        match fs::read_to_string(path) {
+            Ok(database) => {
+                let definitions = Self::dircolors_definitions(&database);
+                self.lscolors = Some(LsColors::from_string(&definitions));
+            }
+            Err(e) => print_error!("LS_COLORS file {} is not readable: {}.", path.display(), e),
+        }
+        self
+    }
+
+    /// Convert the lines of a dircolors database to the definitions of `LS_COLORS`, like
+    /// `dircolors` does, the `TERM` conditions and the unknown keywords being skipped.
+    fn dircolors_definitions(database: &str) -> String {
+        let mut definitions = Vec::new();
+        for line in database.lines() {
+            let mut words = line.split_whitespace().take_while(|w| !w.starts_with('#'));
+            let (key, value) = match (words.next(), words.next()) {
+                (Some(key), Some(value)) => (key, value),
+                _ => continue,
+            };
+
+            let key = if key.starts_with('.') {
+                format!("*{}", key)
+            } else if key.starts_with('*') {
+                key.to_string()
+            } else {
+                match dircolors_indicator(key) {
+                    Some(indicator) => indicator.to_string(),
+                    None => continue,
+                }
+            };
+            definitions.push(format!("{}={}", key, value));
+        }
+        definitions.join(":")
+    }
+
+    /// Get the colors of the elements of a theme file, each mapped to an RGB triple.
+    fn theme_file_colours(yaml: &str) -> Result<HashMap<Elem, Colour>, serde_yaml::Error> {
+        let triples: BTreeMap<String, [u8; 3]> = serde_yaml::from_str(yaml)?;
+
+        let mut m = HashMap::new();
+        for (key, [r, g, b]) in triples {
+            match Elem::from_theme_key(&key) {
+                Some(elems) => {
+                    for elem in elems {
+                        m.insert(elem, Colour::RGB(r, g, b));
+                    }
+                }
+                None => print_error!("Theme file element {} is unknown, skipping it.", key),
+            }
+        }
+        Ok(m)
+    }
+
     pub fn colorize<'a>(&self, input: String, elem: &Elem) -> ColoredString<'a> {
         self.style(elem).paint(input)
     }
 
+    /// Paint the input with the given colour, rather than with the colour of an [Elem].
+    pub fn colorize_with_colour<'a>(&self, input: String, colour: Colour) -> ColoredString<'a> {
+        match self.colors {
+            Some(_) => Style::default().fg(colour).paint(input),
+            None => Style::default().paint(input),
+        }
+    }
+
+    /// Paint the input on the background of the colour of an [Elem], in black for readability.
+    pub fn colorize_background<'a>(&self, input: String, elem: &Elem) -> ColoredString<'a> {
+        match self.colors {
+            Some(ref colors) => Style::default()
+                .fg(Colour::Black)
+                .on(colors[elem])
+                .paint(input),
+            None => Style::default().paint(input),
+        }
+    }
+
     pub fn colorize_using_path<'a>(
         &self,
         input: String,
@@ -110,6 +340,24 @@ impl Colors {
         }
     }
 
+    /// Colorize the input like [colorize_using_path](Self::colorize_using_path), dimmed to set it
+    /// apart from the surrounding text.
+    pub fn colorize_dimmed_using_path<'a>(
+        &self,
+        input: String,
+        path: &Path,
+        elem: &Elem,
+    ) -> ColoredString<'a> {
+        if self.colors.is_none() && self.lscolors.is_none() {
+            return Style::default().paint(input);
+        }
+
+        let style = self
+            .style_from_path(path)
+            .unwrap_or_else(|| self.style(elem));
+        style.dimmed().paint(input)
+    }
+
     fn style_from_path(&self, path: &Path) -> Option<Style> {
         match &self.lscolors {
             Some(lscolors) => lscolors
@@ -167,6 +415,7 @@ impl Colors {
             Elem::BlockDevice => Some("bd"),
             Elem::CharDevice => Some("cd"),
             Elem::BrokenSymLink => Some("or"),
+            Elem::MissingTarget => Some("mi"),
             Elem::INode { valid } => match valid {
                 true => Some("so"),
                 false => Some("no"),
@@ -199,6 +448,12 @@ impl Colors {
         m.insert(Elem::Exec, Colour::Red);
         m.insert(Elem::ExecSticky, Colour::Purple);
         m.insert(Elem::NoAccess, Colour::Fixed(245)); // Grey
+        m.insert(Elem::Octal, Colour::Fixed(6)); // Teal
+
+        // Permission triads of --permission-heatmap
+        m.insert(Elem::TriadFull, Colour::Fixed(40)); // Green3
+        m.insert(Elem::TriadPartial, Colour::Fixed(184)); // Yellow3
+        m.insert(Elem::TriadNone, Colour::Fixed(124)); // Red3
 
         // File Types
         m.insert(
@@ -234,6 +489,7 @@ impl Colors {
         m.insert(Elem::Pipe, Colour::Fixed(44)); // DarkTurquoise
         m.insert(Elem::SymLink, Colour::Fixed(44)); // DarkTurquoise
         m.insert(Elem::BrokenSymLink, Colour::Fixed(124)); // Red3
+        m.insert(Elem::MissingTarget, Colour::Fixed(203)); // IndianRed1
         m.insert(Elem::BlockDevice, Colour::Fixed(44)); // DarkTurquoise
         m.insert(Elem::CharDevice, Colour::Fixed(172)); // Orange3
         m.insert(Elem::Socket, Colour::Fixed(44)); // DarkTurquoise
@@ -249,15 +505,173 @@ impl Colors {
         m.insert(Elem::FileSmall, Colour::Fixed(229)); // Wheat1
         m.insert(Elem::FileMedium, Colour::Fixed(216)); // LightSalmon1
         m.insert(Elem::FileLarge, Colour::Fixed(172)); // Orange3
+        m.insert(Elem::SizeUnit, Colour::Fixed(245)); // Grey
+        m.insert(Elem::SparseFile, Colour::Fixed(75)); // SteelBlue1
 
         // INode
         m.insert(Elem::INode { valid: true }, Colour::Fixed(13)); // Pink
         m.insert(Elem::INode { valid: false }, Colour::Fixed(245)); // Grey
         m.insert(Elem::Links { valid: true }, Colour::Fixed(13));
         m.insert(Elem::Links { valid: false }, Colour::Fixed(245));
+        m.insert(Elem::SingleLink, Colour::Fixed(240)); // Grey35
+
+        // Depth
+        m.insert(Elem::Depth, Colour::Fixed(245)); // Grey
+
+        // Time between the creation and the last modification
+        m.insert(Elem::AgeGap, Colour::Fixed(36)); // DarkCyan
+
+        // Device
+        m.insert(Elem::Device { valid: true }, Colour::Fixed(13)); // Pink
+        m.insert(Elem::Device { valid: false }, Colour::Fixed(245)); // Grey
+
+        // Canonical absolute path
+        m.insert(Elem::RealPath, Colour::Fixed(245)); // Grey
+
+        // Number of the entries of a directory
+        m.insert(Elem::FileCount, Colour::Fixed(13)); // Pink
+
+        // Git status summary
+        m.insert(Elem::GitStatus { clean: true }, Colour::Fixed(40)); // Green3
+        m.insert(Elem::GitStatus { clean: false }, Colour::Fixed(214)); // Orange1
+
+        // Git status of the files
+        m.insert(Elem::GitStaged, Colour::Fixed(40)); // Green3
+        m.insert(Elem::GitModified, Colour::Fixed(214)); // Orange1
+        m.insert(Elem::GitUntracked, Colour::Fixed(160)); // Red3
+        m.insert(Elem::GitIgnored, Colour::Fixed(245)); // Grey
+
+        // Checksum verification
+        m.insert(Elem::ChecksumMatch, Colour::Fixed(40)); // Green3
+        m.insert(Elem::ChecksumMismatch, Colour::Fixed(124)); // Red3
+        m.insert(Elem::ChecksumMissing, Colour::Fixed(245)); // Grey
 
         // TODO add this after we can use file to configure theme
         // m.insert(Elem::TreeEdge, Colour::Fixed(44)); // DarkTurquoise
         m
     }
 }
+
+/// Get the `LS_COLORS` indicator of a dircolors `keyword`, [None] for the other keywords.
+fn dircolors_indicator(keyword: &str) -> Option<&'static str> {
+    let indicator = match keyword.to_ascii_uppercase().as_str() {
+        "NORMAL" | "NORM" => "no",
+        "FILE" => "fi",
+        "RESET" => "rs",
+        "DIR" => "di",
+        "LINK" | "LNK" | "SYMLINK" => "ln",
+        "ORPHAN" => "or",
+        "MISSING" => "mi",
+        "FIFO" | "PIPE" => "pi",
+        "SOCK" => "so",
+        "BLOCK" | "BLK" => "bd",
+        "CHAR" | "CHR" => "cd",
+        "DOOR" => "do",
+        "EXEC" => "ex",
+        "LEFT" | "LEFTCODE" => "lc",
+        "RIGHT" | "RIGHTCODE" => "rc",
+        "END" | "ENDCODE" => "ec",
+        "SUID" | "SETUID" => "su",
+        "SGID" | "SETGID" => "sg",
+        "STICKY" => "st",
+        "OTHER_WRITABLE" | "OWR" => "ow",
+        "STICKY_OTHER_WRITABLE" | "OWT" => "tw",
+        "CAPABILITY" => "ca",
+        "MULTIHARDLINK" => "mh",
+        "CLRTOEOL" => "cl",
+        _ => return None,
+    };
+    Some(indicator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Colors, Elem, Theme};
+    use ansi_term::Colour;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_theme_file_colours() {
+        let colours = Colors::theme_file_colours("dir: [38, 139, 210]\nneon: [1, 2, 3]\n").unwrap();
+        assert_eq!(2, colours.len());
+        assert_eq!(
+            Some(&Colour::RGB(38, 139, 210)),
+            colours.get(&Elem::Dir { uid: false })
+        );
+        assert_eq!(
+            Some(&Colour::RGB(38, 139, 210)),
+            colours.get(&Elem::Dir { uid: true })
+        );
+    }
+
+    #[test]
+    fn test_theme_file_colours_not_triples() {
+        assert!(Colors::theme_file_colours("dir: [38, 139]\n").is_err());
+        assert!(Colors::theme_file_colours("dir: blue\n").is_err());
+    }
+
+    #[test]
+    fn test_with_theme_file() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let path = tmp_dir.path().join("theme.yaml");
+        fs::write(&path, "dir: [38, 139, 210]\n").unwrap();
+
+        let colors = Colors::new(Theme::NoLscolors).with_theme_file(&path);
+        assert_eq!(
+            Colour::RGB(38, 139, 210).paint("sub").to_string(),
+            colors
+                .colorize(String::from("sub"), &Elem::Dir { uid: false })
+                .to_string()
+        );
+        // the missing elements keep their color
+        assert_eq!(
+            Colour::Fixed(230).paint("user").to_string(),
+            colors
+                .colorize(String::from("user"), &Elem::User)
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_theme_file_missing() {
+        let colors = Colors::new(Theme::NoLscolors).with_theme_file("missing.yaml".as_ref());
+        assert_eq!(
+            Colour::Fixed(33).paint("sub").to_string(),
+            colors
+                .colorize(String::from("sub"), &Elem::Dir { uid: false })
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_dircolors_definitions() {
+        let database = "# a comment\nTERM xterm*\nDIR 01;34 # the directories\n.rs 00;31\n*README 01;33\nCOLOR tty\n";
+        assert_eq!(
+            "di=01;34:*.rs=00;31:*README=01;33",
+            Colors::dircolors_definitions(database)
+        );
+    }
+
+    #[test]
+    fn test_with_ls_colors_file() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let path = tmp_dir.path().join("dircolors");
+        fs::write(&path, ".rs 01;31\n").unwrap();
+
+        let colors = Colors::new(Theme::Default).with_ls_colors_file(&path);
+        assert_eq!(
+            Colour::Red.bold().paint("main.rs").to_string(),
+            colors
+                .colorize_using_path(
+                    String::from("main.rs"),
+                    &tmp_dir.path().join("main.rs"),
+                    &Elem::File {
+                        exec: false,
+                        uid: false
+                    }
+                )
+                .to_string()
+        );
+    }
+}
@@ -1,13 +1,19 @@
 ///! This module provides methods to handle the program's config files and operations related to
 ///! this.
-use crate::flags::color::ColorOption;
+use crate::flags::color::{ColorOption, ColorTheme};
 use crate::flags::display::Display;
-use crate::flags::icons::{IconOption, IconTheme};
+use crate::flags::hyperlink::HyperlinkOption;
+use crate::flags::icons::{IconFileType, IconOption, IconTheme};
 use crate::flags::layout::Layout;
+use crate::flags::line_ending::LineEnding;
+use crate::flags::permission::PermissionFlag;
 use crate::flags::size::SizeFlag;
-use crate::flags::sorting::{DirGrouping, SortColumn};
+use crate::flags::sorting::{DirGrouping, SortColumn, SortDotfiles};
+use crate::flags::time::TimeFlag;
+use crate::flags::time_zone::TimeZoneFlag;
 use crate::print_error;
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use serde::Deserialize;
@@ -26,25 +32,63 @@ const YAML_LONG_EXT: &str = "yaml";
 pub struct Config {
     pub classic: Option<bool>,
     pub blocks: Option<Vec<String>>,
+    pub bundle_extensions: Option<Vec<String>>,
     pub color: Option<Color>,
     pub date: Option<String>,
     pub dereference: Option<bool>,
+    pub follow_dir_symlinks: Option<bool>,
     pub display: Option<Display>,
+    pub hyperlink: Option<HyperlinkOption>,
     pub icons: Option<Icons>,
     pub ignore_globs: Option<Vec<String>>,
     pub indicators: Option<bool>,
     pub layout: Option<Layout>,
+    pub line_ending: Option<LineEnding>,
     pub recursion: Option<Recursion>,
     pub size: Option<SizeFlag>,
+    pub size_units: Option<HashMap<String, String>>,
     pub sorting: Option<Sorting>,
     pub no_symlink: Option<bool>,
+    pub permission: Option<PermissionFlag>,
     pub total_size: Option<bool>,
     pub symlink_arrow: Option<String>,
+    pub glyphs: Option<Glyphs>,
+    pub git_symbols: Option<GitSymbols>,
+    pub theme: Option<String>,
+    pub time: Option<TimeFlag>,
+    pub time_zone: Option<TimeZoneFlag>,
 }
 
 #[derive(Eq, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Glyphs {
+    pub no_access: Option<String>,
+    pub broken: Option<String>,
+    #[serde(rename = "loop")]
+    pub cycle: Option<String>,
+}
+
+#[derive(Eq, PartialEq, Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct GitSymbols {
+    pub unmodified: Option<String>,
+    pub untracked: Option<String>,
+    pub ignored: Option<String>,
+    pub added: Option<String>,
+    pub modified: Option<String>,
+    pub deleted: Option<String>,
+    pub renamed: Option<String>,
+    pub copied: Option<String>,
+    pub type_changed: Option<String>,
+    pub conflicted: Option<String>,
+}
+
+#[derive(Eq, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub struct Color {
     pub when: ColorOption,
+    pub theme: Option<ColorTheme>,
+    pub ls_colors_file: Option<String>,
 }
 
 #[derive(Eq, PartialEq, Debug, Deserialize)]
@@ -52,6 +96,10 @@ pub struct Icons {
     pub when: Option<IconOption>,
     pub theme: Option<IconTheme>,
     pub separator: Option<String>,
+    pub spacing: Option<usize>,
+    pub disabled: Option<Vec<IconFileType>>,
+    pub name: Option<HashMap<String, String>>,
+    pub extension: Option<HashMap<String, String>>,
 }
 
 #[derive(Eq, PartialEq, Debug, Deserialize)]
@@ -66,6 +114,9 @@ pub struct Sorting {
     pub column: Option<SortColumn>,
     pub reverse: Option<bool>,
     pub dir_grouping: Option<DirGrouping>,
+    pub interleave_dirs: Option<bool>,
+    pub version_ignore_case: Option<bool>,
+    pub dotfiles: Option<SortDotfiles>,
 }
 
 impl Config {
@@ -74,20 +125,31 @@ impl Config {
         Self {
             classic: None,
             blocks: None,
+            bundle_extensions: None,
             color: None,
             date: None,
             dereference: None,
+            follow_dir_symlinks: None,
             display: None,
+            hyperlink: None,
             icons: None,
             ignore_globs: None,
             indicators: None,
             layout: None,
+            line_ending: None,
             recursion: None,
             size: None,
+            size_units: None,
             sorting: None,
             no_symlink: None,
+            permission: None,
             total_size: None,
             symlink_arrow: None,
+            glyphs: None,
+            git_symbols: None,
+            theme: None,
+            time: None,
+            time_zone: None,
         }
     }
 
@@ -170,7 +232,9 @@ classic: false
 # == Blocks ==
 # This specifies the columns and their order when using the long and the tree
 # layout.
-# Possible values: permission, user, group, size, size_value, date, name, inode
+# Possible values: permission, user, group, size, size_value, date, name, inode,
+# links, depth, free_inodes, size_percent, size_bar, git_dir_summary, git,
+# age_gap, device, realpath, file_count
 blocks:
   - permission
   - user
@@ -179,6 +243,14 @@ blocks:
   - date
   - name
 
+# == Bundle Extensions ==
+# A list of extensions of the directories to show as single entries, without
+# listing their content, like the bundles on macOS.
+# bundle-extensions:
+#   - app
+#   - bundle
+#   - framework
+
 # == Color ==
 # This has various color options. (Will be expanded in the future.)
 color:
@@ -186,6 +258,13 @@ color:
   # When "classic" is set, this is set to "never".
   # Possible values: never, auto, always
   when: auto
+  # Which colors to use, "fixed" ignoring the LS_COLORS environment variable.
+  # This overrides the color theme of the "theme".
+  # Possible values: dark, fixed
+  # theme: dark
+  # The dircolors database to read the LS_COLORS from, instead of the
+  # environment variable.
+  # ls-colors-file: /etc/DIR_COLORS
 
 # == Date ==
 # This specifies the date format for the date column. The freeform format
@@ -194,16 +273,48 @@ color:
 # Possible values: date, relative, +<date_format>
 # date: date
 
+# == Time ==
+# Which time stamp of the files the date column shows and sorts by. Without
+# the birth time, "created" falls back to "modified".
+# When "classic" is set, this is set to "modified".
+# Possible values: modified, accessed, created
+# time: modified
+
+# == Time Zone ==
+# In which time zone to display the dates, the local one being the one of the
+# TZ environment variable if set.
+# Possible values: local, utc
+# time-zone: local
+
+# == Permission ==
+# How to display the permissions, rwx letters or four octal digits like 0755.
+# When "classic" is set, this is set to "rwx".
+# Possible values: rwx, octal
+# permission: rwx
+
 # == Dereference ==
 # Whether to dereference symbolic links.
 # Possible values: false, true
 dereference: false
 
+# == Follow Dir Symlinks ==
+# Whether to dereference symbolic links pointing to directories, listing their
+# content with --long while leaving other symbolic links untouched. The other
+# layouts already list this content, showing the links as directories here.
+# Possible values: false, true
+follow-dir-symlinks: false
+
 # == Display ==
 # What items to display. Do not specify this for the default behavior.
 # Possible values: all, almost-all, directory-only
 # display: all
 
+# == Hyperlink ==
+# When to make the file names links to the files, for the terminals able to
+# open them. When "classic" is set, this is set to "never".
+# Possible values: always, auto, never
+hyperlink: never
+
 # == Icons ==
 icons:
   # When to use icons.
@@ -214,8 +325,22 @@ icons:
   # Possible values: fancy, unicode
   theme: fancy
   # The string between the icons and the name.
-  # Possible values: any string (eg: " |")
+  # Possible values: any string one or two columns wide (eg: " |")
   separator: " "
+  # The number of spaces after the icons, in place of the separator, for the
+  # fonts whose icons overlap the names.
+  # spacing: 2
+  # The file types to display without an icon.
+  # Possible values: file, dir, symlink, socket, pipe, char-device, block-device, special
+  # disabled:
+  #   - socket
+  #   - pipe
+  # The icons to use for some file names and extensions, on top of the default
+  # ones. Each icon is a single glyph, or its codepoint like '\uf0e7'.
+  # name:
+  #   .trash: '\uf1f8'
+  # extension:
+  #   zig: '\uf0e7'
 
 # == Ignore Globs ==
 # A list of globs to ignore when listing.
@@ -233,6 +358,11 @@ indicators: false
 # Possible values: grid, tree, oneline
 layout: grid
 
+# == Line ending ==
+# Which characters to end the output lines with.
+# Possible values: lf, crlf
+line-ending: lf
+
 # == Recursion ==
 recursion:
   # Whether to enable recursion.
@@ -247,10 +377,18 @@ recursion:
 # Possible values: default, short, bytes
 size: default
 
+# == Size units ==
+# The units in which to display the sizes of the files with these extensions,
+# whatever their magnitude. Overridden by the "bytes" size.
+# Possible values: B, KB, MB, GB, TB
+# size-units:
+#   mp4: MB
+#   flac: MB
+
 # == Sorting ==
 sorting:
   # Specify what to sort by.
-  # Possible values: extension, name, time, size, version
+  # Possible values: extension, name, time, created, size, version
   column: name
   # Whether to reverse the sorting.
   # Possible values: false, true
@@ -259,6 +397,17 @@ sorting:
   # When "classic" is set, this is set to "none".
   # Possible values: first, last, none
   dir-grouping: none
+  # Whether to keep directories among the files, whatever "dir-grouping" and
+  # the "--group-dirs" argument say.
+  # Possible values: false, true
+  # interleave-dirs: false
+  # Whether the "version" column compares the names ignoring their case.
+  # Possible values: false, true
+  # version-ignore-case: false
+  # Where to place the dotfiles, whatever the column. By default they are left
+  # where the column puts them.
+  # Possible values: first, last, mixed
+  # dotfiles: first
 
 # == No Symlink ==
 # Whether to omit showing symlink targets
@@ -273,6 +422,40 @@ total-size: false
 # == Symlink arrow ==
 # Specifies how the symlink arrow display, chars in both ascii and utf8
 symlink-arrow: ⇒
+
+# == Glyphs ==
+# The glyphs marking the symlinks whose target can not be accessed, is missing
+# or is part of a loop, and the directories whose content can not be read or
+# loops back to a listed one. They are empty by default, leaving the states
+# unmarked, like `?`, `✗` and `∞` can be used.
+glyphs:
+  no-access: ""
+  broken: ""
+  loop: ""
+
+# == Git symbols ==
+# The symbols of the statuses shown by the git block. The unmodified, untracked
+# and ignored symbols replace both letters of the status, the other ones the
+# letter of the index or of the work tree.
+git-symbols:
+  unmodified: "--"
+  untracked: "??"
+  ignored: "!!"
+  added: A
+  modified: M
+  deleted: D
+  renamed: R
+  copied: C
+  type-changed: T
+  conflicted: U
+
+# == Theme ==
+# A bundled pair of color and icon themes, overridden by "color.theme" and
+# "icons.theme".
+# Possible values: dark-fancy, dark-unicode, fixed-fancy, fixed-unicode
+# Otherwise the path of a YAML file mapping the colored elements to RGB
+# triples, like `dir: [38, 139, 210]`.
+# theme: dark-fancy
 "#;
 
 #[cfg(test)]
@@ -287,8 +470,10 @@ mod tests {
     use super::Config;
     use crate::config_file;
     use crate::flags::color::ColorOption;
+    use crate::flags::hyperlink::HyperlinkOption;
     use crate::flags::icons::{IconOption, IconTheme};
     use crate::flags::layout::Layout;
+    use crate::flags::line_ending::LineEnding;
     use crate::flags::size::SizeFlag;
     use crate::flags::sorting::{DirGrouping, SortColumn};
 
@@ -309,33 +494,68 @@ mod tests {
                     ]
                     .into()
                 ),
+                bundle_extensions: None,
                 color: Some(config_file::Color {
                     when: ColorOption::Auto,
+                    theme: None,
+                    ls_colors_file: None,
                 }),
                 date: None,
                 dereference: Some(false),
+                follow_dir_symlinks: Some(false),
                 display: None,
+                hyperlink: Some(HyperlinkOption::Never),
                 icons: Some(config_file::Icons {
                     when: Some(IconOption::Auto),
                     theme: Some(IconTheme::Fancy),
                     separator: Some(" ".to_string()),
+                    spacing: None,
+                    disabled: None,
+                    name: None,
+                    extension: None,
                 }),
                 ignore_globs: None,
                 indicators: Some(false),
                 layout: Some(Layout::Grid),
+                line_ending: Some(LineEnding::Lf),
                 recursion: Some(config_file::Recursion {
                     enabled: Some(false),
                     depth: None,
                 }),
                 size: Some(SizeFlag::Default),
+                size_units: None,
                 sorting: Some(config_file::Sorting {
                     column: Some(SortColumn::Name),
                     reverse: Some(false),
                     dir_grouping: Some(DirGrouping::None),
+                    interleave_dirs: None,
+                    version_ignore_case: None,
+                    dotfiles: None,
                 }),
                 no_symlink: Some(false),
+                permission: None,
                 total_size: Some(false),
                 symlink_arrow: Some("⇒".into()),
+                glyphs: Some(config_file::Glyphs {
+                    no_access: Some("".into()),
+                    broken: Some("".into()),
+                    cycle: Some("".into()),
+                }),
+                git_symbols: Some(config_file::GitSymbols {
+                    unmodified: Some("--".into()),
+                    untracked: Some("??".into()),
+                    ignored: Some("!!".into()),
+                    added: Some("A".into()),
+                    modified: Some("M".into()),
+                    deleted: Some("D".into()),
+                    renamed: Some("R".into()),
+                    copied: Some("C".into()),
+                    type_changed: Some("T".into()),
+                    conflicted: Some("U".into()),
+                }),
+                theme: None,
+                time: None,
+                time_zone: None,
             },
             c
         );
@@ -1,10 +1,15 @@
 use crate::color::{self, Colors};
 use crate::display;
-use crate::flags::{ColorOption, Display, Flags, IconOption, IconTheme, Layout, SortOrder};
+use crate::flags::{
+    ColorOption, ColorTheme, Display, Flags, HyperlinkOption, IconOption, IconTheme, Layout,
+    OutputFormat, SortOrder,
+};
 use crate::icon::{self, Icons};
-use crate::meta::Meta;
-use crate::{print_error, print_output, sort};
-use std::path::PathBuf;
+use crate::meta::{archive, budget, progress, FileType, Meta, Owner, UsersCache};
+use crate::{output, print_error, print_output, sort};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 #[cfg(not(target_os = "windows"))]
 use std::io;
@@ -20,10 +25,12 @@ pub struct Core {
     //display: Display,
     colors: Colors,
     sorters: Vec<(SortOrder, sort::SortFn)>,
+    /// The owner names resolved during the run.
+    users: UsersCache,
 }
 
 impl Core {
-    pub fn new(flags: Flags) -> Self {
+    pub fn new(mut flags: Flags) -> Self {
         // Check through libc if stdout is a tty. Unix specific so not on windows.
         // Determine color output availability (and initialize color output (for Windows 10))
         #[cfg(not(target_os = "windows"))]
@@ -38,10 +45,23 @@ impl Core {
         #[cfg(target_os = "windows")]
         let console_color_ok = ansi_term::enable_ansi_support().is_ok();
 
+        // the names only tell whether to link, so the terminal is checked once here
+        if flags.hyperlink == HyperlinkOption::Auto {
+            flags.hyperlink = if tty_available {
+                HyperlinkOption::Always
+            } else {
+                HyperlinkOption::Never
+            };
+        }
+
         let mut inner_flags = flags.clone();
 
         let color_theme = match (tty_available && console_color_ok, flags.color.when) {
             (_, ColorOption::Never) | (false, ColorOption::Auto) => color::Theme::NoColor,
+            // the colors of a theme file are the ones asked for, whatever the LS_COLORS
+            _ if flags.color.theme == ColorTheme::Fixed || flags.color.file.is_some() => {
+                color::Theme::NoLscolors
+            }
             _ => color::Theme::Default,
         };
 
@@ -51,7 +71,17 @@ impl Core {
             (_, _, IconTheme::Unicode) => icon::Theme::Unicode,
         };
 
-        let icon_separator = flags.icons.separator.0.clone();
+        if flags.font_check.0 && icon_theme == icon::Theme::Fancy {
+            if let Some(hint) = icon::font_hint(|name| std::env::var(name).ok()) {
+                print_error!("{}", hint);
+            }
+        }
+
+        let icon_separator = flags.icons.gap();
+        let icons = Icons::new(icon_theme, icon_separator)
+            .with_bundle_extensions(flags.bundle_extensions.clone())
+            .with_disabled(flags.icons.disabled.clone())
+            .with_overrides(flags.icons.overrides.clone());
 
         if !tty_available {
             // The output is not a tty, this means the command is piped. (ex: lsd -l | less)
@@ -63,43 +93,112 @@ impl Core {
 
         let sorters = sort::assemble_sorters(&flags);
 
+        Owner::set_lookup_timeout(flags.owner_lookup_timeout.0.map(Duration::from_millis));
+
+        if flags.progress.0 {
+            progress::enable();
+        }
+
+        if let Some(limit) = flags.scan_budget.0 {
+            budget::start(limit);
+        }
+
+        let mut colors = Colors::new(color_theme);
+        if let Some(file) = &flags.color.file {
+            colors = colors.with_theme_file(file);
+        }
+        if let Some(file) = &flags.color.ls_colors_file {
+            colors = colors.with_ls_colors_file(file);
+        }
+
         Self {
             flags,
             //display: Display::new(inner_flags),
-            colors: Colors::new(color_theme),
-            icons: Icons::new(icon_theme, icon_separator),
+            colors,
+            icons,
             sorters,
+            users: UsersCache::new(),
         }
     }
 
     pub fn run(self, paths: Vec<PathBuf>) {
+        if self.flags.legend.0 {
+            print_output!("{}", display::legend(&self.colors, &self.icons));
+            return;
+        }
+
         let mut meta_list = self.fetch(paths);
+        progress::finish();
+        if let Some(note) = budget::finish() {
+            print_error!("{}.", note);
+        }
 
-        self.sort(&mut meta_list);
+        match self.flags.top.0 {
+            Some(count) => meta_list = largest_files(meta_list, count),
+            None => self.sort(&mut meta_list),
+        }
         self.display(&meta_list)
     }
 
     fn fetch(&self, paths: Vec<PathBuf>) -> Vec<Meta> {
         let mut meta_list = Vec::with_capacity(paths.len());
+        // the archive sizes come from their headers, they can not be totalled from the disk
+        let mut archive_list = Vec::new();
         let depth = match self.flags.layout {
+            // the whole trees unless --depth limits them, its default being unlimited
+            _ if self.flags.top.0.is_some() => self.flags.recursion.depth,
             Layout::Tree { .. } => self.flags.recursion.depth,
             _ if self.flags.recursion.enabled => self.flags.recursion.depth,
             _ => 1,
         };
 
         for path in paths {
-            let mut meta = match Meta::from_path(&path, self.flags.dereference.0) {
+            let mut meta = match Meta::from_path_with_time(
+                &path,
+                self.flags.dereference.0,
+                self.flags.time,
+                &self.users,
+            ) {
                 Ok(meta) => meta,
                 Err(err) => {
                     print_error!("{}: {}.", path.display(), err);
                     continue;
                 }
             };
+            meta.count_entries(&self.flags);
+            meta.read_git_status(&self.flags);
+
+            if self.flags.archive.0 && meta.path.is_file() && archive::is_archive(&meta.path) {
+                match archive::read_into(&mut meta, depth, &self.flags) {
+                    Ok(()) => archive_list.push(meta),
+                    Err(err) => print_error!("{}: {}.", path.display(), err),
+                }
+                continue;
+            }
+
+            let recurse = (self.flags.layout == Layout::Tree
+                || self.flags.display != Display::DirectoryOnly)
+                && (!self.flags.content_on_trailing_slash.0 || lists_content_of(&path));
+
+            // list the content of the link target, while keeping the link as the listed entry
+            if recurse
+                && self.flags.dereference_command_line_symlink_to_dir.0
+                && meta.file_type == (FileType::SymLink { is_dir: true })
+            {
+                match Meta::from_path(&path, true, &self.users)
+                    .and_then(|t| t.recurse_into(depth, &self.flags, &self.users))
+                {
+                    Ok(content) => {
+                        meta.content = content;
+                        meta_list.push(meta);
+                    }
+                    Err(err) => print_error!("lsd: {}: {}\n", path.display(), err),
+                };
+                continue;
+            }
 
-            let recurse =
-                self.flags.layout == Layout::Tree || self.flags.display != Display::DirectoryOnly;
             if recurse {
-                match meta.recurse_into(depth, &self.flags) {
+                match meta.recurse_into(depth, &self.flags, &self.users) {
                     Ok(content) => {
                         meta.content = content;
                         meta_list.push(meta);
@@ -115,9 +214,14 @@ impl Core {
         }
         if self.flags.total_size.0 {
             for meta in &mut meta_list.iter_mut() {
-                meta.calculate_total_size();
+                meta.calculate_total_size(&self.flags.ignore_globs);
             }
         }
+        meta_list.append(&mut archive_list);
+
+        if self.flags.dedup.0 {
+            dedup(&mut meta_list);
+        }
 
         meta_list
     }
@@ -125,6 +229,12 @@ impl Core {
     fn sort(&self, metas: &mut Vec<Meta>) {
         metas.sort_unstable_by(|a, b| sort::by_meta(&self.sorters, a, b));
 
+        if let Some(command) = &self.flags.sort_command.0 {
+            if let Err(err) = sort::by_command(command, metas) {
+                print_error!("sort command '{}': {}.", command, err);
+            }
+        }
+
         for meta in metas {
             if let Some(ref mut content) = meta.content {
                 self.sort(content);
@@ -133,12 +243,74 @@ impl Core {
     }
 
     fn display(&self, metas: &[Meta]) {
-        let output = if self.flags.layout == Layout::Tree {
+        let output = if self.flags.output_format == OutputFormat::Csv {
+            output::csv(metas, &self.flags)
+        } else if self.flags.output_format == OutputFormat::Json {
+            output::json(metas, &self.flags)
+        } else if self.flags.output_format == OutputFormat::Raw {
+            output::raw(metas, &self.flags)
+        } else if self.flags.output_format == OutputFormat::Html {
+            output::html(metas, &self.flags, &self.colors, &self.icons)
+        } else if self.flags.layout == Layout::Tree {
             display::tree(&metas, &self.flags, &self.colors, &self.icons)
         } else {
             display::grid(&metas, &self.flags, &self.colors, &self.icons)
         };
 
         print_output!("{}", output);
+
+        // the machine readable outputs are kept parseable
+        if self.flags.type_histogram.0 && self.flags.output_format == OutputFormat::Text {
+            print_output!("\n{}", display::type_histogram(metas, &self.colors));
+        }
+    }
+}
+
+/// Check if the input asks for its content with --content-on-trailing-slash, by ending with a
+/// path separator or by being the current, parent or root directory.
+fn lists_content_of(input: &Path) -> bool {
+    input.file_name().is_none() || input.to_string_lossy().ends_with(std::path::is_separator)
+}
+
+/// Drop the listed `metas` that are the same file as an earlier one, sharing its device and inode,
+/// like the hard links or the paths given twice.
+fn dedup(metas: &mut Vec<Meta>) {
+    let mut seen = HashSet::new();
+    metas.retain(|meta| match (meta.device.id(), meta.inode.index()) {
+        (Some(device), Some(index)) => seen.insert((device, index)),
+        _ => true,
+    });
+}
+
+/// Get the `count` largest files among the `metas` and their whole content, by decreasing
+/// apparent size, the directories being left out. There is no flag selecting the disk usage as
+/// the size, so the allocated sizes are not used for the ranking.
+fn largest_files(metas: Vec<Meta>, count: usize) -> Vec<Meta> {
+    let mut files = Vec::new();
+    collect_files(metas, &mut files);
+
+    let by_size = |a: &Meta, b: &Meta| {
+        b.size
+            .get_bytes()
+            .cmp(&a.size.get_bytes())
+            .then_with(|| a.path.cmp(&b.path))
+    };
+    // only the largest ones are sorted
+    if files.len() > count {
+        files.select_nth_unstable_by(count, by_size);
+        files.truncate(count);
+    }
+    files.sort_unstable_by(by_size);
+    files
+}
+
+/// Move the regular files among the `metas` and their content into `files`.
+fn collect_files(metas: Vec<Meta>, files: &mut Vec<Meta>) {
+    for mut meta in metas {
+        match meta.content.take() {
+            Some(content) => collect_files(content, files),
+            None if matches!(meta.file_type, FileType::File { .. }) => files.push(meta),
+            None => {}
+        }
     }
 }
@@ -1,26 +1,32 @@
-use crate::color::{ColoredString, Colors};
-use crate::flags::{Block, Display, Flags, Layout};
+use crate::color::{ColoredString, Colors, Elem};
+use crate::flags::{Block, Display, Flags, Layout, LineEnding, PermissionFlag};
 use crate::icon::Icons;
+use crate::meta::checksum::Checksum;
 use crate::meta::name::DisplayOption;
-use crate::meta::{FileType, Meta};
+use crate::meta::{Access, DateSection, FileType, Meta, Name, RealPath, Size};
+use crate::print_error;
 use ansi_term::{ANSIString, ANSIStrings};
-use std::collections::HashMap;
-use term_grid::{Cell, Direction, Filling, Grid, GridOptions};
+use chrono::Local;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use term_grid::{Cell, Direction, Display as GridDisplay, Filling, Grid, GridOptions};
 use terminal_size::terminal_size;
-use unicode_width::UnicodeWidthStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 const EDGE: &str = "\u{251c}\u{2500}\u{2500}"; // "├──"
 const LINE: &str = "\u{2502}  "; // "│  "
 const CORNER: &str = "\u{2514}\u{2500}\u{2500}"; // "└──"
 const BLANK: &str = "   ";
 
+const VARIATION_SELECTOR_EMOJI: char = '\u{fe0f}';
+
 pub fn grid(metas: &[Meta], flags: &Flags, colors: &Colors, icons: &Icons) -> String {
     let term_width = match terminal_size() {
         Some((w, _)) => Some(w.0 as usize),
         None => None,
     };
 
-    inner_display_grid(
+    let output = inner_display_grid(
         &DisplayOption::None,
         metas,
         &flags,
@@ -28,7 +34,9 @@ pub fn grid(metas: &[Meta], flags: &Flags, colors: &Colors, icons: &Icons) -> St
         icons,
         0,
         term_width,
-    )
+    );
+
+    truncate_lines(output, flags)
 }
 
 pub fn tree(metas: &[Meta], flags: &Flags, colors: &Colors, icons: &Icons) -> String {
@@ -46,11 +54,27 @@ pub fn tree(metas: &[Meta], flags: &Flags, colors: &Colors, icons: &Icons) -> St
         }
     }
 
-    for cell in inner_display_tree(metas, &flags, colors, icons, (0, ""), &padding_rules, index) {
+    // produce one more row than the cap, to know whether the output has to be truncated
+    let max_rows = flags.max_lines.0.map(|max| max + 1);
+    for cell in inner_display_tree(
+        metas,
+        &flags,
+        colors,
+        icons,
+        (0, ""),
+        &padding_rules,
+        index,
+        max_rows,
+    ) {
         grid.add(cell);
     }
 
-    grid.fit_into_columns(flags.blocks.0.len()).to_string()
+    let output = with_line_ending(
+        grid.fit_into_columns(flags.blocks.0.len()).to_string(),
+        flags,
+    );
+
+    truncate_lines(output, flags)
 }
 
 fn inner_display_grid(
@@ -61,10 +85,77 @@ fn inner_display_grid(
     icons: &Icons,
     depth: usize,
     term_width: Option<usize>,
+) -> String {
+    let line_ending = flags.line_ending.as_str();
+    // the total heads the listing of the directories, not the one of the inputs
+    let total = if flags.total.0 && depth > 0 {
+        format!("total {}{}", get_total_allocated(metas, flags), line_ending)
+    } else {
+        String::new()
+    };
+
+    // the entries of the directories are split by --group-by-date, not the inputs
+    if !flags.group_by_date.0 || depth == 0 {
+        return total
+            + &display_grid_entries(
+                display_option,
+                metas,
+                flags,
+                colors,
+                icons,
+                depth,
+                term_width,
+            );
+    }
+
+    let sections = date_sections(metas)
+        .into_iter()
+        .map(|(section, metas)| {
+            let entries = display_grid_entries(
+                display_option,
+                &metas,
+                flags,
+                colors,
+                icons,
+                depth,
+                term_width,
+            );
+            format!("{}:{}{}", section.label(), line_ending, entries)
+        })
+        .collect::<Vec<_>>()
+        .join(line_ending);
+    total + &sections
+}
+
+/// Split the `metas` into the sections of their dates relative to now, keeping their order.
+fn date_sections(metas: &[Meta]) -> BTreeMap<DateSection, Vec<Meta>> {
+    let now = Local::now();
+    let mut sections: BTreeMap<DateSection, Vec<Meta>> = BTreeMap::new();
+    for meta in metas {
+        sections
+            .entry(meta.date.section(&now))
+            .or_default()
+            .push(meta.clone());
+    }
+    sections
+}
+
+/// Lay the `metas` out on the grid, followed by the content of their directories.
+fn display_grid_entries(
+    display_option: &DisplayOption,
+    metas: &[Meta],
+    flags: &Flags,
+    colors: &Colors,
+    icons: &Icons,
+    depth: usize,
+    term_width: Option<usize>,
 ) -> String {
     let mut output = String::new();
 
     let padding_rules = get_padding_rules(&metas, flags);
+    let dir_total = get_total_size(metas, flags);
+    let max_size = get_max_size(metas, flags);
+    let date_range = get_date_range(metas, flags);
     let mut grid = match flags.layout {
         Layout::OneLine => Grid::new(GridOptions {
             filling: Filling::Spaces(1),
@@ -81,14 +172,17 @@ fn inner_display_grid(
     // asked to display the directory itself (rather than its contents).
     let skip_dirs = (depth == 0) && (flags.display != Display::DirectoryOnly);
 
+    let mut cell_count: usize = 0;
+    // the cells are kept aside to lay them out from right to left for --rtl
+    let mut rtl_cells = Vec::new();
+
+    // the blocks of the previous row, for --collapse-repeats
+    let mut previous_row = Vec::new();
+
     // print the files first.
     for meta in metas {
         // Maybe skip showing the directory meta now; show its contents later.
-        if skip_dirs
-            && (matches!(meta.file_type, FileType::Directory { .. })
-                || (matches!(meta.file_type, FileType::SymLink { is_dir: true })
-                    && flags.layout != Layout::OneLine))
-        {
+        if skip_dirs && lists_content(meta, flags) {
             continue;
         }
 
@@ -100,33 +194,82 @@ fn inner_display_grid(
             &display_option,
             &padding_rules,
             (0, ""),
+            // the inputs and the entries of the listed directories are both the top level
+            depth.saturating_sub(1),
+            dir_total,
+            max_size,
+            date_range,
         );
+        let mut row: Vec<String> = blocks.iter().map(ToString::to_string).collect();
+        collapse_repeats(&mut row, &mut previous_row, flags);
 
-        for block in blocks {
-            let block_str = block.to_string();
+        for mut block_str in row {
+            let mut width = get_visible_width(&block_str);
 
-            grid.add(Cell {
-                width: get_visible_width(&block_str),
+            // pad the narrow entries up to the minimum column width of the grid
+            if flags.layout == Layout::Grid && width < flags.min_column_width.0 {
+                block_str += &" ".repeat(flags.min_column_width.0 - width);
+                width = flags.min_column_width.0;
+            }
+
+            let cell = Cell {
+                width,
                 contents: block_str,
-            });
+            };
+            if flags.rtl.0 {
+                rtl_cells.push(Cell {
+                    width: cell.width,
+                    contents: cell.contents.clone(),
+                });
+            }
+            grid.add(cell);
+            cell_count += 1;
         }
     }
 
-    if flags.layout == Layout::Grid {
+    if let (Layout::Grid, Some(columns)) = (flags.layout, flags.columns.0) {
+        // the fixed columns may overflow the terminal, letting it wrap the lines
+        if flags.debug_layout.0 {
+            print_error!("grid layout: {} columns, as given by --columns", columns);
+        }
+        output += &render_grid(&grid.fit_into_columns(columns), &rtl_cells, flags);
+    } else if flags.layout == Layout::Grid {
         if let Some(tw) = term_width {
             if let Some(gridded_output) = grid.fit_into_width(tw) {
-                output += &gridded_output.to_string();
+                if flags.debug_layout.0 && gridded_output.row_count() > 0 {
+                    let rows = gridded_output.row_count();
+                    print_error!(
+                        "grid layout: {} columns of {} rows for a terminal width of {}",
+                        cell_count.div_ceil(rows),
+                        rows,
+                        tw
+                    );
+                }
+                output += &render_grid(&gridded_output, &rtl_cells, flags);
             } else {
+                if flags.debug_layout.0 {
+                    print_error!(
+                        "grid layout: 1 column, the entries do not fit into a terminal width of {}",
+                        tw
+                    );
+                }
                 //does not fit into grid, usually because (some) filename(s)
                 //are longer or almost as long as term_width
                 //print line by line instead!
-                output += &grid.fit_into_columns(1).to_string();
+                output += &render_grid(&grid.fit_into_columns(1), &rtl_cells, flags);
             }
         } else {
-            output += &grid.fit_into_columns(1).to_string();
+            if flags.debug_layout.0 {
+                print_error!("grid layout: 1 column, the terminal width is unknown");
+            }
+            output += &render_grid(&grid.fit_into_columns(1), &rtl_cells, flags);
         }
     } else {
-        output += &grid.fit_into_columns(flags.blocks.0.len()).to_string();
+        output += &render_grid(
+            &grid.fit_into_columns(flags.blocks.0.len()),
+            &rtl_cells,
+            flags,
+        );
     }
 
     let should_display_folder_path = should_display_folder_path(depth, &metas, &flags);
@@ -135,11 +278,17 @@ fn inner_display_grid(
     for meta in metas {
         if meta.content.is_some() {
             if should_display_folder_path {
-                output += &display_folder_path(&meta);
+                output += &display_folder_path(&meta, flags, icons);
             }
 
-            let display_option = DisplayOption::Relative {
-                base_path: &meta.path,
+            // keep the names relative to the listed input for --show-relative-prefix
+            let display_option = match display_option {
+                DisplayOption::Relative { base_path } if flags.show_relative_prefix.0 => {
+                    DisplayOption::Relative { base_path }
+                }
+                _ => DisplayOption::Relative {
+                    base_path: &meta.path,
+                },
             };
 
             output += &inner_display_grid(
@@ -157,6 +306,44 @@ fn inner_display_grid(
     output
 }
 
+/// Render the blocks of each of the `metas` as plain text without any padding, for the other
+/// output formats than the terminal one.
+pub fn plain_blocks(
+    metas: &[Meta],
+    flags: &Flags,
+    colors: &Colors,
+    icons: &Icons,
+    depth: usize,
+) -> Vec<Vec<String>> {
+    let padding_rules = get_padding_rules(metas, flags);
+    let dir_total = get_total_size(metas, flags);
+    let max_size = get_max_size(metas, flags);
+    let date_range = get_date_range(metas, flags);
+
+    metas
+        .iter()
+        .map(|meta| {
+            get_output(
+                meta,
+                colors,
+                icons,
+                flags,
+                &DisplayOption::FileName,
+                &padding_rules,
+                (0, ""),
+                depth.saturating_sub(1),
+                dir_total,
+                max_size,
+                date_range,
+            )
+            .iter()
+            .map(|block| block.to_string().trim().to_string())
+            .collect()
+        })
+        .collect()
+}
+
+#[allow(clippy::too_many_arguments)]
 fn inner_display_tree(
     metas: &[Meta],
     flags: &Flags,
@@ -165,11 +352,23 @@ fn inner_display_tree(
     tree_depth_prefix: (usize, &str),
     padding_rules: &HashMap<Block, usize>,
     tree_index: usize,
+    max_rows: Option<usize>,
 ) -> Vec<Cell> {
     let mut cells = Vec::new();
     let last_idx = metas.len();
+    let dir_total = get_total_size(metas, flags);
+    let max_size = get_max_size(metas, flags);
+    let date_range = get_date_range(metas, flags);
+    let row_count = |cells: &Vec<Cell>| cells.len() / flags.blocks.0.len().max(1);
+    // the blocks of the previous row, for --collapse-repeats
+    let mut previous_row = Vec::new();
 
     for (idx, meta) in metas.iter().enumerate() {
+        // stop producing the rows once the cap of --max-lines is hit
+        if max_rows.is_some_and(|max| row_count(&cells) >= max) {
+            break;
+        }
+
         let current_prefix = if tree_depth_prefix.0 > 0 {
             if idx + 1 != last_idx {
                 // is last folder elem
@@ -181,7 +380,20 @@ fn inner_display_tree(
             tree_depth_prefix.1.to_string()
         };
 
-        for block in get_output(
+        let new_prefix = if tree_depth_prefix.0 > 0 {
+            if idx + 1 != last_idx {
+                // is last folder elem
+                format!("{}{} ", tree_depth_prefix.1, LINE)
+            } else {
+                format!("{}{} ", tree_depth_prefix.1, BLANK)
+            }
+        } else {
+            tree_depth_prefix.1.to_string()
+        };
+
+        // the wrapped parts of the name, for --tree-name-width
+        let mut continuations = Vec::new();
+        let mut row: Vec<String> = get_output(
             &meta,
             &colors,
             &icons,
@@ -189,8 +401,28 @@ fn inner_display_tree(
             &DisplayOption::FileName,
             &padding_rules,
             (tree_index, &current_prefix),
-        ) {
-            let block_str = block.to_string();
+            tree_depth_prefix.0,
+            dir_total,
+            max_size,
+            date_range,
+        )
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+        collapse_repeats(&mut row, &mut previous_row, flags);
+
+        for (i, mut block_str) in row.into_iter().enumerate() {
+            if let (Some(width), Block::Name) = (flags.tree_name_width.0, flags.blocks.0[i]) {
+                if i == tree_index {
+                    let name = block_str
+                        .strip_prefix(&current_prefix)
+                        .unwrap_or(&block_str);
+                    let mut lines = wrap_visible(name, width).into_iter();
+                    let first = lines.next().unwrap_or_default();
+                    continuations = lines.map(|line| new_prefix.clone() + &line).collect();
+                    block_str = current_prefix.clone() + &first;
+                }
+            }
 
             cells.push(Cell {
                 width: get_visible_width(&block_str),
@@ -198,18 +430,22 @@ fn inner_display_tree(
             });
         }
 
-        if meta.content.is_some() {
-            let new_prefix = if tree_depth_prefix.0 > 0 {
-                if idx + 1 != last_idx {
-                    // is last folder elem
-                    format!("{}{} ", tree_depth_prefix.1, LINE)
+        // the continuation lines only fill the name column
+        for line in continuations {
+            for i in 0..flags.blocks.0.len() {
+                let contents = if i == tree_index {
+                    line.clone()
                 } else {
-                    format!("{}{} ", tree_depth_prefix.1, BLANK)
-                }
-            } else {
-                tree_depth_prefix.1.to_string()
-            };
+                    String::new()
+                };
+                cells.push(Cell {
+                    width: get_visible_width(&contents),
+                    contents,
+                });
+            }
+        }
 
+        if meta.content.is_some() {
             cells.extend(inner_display_tree(
                 &meta.content.as_ref().unwrap(),
                 &flags,
@@ -218,6 +454,7 @@ fn inner_display_tree(
                 (tree_depth_prefix.0 + 1, &new_prefix),
                 padding_rules,
                 tree_index,
+                max_rows.map(|max| max.saturating_sub(row_count(&cells))),
             ));
         }
     }
@@ -225,32 +462,197 @@ fn inner_display_tree(
     cells
 }
 
+/// Split `input` into lines of at most `width` visible columns, resetting the colors at the end of
+/// each line and applying them again at the start of the next one.
+fn wrap_visible(input: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut line_width = 0;
+    // the escape sequences in effect since the last reset
+    let mut active = String::new();
+
+    let mut chars = input.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            let mut escape = String::from(c);
+            for c in chars.by_ref() {
+                escape.push(c);
+                if c == 'm' {
+                    break;
+                }
+            }
+            if escape == "\u{1b}[0m" {
+                active.clear();
+            } else {
+                active += &escape;
+            }
+            line += &escape;
+            continue;
+        }
+
+        let char_width = c.width().unwrap_or(0);
+        if line_width > 0 && line_width + char_width > width {
+            if !active.is_empty() {
+                line += "\u{1b}[0m";
+            }
+            lines.push(std::mem::replace(&mut line, active.clone()));
+            line_width = 0;
+        }
+        line.push(c);
+        line_width += char_width;
+    }
+    lines.push(line);
+
+    lines
+}
+
+/// Check if the content of a user input is listed instead of the input itself.
+fn lists_content(meta: &Meta, flags: &Flags) -> bool {
+    // the directories given without a trailing slash are listed as entries
+    if flags.content_on_trailing_slash.0 && meta.content.is_none() {
+        return false;
+    }
+
+    match meta.file_type {
+        FileType::Directory { .. } => true,
+        FileType::SymLink { is_dir: true } => {
+            flags.layout != Layout::OneLine || flags.dereference_command_line_symlink_to_dir.0
+        }
+        _ => false,
+    }
+}
+
 fn should_display_folder_path(depth: usize, metas: &[Meta], flags: &Flags) -> bool {
     if depth > 0 {
         true
     } else {
-        let folder_number = metas
-            .iter()
-            .filter(|x| {
-                matches!(x.file_type, FileType::Directory { .. })
-                    || (matches!(x.file_type, FileType::SymLink { is_dir: true })
-                        && flags.layout != Layout::OneLine)
-            })
-            .count();
+        let folder_number = metas.iter().filter(|x| lists_content(x, flags)).count();
 
         folder_number > 1 || folder_number < metas.len()
     }
 }
 
-fn display_folder_path(meta: &Meta) -> String {
+fn display_folder_path(meta: &Meta, flags: &Flags, icons: &Icons) -> String {
+    let line_ending = flags.line_ending.as_str();
+
     let mut output = String::new();
-    output.push('\n');
+    output += line_ending;
+    output += &icons.get(&meta.name);
     output += &meta.path.to_string_lossy();
-    output += ":\n";
+    output += ":";
+    output += line_ending;
+
+    output
+}
+
+/// Render the fitted grid, or its cells laid out from right to left for --rtl.
+fn render_grid(display: &GridDisplay, rtl_cells: &[Cell], flags: &Flags) -> String {
+    if !flags.rtl.0 {
+        return with_line_ending(display.to_string(), flags);
+    }
+
+    let direction = match flags.layout {
+        Layout::OneLine => Direction::LeftToRight,
+        _ => Direction::TopToBottom,
+    };
+    let filling = match flags.layout {
+        Layout::OneLine => 1,
+        _ => 2,
+    };
+
+    with_line_ending(
+        display_rtl(rtl_cells, direction, display.row_count(), filling),
+        flags,
+    )
+}
+
+/// Lay the cells out in the given number of rows like the grid does, but with the columns in the
+/// reverse order and the cells aligned to the right.
+fn display_rtl(cells: &[Cell], direction: Direction, rows: usize, filling: usize) -> String {
+    if cells.is_empty() || rows == 0 {
+        return String::new();
+    }
+
+    let columns = cells.len().div_ceil(rows);
+    let cell_at = |row: usize, column: usize| {
+        let index = match direction {
+            Direction::LeftToRight => row * columns + column,
+            Direction::TopToBottom => row + rows * column,
+        };
+        cells.get(index)
+    };
+
+    let widths: Vec<usize> = (0..columns)
+        .map(|column| {
+            (0..rows)
+                .filter_map(|row| cell_at(row, column))
+                .map(|cell| cell.width)
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let mut output = String::new();
+    for row in 0..rows {
+        let line: Vec<String> = (0..columns)
+            .rev()
+            .map(|column| match cell_at(row, column) {
+                Some(cell) => " ".repeat(widths[column] - cell.width) + &cell.contents,
+                None => " ".repeat(widths[column]),
+            })
+            .collect();
+        output += &line.join(&" ".repeat(filling));
+        output += "\n";
+    }
 
     output
 }
 
+/// Blank the permissions, user and group of the `row` repeating the ones of the `previous` row
+/// for --collapse-repeats, the `previous` row becoming the `row` as rendered.
+fn collapse_repeats(row: &mut [String], previous: &mut Vec<String>, flags: &Flags) {
+    if !flags.collapse_repeats.0 {
+        return;
+    }
+
+    let rendered = row.to_vec();
+    for (i, block) in flags.blocks.0.iter().enumerate() {
+        let collapsible = matches!(block, Block::Permission | Block::User | Block::Group);
+        if collapsible && previous.get(i) == Some(&row[i]) {
+            row[i] = String::new();
+        }
+    }
+    *previous = rendered;
+}
+
+/// Replace the line feeds ending the lines rendered by the grid with the configured line ending.
+fn with_line_ending(output: String, flags: &Flags) -> String {
+    match flags.line_ending {
+        LineEnding::Lf => output,
+        LineEnding::Crlf | LineEnding::Nul => output.replace('\n', flags.line_ending.as_str()),
+    }
+}
+
+/// Keep the first lines of the output for --max-lines, with a note when the others are dropped.
+fn truncate_lines(output: String, flags: &Flags) -> String {
+    let max = match flags.max_lines.0 {
+        Some(max) => max,
+        None => return output,
+    };
+
+    let lines: Vec<&str> = output.split_inclusive('\n').collect();
+    if lines.len() <= max {
+        return output;
+    }
+
+    let mut truncated = lines[..max].concat();
+    truncated += "… (truncated)";
+    truncated += flags.line_ending.as_str();
+
+    truncated
+}
+
+#[allow(clippy::too_many_arguments)]
 fn get_output<'a>(
     meta: &'a Meta,
     colors: &'a Colors,
@@ -259,6 +661,10 @@ fn get_output<'a>(
     display_option: &DisplayOption,
     padding_rules: &HashMap<Block, usize>,
     tree: (usize, &'a str),
+    depth: usize,
+    dir_total: u64,
+    max_size: u64,
+    date_range: Option<(i64, i64)>,
 ) -> Vec<ANSIString<'a>> {
     let mut strings: Vec<ANSIString> = Vec::new();
     for (i, block) in flags.blocks.0.iter().enumerate() {
@@ -271,33 +677,116 @@ fn get_output<'a>(
         };
 
         match block {
-            Block::INode => block_vec.push(meta.inode.render(colors)),
-            Block::Links => block_vec.push(meta.links.render(colors)),
+            Block::INode => block_vec.push(meta.inode.render(colors, flags)),
+            Block::Links => block_vec.push(meta.links.render(colors, flags)),
+            Block::FreeInodes => block_vec.push(meta.free_inodes.render(colors)),
+            Block::AgeGap => block_vec.push(meta.age_gap.render(colors)),
+            Block::Device => block_vec.push(meta.device.render(colors)),
+            Block::RealPath => {
+                let real_path = if meta.archived {
+                    RealPath::none()
+                } else {
+                    RealPath::from_path(&meta.path)
+                };
+                block_vec.push(real_path.render(colors))
+            }
+            Block::FileCount => block_vec.push(meta.file_count.render(colors)),
+            Block::GitDirSummary => block_vec.push(meta.git_summary.render(colors)),
+            Block::GitStatus => block_vec.push(meta.git_status.render(colors, &flags.git_symbols)),
             Block::Permission => {
-                block_vec.extend(vec![
-                    meta.file_type.render(colors),
-                    meta.permissions.render(colors),
-                ]);
+                block_vec.push(meta.file_type.render(colors));
+                if flags.permission == PermissionFlag::Octal {
+                    // the digits are set apart from the letter of the file type
+                    block_vec.push(ANSIString::from(" "));
+                    block_vec.push(meta.permissions.render_octal(colors));
+                } else if flags.permission_heatmap.0 {
+                    block_vec.push(meta.permissions.render_heatmap(colors));
+                } else {
+                    block_vec.push(meta.permissions.render(colors));
+                }
+                if flags.access_check.0 {
+                    block_vec.push(ANSIString::from(" "));
+                    let access = if meta.archived {
+                        Access::none()
+                    } else {
+                        Access::from(meta.path.as_path())
+                    };
+                    block_vec.push(access.render(colors));
+                }
             }
-            Block::User => block_vec.push(meta.owner.render_user(colors)),
-            Block::Group => block_vec.push(meta.owner.render_group(colors)),
+            Block::User => block_vec.push(meta.owner.render_user(colors, flags)),
+            Block::Group => block_vec.push(meta.owner.render_group(colors, flags)),
             Block::Size => {
                 let pad = if Layout::Tree == flags.layout && 0 == tree.0 && 0 == i {
                     None
                 } else {
                     Some(padding_rules[&Block::SizeValue])
                 };
-                block_vec.push(meta.size.render(colors, &flags, pad))
+                let size = meta.size.of_name(&meta.name, flags);
+                block_vec.push(size.render(colors, &flags, pad))
+            }
+            Block::SizeValue => {
+                let size = meta.size.of_name(&meta.name, flags);
+                block_vec.push(size.render_value(colors, flags))
             }
-            Block::SizeValue => block_vec.push(meta.size.render_value(colors, flags)),
-            Block::Date => block_vec.push(meta.date.render(colors, &flags)),
+            Block::SizePercent => {
+                if counts_in_total_size(meta, flags) {
+                    block_vec.push(meta.size.render_percent(colors, flags, dir_total))
+                } else {
+                    block_vec.push(colors.colorize(String::from("-"), &Elem::NonFile))
+                }
+            }
+            Block::SizeBar => {
+                if counts_in_total_size(meta, flags) {
+                    block_vec.push(meta.size.render_bar(colors, flags, max_size))
+                } else {
+                    block_vec.push(ANSIString::from(""))
+                }
+            }
+            Block::Date => match date_range {
+                Some(range) => block_vec.push(meta.date.render_scaled(colors, flags, range)),
+                None => block_vec.push(meta.date.render(colors, &flags)),
+            },
+            Block::Depth => block_vec.push(colors.colorize(depth.to_string(), &Elem::Depth)),
             Block::Name => {
                 block_vec.extend(vec![
-                    meta.name.render(colors, icons, &display_option),
+                    meta.name.render(colors, icons, &display_option, flags),
                     meta.indicator.render(&flags),
                 ]);
-                if !(flags.no_symlink.0 || flags.dereference.0 || flags.layout == Layout::Grid) {
-                    block_vec.push(meta.symlink.render(colors, &flags))
+                block_vec.extend(meta.child_count.render_suffix(colors));
+                // the directories which could not be listed are marked by the glyph of their state
+                let glyph = meta.content_state.glyph(&flags.glyphs);
+                if !glyph.is_empty() {
+                    block_vec.push(colors.colorize(format!(" {}", glyph), &Elem::MissingTarget));
+                }
+                if meta.mount_point {
+                    block_vec.push(
+                        colors.colorize(String::from(" [mount]"), &Elem::Device { valid: true }),
+                    );
+                }
+                // a oneline listing of the names alone prints the bare names, like `ls -1`
+                let names_only = flags.layout == Layout::OneLine && flags.blocks.0 == [Block::Name];
+                if !(flags.no_symlink.0
+                    || flags.dereference.0
+                    || flags.layout == Layout::Grid
+                    || names_only)
+                {
+                    if flags.styled_link_target.0 && !meta.archived {
+                        block_vec.push(meta.symlink.render_styled(&meta.path, colors, icons, flags))
+                    } else {
+                        block_vec.push(meta.symlink.render(colors, &flags))
+                    }
+                }
+                if let Some(manifest) = &flags.checksum_verify.0 {
+                    if let FileType::File { .. } = meta.file_type {
+                        block_vec.push(ANSIString::from(" "));
+                        let checksum = if meta.archived {
+                            Checksum::Missing
+                        } else {
+                            manifest.verify(&meta.path)
+                        };
+                        block_vec.push(checksum.render(colors));
+                    }
                 }
             }
         };
@@ -319,14 +808,40 @@ fn get_visible_width(input: &str) -> usize {
         }
     }
 
-    UnicodeWidthStr::width(input) - nb_invisible_char
+    // Nor the length of the hyperlinks, the OSC 8 sequences ending with the string terminator
+    for (idx, _) in input.match_indices("\u{1b}]8;;") {
+        let (_, s) = input.split_at(idx);
+
+        if let Some(len) = s.find("\u{1b}\\") {
+            nb_invisible_char += UnicodeWidthStr::width(&s[..len + 2]);
+        }
+    }
+
+    // The emoji variation selector is zero width by itself, but it makes the terminal render
+    // the preceding narrow character with the emoji presentation, taking two cells.
+    let mut nb_emoji_presentation = 0;
+    let mut previous = None;
+    for c in input.chars() {
+        if c == VARIATION_SELECTOR_EMOJI {
+            if let Some(1) = previous.and_then(UnicodeWidthChar::width) {
+                nb_emoji_presentation += 1;
+            }
+        }
+        previous = Some(c);
+    }
+
+    UnicodeWidthStr::width(input) + nb_emoji_presentation - nb_invisible_char
 }
 
 fn detect_size_lengths(metas: &[Meta], flags: &Flags) -> usize {
     let mut max_value_length: usize = 0;
 
     for meta in metas {
-        let value_len = meta.size.value_string(flags).len();
+        let value_len = meta
+            .size
+            .of_name(&meta.name, flags)
+            .value_string(flags)
+            .len();
 
         if value_len > max_value_length {
             max_value_length = value_len;
@@ -345,11 +860,219 @@ fn detect_size_lengths(metas: &[Meta], flags: &Flags) -> usize {
     max_value_length
 }
 
+/// The number of cells of the longest bar of the type histogram.
+const HISTOGRAM_WIDTH: usize = 20;
+
+/// Render the count of the listed entries of each extension, or of each file type for the
+/// entries other than files, as bars in proportion to the largest count. The listed directories
+/// are counted through their content.
+pub fn type_histogram(metas: &[Meta], colors: &Colors) -> String {
+    let mut counts = HashMap::new();
+    for meta in metas {
+        match &meta.content {
+            Some(content) => count_types(content, &mut counts),
+            None => count_types(std::slice::from_ref(meta), &mut counts),
+        }
+    }
+
+    let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+    counts.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+
+    let name_width = counts.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    let max = counts.first().map_or(0, |(_, count)| *count);
+    let count_width = max.to_string().len();
+
+    let mut output = String::new();
+    for (name, count) in &counts {
+        let filled = (count * HISTOGRAM_WIDTH + max / 2) / max;
+        let bar = colors.colorize("█".repeat(filled.max(1)), &Elem::FileMedium);
+        output += &format!(
+            "{:<name_width$} {:>count_width$} {}\n",
+            name,
+            count,
+            bar,
+            name_width = name_width,
+            count_width = count_width
+        );
+    }
+    output
+}
+
+/// Count the `metas` and their content by extension or file type into `counts`.
+fn count_types(metas: &[Meta], counts: &mut HashMap<String, usize>) {
+    for meta in metas {
+        // the current and parent directories of --all are the listed directory and its parent
+        if meta.name.name == "." || meta.name.name == ".." {
+            continue;
+        }
+
+        let key = match (meta.file_type, meta.name.extension()) {
+            (FileType::File { .. }, Some(extension)) => format!(".{}", extension.to_lowercase()),
+            (FileType::File { .. }, None) => String::from("file"),
+            (FileType::Directory { .. }, _) => String::from("directory"),
+            (FileType::SymLink { .. }, _) => String::from("symlink"),
+            (FileType::Pipe, _) => String::from("pipe"),
+            (FileType::Socket, _) => String::from("socket"),
+            (FileType::BlockDevice, _) => String::from("block device"),
+            (FileType::CharDevice, _) => String::from("char device"),
+            (FileType::Special, _) => String::from("special"),
+        };
+        *counts.entry(key).or_insert(0) += 1;
+
+        if let Some(content) = &meta.content {
+            count_types(content, counts);
+        }
+    }
+}
+
+/// Render the meaning of the colors and icons used in the listings.
+pub fn legend(colors: &Colors, icons: &Icons) -> String {
+    let file = FileType::File {
+        exec: false,
+        uid: false,
+    };
+    let file_types = [
+        (FileType::Directory { uid: false }, "directory"),
+        (file, "file"),
+        (
+            FileType::File {
+                exec: true,
+                uid: false,
+            },
+            "executable file",
+        ),
+        (FileType::SymLink { is_dir: false }, "symbolic link"),
+        (
+            FileType::SymLink { is_dir: true },
+            "symbolic link to a directory",
+        ),
+        (FileType::Pipe, "named pipe"),
+        (FileType::Socket, "socket"),
+        (FileType::BlockDevice, "block device"),
+        (FileType::CharDevice, "character device"),
+        (FileType::Special, "special file"),
+    ];
+    let dates = [
+        (Elem::HourOld, "modified within the last hour"),
+        (Elem::DayOld, "modified within the last day"),
+        (Elem::Older, "modified more than a day ago"),
+    ];
+    let sizes = [
+        (Elem::FileSmall, "size of a file smaller than a megabyte"),
+        (Elem::FileMedium, "size of a file smaller than a gigabyte"),
+        (Elem::FileLarge, "size of a larger file"),
+        (Elem::NonFile, "size of a directory or special file"),
+        (
+            Elem::SparseFile,
+            "size of a sparse file, with --highlight-sparse",
+        ),
+    ];
+    let permissions = [
+        (Elem::Read, "r", "readable"),
+        (Elem::Write, "w", "writable"),
+        (Elem::Exec, "x", "executable"),
+        (
+            Elem::ExecSticky,
+            "s",
+            "executable with the setuid, setgid or sticky bit",
+        ),
+        (Elem::NoAccess, "-", "no access"),
+    ];
+
+    let mut output = String::from("File types:\n");
+    for (file_type, description) in &file_types {
+        let name = Name::new(Path::new(description), *file_type);
+        output += &format!(
+            "  {}{}\n",
+            icons.get(&name),
+            colors.colorize(description.to_string(), &file_type_elem(*file_type))
+        );
+    }
+
+    output += "Dates:\n";
+    for (elem, description) in &dates {
+        output += &format!("  {}\n", colors.colorize(description.to_string(), elem));
+    }
+
+    output += "Sizes:\n";
+    for (elem, description) in &sizes {
+        output += &format!("  {}\n", colors.colorize(description.to_string(), elem));
+    }
+
+    output += "Permissions:\n";
+    for (elem, bit, description) in &permissions {
+        output += &format!(
+            "  {} {}\n",
+            colors.colorize(bit.to_string(), elem),
+            description
+        );
+    }
+
+    output
+}
+
+fn file_type_elem(file_type: FileType) -> Elem {
+    match file_type {
+        FileType::File { exec, uid } => Elem::File { exec, uid },
+        FileType::Directory { uid } => Elem::Dir { uid },
+        FileType::SymLink { .. } => Elem::SymLink,
+        FileType::Pipe => Elem::Pipe,
+        FileType::Socket => Elem::Socket,
+        FileType::BlockDevice => Elem::BlockDevice,
+        FileType::CharDevice => Elem::CharDevice,
+        FileType::Special => Elem::Special,
+    }
+}
+
+/// Whether the size of `meta` is part of the total size of its listing. The size of directories
+/// only counts when it is their total size, and the "." and ".." entries never count.
+fn counts_in_total_size(meta: &Meta, flags: &Flags) -> bool {
+    if meta.name.name == "." || meta.name.name == ".." {
+        return false;
+    }
+
+    !matches!(meta.file_type, FileType::Directory { .. }) || flags.total_size.0
+}
+
+fn get_total_size(metas: &[Meta], flags: &Flags) -> u64 {
+    metas
+        .iter()
+        .filter(|meta| counts_in_total_size(meta, flags))
+        .map(|meta| meta.size.get_bytes())
+        .sum()
+}
+
+/// Get the `total` disk space allocated to the entries of the listing, in the selected size units.
+fn get_total_allocated(metas: &[Meta], flags: &Flags) -> String {
+    let allocated = metas.iter().map(|meta| meta.size.get_allocated()).sum();
+    Size::new(allocated).total_string(flags)
+}
+
+/// Get the largest size of the listing, filling the whole bar of the `size_bar` block.
+fn get_max_size(metas: &[Meta], flags: &Flags) -> u64 {
+    metas
+        .iter()
+        .filter(|meta| counts_in_total_size(meta, flags))
+        .map(|meta| meta.size.get_bytes())
+        .max()
+        .unwrap_or(0)
+}
+
+/// Get the oldest and the newest timestamps of the listing, for --date-color-scale.
+fn get_date_range(metas: &[Meta], flags: &Flags) -> Option<(i64, i64)> {
+    if !flags.date_color_scale.0 {
+        return None;
+    }
+
+    let timestamps = metas.iter().map(|meta| meta.date.timestamp());
+    Some((timestamps.clone().min()?, timestamps.max()?))
+}
+
 fn get_padding_rules(metas: &[Meta], flags: &Flags) -> HashMap<Block, usize> {
     let mut padding_rules: HashMap<Block, usize> = HashMap::new();
 
     if flags.blocks.0.contains(&Block::Size) {
-        let size_val = detect_size_lengths(&metas, &flags);
+        let size_val = detect_size_lengths(&metas, &flags).max(flags.size_width.0.unwrap_or(0));
 
         padding_rules.insert(Block::SizeValue, size_val);
     }
@@ -363,11 +1086,13 @@ mod tests {
     use crate::color;
     use crate::color::Colors;
     use crate::icon::Icons;
+    use crate::meta::UsersCache;
     use crate::meta::{FileType, Name};
     use crate::Config;
     use crate::{app, flags, icon, sort};
+    use ansi_term::Colour;
     use assert_fs::prelude::*;
-    use std::path::Path;
+    use std::path::{Path, MAIN_SEPARATOR};
 
     #[test]
     fn test_display_get_visible_width_without_icons() {
@@ -392,6 +1117,7 @@ mod tests {
                 &Colors::new(color::Theme::NoColor),
                 &Icons::new(icon::Theme::NoIcon, " ".to_string()),
                 &DisplayOption::FileName,
+                &Flags::default(),
             );
 
             assert_eq!(get_visible_width(&output), *l);
@@ -424,6 +1150,7 @@ mod tests {
                     &Colors::new(color::Theme::NoColor),
                     &Icons::new(icon::Theme::Fancy, " ".to_string()),
                     &DisplayOption::FileName,
+                    &Flags::default(),
                 )
                 .to_string();
 
@@ -432,20 +1159,17 @@ mod tests {
     }
 
     #[test]
-    fn test_display_get_visible_width_with_colors() {
+    fn test_display_get_visible_width_with_unicode_icons() {
         for (s, l) in &[
-            ("Ｈｅｌｌｏ,ｗｏｒｌｄ!", 22),
-            ("ASCII1234-_", 11),
-            ("File with space", 15),
-            ("制作样本。", 10),
-            ("日本語", 6),
-            ("샘플은 무료로 드리겠습니다", 26),
-            ("👩🐩", 4),
-            ("🔬", 2),
+            // Add 3 characters for the emoji presentation icon and its separator.
+            ("ASCII1234-_", 14),
+            ("File with space", 18),
+            ("日本語", 9),
+            ("🔬", 5),
         ] {
             let path = Path::new(s);
             let name = Name::new(
-                &path,
+                path,
                 FileType::File {
                     exec: false,
                     uid: false,
@@ -453,45 +1177,118 @@ mod tests {
             );
             let output = name
                 .render(
-                    &Colors::new(color::Theme::NoLscolors),
-                    &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+                    &Colors::new(color::Theme::NoColor),
+                    &Icons::new(icon::Theme::Unicode, " ".to_string()),
                     &DisplayOption::FileName,
+                    &Flags::default(),
                 )
                 .to_string();
 
-            // check if the color is present.
-            assert_eq!(true, output.starts_with("\u{1b}[38;5;"));
-            assert_eq!(true, output.ends_with("[0m"));
-
+            assert!(output.contains('\u{fe0f}'));
             assert_eq!(get_visible_width(&output), *l);
         }
     }
 
     #[test]
-    fn test_display_get_visible_width_without_colors() {
-        for (s, l) in &[
-            ("Ｈｅｌｌｏ,ｗｏｒｌｄ!", 22),
-            ("ASCII1234-_", 11),
-            ("File with space", 15),
-            ("制作样本。", 10),
-            ("日本語", 6),
-            ("샘플은 무료로 드리겠습니다", 26),
-            ("👩🐩", 4),
-            ("🔬", 2),
-        ] {
+    fn test_display_get_visible_width_with_icon_separator() {
+        for (s, l) in &[("ASCII1234-_", 13), ("日本語", 8), ("🔬", 4)] {
             let path = Path::new(s);
             let name = Name::new(
-                &path,
+                path,
                 FileType::File {
                     exec: false,
                     uid: false,
                 },
             );
-            let output = name
-                .render(
+            let render = |separator: &str| {
+                name.render(
                     &Colors::new(color::Theme::NoColor),
-                    &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+                    &Icons::new(icon::Theme::Fancy, separator.to_string()),
                     &DisplayOption::FileName,
+                    &Flags::default(),
+                )
+                .to_string()
+            };
+
+            // The two spaces separator widens the cell by one column.
+            assert_eq!(get_visible_width(&render(" ")), *l);
+            assert_eq!(get_visible_width(&render("  ")), *l + 1);
+            assert_eq!(get_visible_width(&render(" |")), *l + 1);
+        }
+    }
+
+    #[test]
+    fn test_display_get_visible_width_variation_selector() {
+        assert_eq!(get_visible_width("\u{1f5cb}"), 1);
+        assert_eq!(get_visible_width("\u{1f5cb}\u{fe0f}"), 2);
+        assert_eq!(get_visible_width("\u{1f5c1}\u{fe0f}"), 2);
+        // already wide characters are not widened any further
+        assert_eq!(get_visible_width("\u{1f52c}\u{fe0f}"), 2);
+    }
+
+    #[test]
+    fn test_display_get_visible_width_with_colors() {
+        for (s, l) in &[
+            ("Ｈｅｌｌｏ,ｗｏｒｌｄ!", 22),
+            ("ASCII1234-_", 11),
+            ("File with space", 15),
+            ("制作样本。", 10),
+            ("日本語", 6),
+            ("샘플은 무료로 드리겠습니다", 26),
+            ("👩🐩", 4),
+            ("🔬", 2),
+        ] {
+            let path = Path::new(s);
+            let name = Name::new(
+                &path,
+                FileType::File {
+                    exec: false,
+                    uid: false,
+                },
+            );
+            let output = name
+                .render(
+                    &Colors::new(color::Theme::NoLscolors),
+                    &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+                    &DisplayOption::FileName,
+                    &Flags::default(),
+                )
+                .to_string();
+
+            // check if the color is present.
+            assert_eq!(true, output.starts_with("\u{1b}[38;5;"));
+            assert_eq!(true, output.ends_with("[0m"));
+
+            assert_eq!(get_visible_width(&output), *l);
+        }
+    }
+
+    #[test]
+    fn test_display_get_visible_width_without_colors() {
+        for (s, l) in &[
+            ("Ｈｅｌｌｏ,ｗｏｒｌｄ!", 22),
+            ("ASCII1234-_", 11),
+            ("File with space", 15),
+            ("制作样本。", 10),
+            ("日本語", 6),
+            ("샘플은 무료로 드리겠습니다", 26),
+            ("👩🐩", 4),
+            ("🔬", 2),
+        ] {
+            let path = Path::new(s);
+            let name = Name::new(
+                &path,
+                FileType::File {
+                    exec: false,
+                    uid: false,
+                },
+            );
+            let output = name
+                .render(
+                    &Colors::new(color::Theme::NoColor),
+                    &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+                    &DisplayOption::FileName,
+                    &Flags::default(),
                 )
                 .to_string();
 
@@ -503,6 +1300,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_display_get_visible_width_with_hyperlinks() {
+        let mut flags = Flags::default();
+        flags.hyperlink = flags::HyperlinkOption::Always;
+
+        for (s, l) in &[("ASCII1234-_", 13), ("File with space", 17), ("日本語", 8)] {
+            let path = Path::new(s);
+            let name = Name::new(
+                path,
+                FileType::File {
+                    exec: false,
+                    uid: false,
+                },
+            );
+            let output = name
+                .render(
+                    &Colors::new(color::Theme::NoLscolors),
+                    &Icons::new(icon::Theme::Fancy, " ".to_string()),
+                    &DisplayOption::FileName,
+                    &flags,
+                )
+                .to_string();
+
+            assert!(output.starts_with("\u{1b}]8;;file://"));
+            assert_eq!(get_visible_width(&output), *l);
+        }
+    }
+
     fn sort(metas: &mut Vec<Meta>, sorters: &Vec<(flags::SortOrder, sort::SortFn)>) {
         metas.sort_unstable_by(|a, b| sort::by_meta(sorters, a, b));
 
@@ -523,9 +1348,9 @@ mod tests {
         dir.child("one.d").create_dir_all().unwrap();
         dir.child("one.d/two").touch().unwrap();
         dir.child("one.d/.hidden").touch().unwrap();
-        let mut metas = Meta::from_path(Path::new(dir.path()), false)
+        let mut metas = Meta::from_path(Path::new(dir.path()), false, &UsersCache::new())
             .unwrap()
-            .recurse_into(42, &flags)
+            .recurse_into(42, &flags, &UsersCache::new())
             .unwrap()
             .unwrap();
         sort(&mut metas, &sort::assemble_sorters(&flags));
@@ -554,9 +1379,9 @@ mod tests {
         let dir = assert_fs::TempDir::new().unwrap();
         dir.child("dir").create_dir_all().unwrap();
         dir.child("dir/file").touch().unwrap();
-        let metas = Meta::from_path(Path::new(dir.path()), false)
+        let metas = Meta::from_path(Path::new(dir.path()), false, &UsersCache::new())
             .unwrap()
-            .recurse_into(42, &flags)
+            .recurse_into(42, &flags, &UsersCache::new())
             .unwrap()
             .unwrap();
         let output = tree(
@@ -593,9 +1418,9 @@ mod tests {
         let dir = assert_fs::TempDir::new().unwrap();
         dir.child("dir").create_dir_all().unwrap();
         dir.child("dir/file").touch().unwrap();
-        let metas = Meta::from_path(Path::new(dir.path()), false)
+        let metas = Meta::from_path(Path::new(dir.path()), false, &UsersCache::new())
             .unwrap()
-            .recurse_into(42, &flags)
+            .recurse_into(42, &flags, &UsersCache::new())
             .unwrap()
             .unwrap();
         let output = tree(
@@ -631,9 +1456,9 @@ mod tests {
         let dir = assert_fs::TempDir::new().unwrap();
         dir.child("one.d").create_dir_all().unwrap();
         dir.child("one.d/two").touch().unwrap();
-        let metas = Meta::from_path(Path::new(dir.path()), false)
+        let metas = Meta::from_path(Path::new(dir.path()), false, &UsersCache::new())
             .unwrap()
-            .recurse_into(42, &flags)
+            .recurse_into(42, &flags, &UsersCache::new())
             .unwrap()
             .unwrap();
         let output = tree(
@@ -645,4 +1470,683 @@ mod tests {
 
         assert!(output.ends_with("└── two\n"));
     }
+
+    #[test]
+    fn test_tree_depth_block() {
+        let argv = vec!["lsd", "--tree", "--blocks", "depth,name"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one.d").create_dir_all().unwrap();
+        dir.child("one.d/two.d").create_dir_all().unwrap();
+        dir.child("one.d/two.d/three").touch().unwrap();
+        let metas = Meta::from_path(Path::new(dir.path()), false, &UsersCache::new())
+            .unwrap()
+            .recurse_into(42, &flags, &UsersCache::new())
+            .unwrap()
+            .unwrap();
+        let output = tree(
+            &metas,
+            &flags,
+            &Colors::new(color::Theme::NoColor),
+            &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+        );
+
+        assert_eq!("0 one.d\n1 └── two.d\n2     └── three\n", output);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_tree_directory_state_glyphs() {
+        use std::os::unix::fs::{symlink, PermissionsExt};
+
+        let mut config = Config::with_none();
+        config.glyphs = Some(crate::config_file::Glyphs {
+            no_access: Some("N".into()),
+            broken: Some("B".into()),
+            cycle: Some("L".into()),
+        });
+        let argv = vec!["lsd", "--tree", "--follow-dir-symlinks"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &config).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("locked").create_dir_all().unwrap();
+        dir.child("locked/file").touch().unwrap();
+        symlink(".", dir.path().join("loop")).unwrap();
+        std::fs::set_permissions(dir.path().join("locked"), PermissionsExt::from_mode(0o000))
+            .unwrap();
+
+        let metas = Meta::from_path(Path::new(dir.path()), false, &UsersCache::new())
+            .unwrap()
+            .recurse_into(42, &flags, &UsersCache::new())
+            .unwrap()
+            .unwrap();
+        let output = tree(
+            &metas,
+            &flags,
+            &Colors::new(color::Theme::NoColor),
+            &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+        );
+        std::fs::set_permissions(dir.path().join("locked"), PermissionsExt::from_mode(0o755))
+            .unwrap();
+
+        // the permissions do not stop root from reading the directory
+        let expected = if unsafe { libc::geteuid() } == 0 {
+            "locked\n└── file\nloop L ⇒ .\n"
+        } else {
+            "locked N\nloop L ⇒ .\n"
+        };
+        assert_eq!(expected, output);
+    }
+
+    #[test]
+    fn test_grid_depth_block() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one.d").create_dir_all().unwrap();
+        dir.child("one.d/two").touch().unwrap();
+
+        for layout in &["--recursive", "--long"] {
+            let argv = vec!["lsd", layout, "--recursive", "--blocks", "depth,name"];
+            let matches = app::build().get_matches_from_safe(argv).unwrap();
+            let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+            let mut meta =
+                Meta::from_path(Path::new(dir.path()), false, &UsersCache::new()).unwrap();
+            meta.content = meta.recurse_into(42, &flags, &UsersCache::new()).unwrap();
+            let output = inner_display_grid(
+                &DisplayOption::None,
+                &[meta],
+                &flags,
+                &Colors::new(color::Theme::NoColor),
+                &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+                0,
+                None,
+            );
+
+            // the entries of the listed directory are the top level
+            let depths: Vec<&str> = output
+                .lines()
+                .filter(|line| line.ends_with("one.d") || line.ends_with("two"))
+                .filter_map(|line| line.split_whitespace().next())
+                .collect();
+            assert_eq!(vec!["0", "1"], depths, "{}", layout);
+        }
+    }
+
+    #[test]
+    fn test_grid_columns() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        for name in &["a", "b", "c", "d", "e", "f"] {
+            dir.child(name).touch().unwrap();
+        }
+
+        for (columns, expected) in &[
+            ("3", "a  c  e\nb  d  f\n"),
+            ("2", "a  d\nb  e\nc  f\n"),
+            ("6", "a  b  c  d  e  f\n"),
+        ] {
+            let argv = vec!["lsd", "--columns", columns];
+            let matches = app::build().get_matches_from_safe(argv).unwrap();
+            let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+            let mut metas = Meta::from_path(Path::new(dir.path()), false, &UsersCache::new())
+                .unwrap()
+                .recurse_into(1, &flags, &UsersCache::new())
+                .unwrap()
+                .unwrap();
+            sort(&mut metas, &sort::assemble_sorters(&flags));
+            let output = inner_display_grid(
+                &DisplayOption::FileName,
+                &metas,
+                &flags,
+                &Colors::new(color::Theme::NoColor),
+                &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+                0,
+                Some(8),
+            );
+
+            assert_eq!(*expected, output);
+        }
+    }
+
+    #[test]
+    fn test_grid_folder_path_icon() {
+        let argv = vec!["lsd", "--recursive", "--oneline"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one.d/two").touch().unwrap();
+        let mut meta = Meta::from_path(Path::new(dir.path()), false, &UsersCache::new()).unwrap();
+        meta.content = meta.recurse_into(42, &flags, &UsersCache::new()).unwrap();
+        let display = |theme| {
+            inner_display_grid(
+                &DisplayOption::None,
+                &[meta.clone()],
+                &flags,
+                &Colors::new(color::Theme::NoColor),
+                &Icons::new(theme, " ".to_string()),
+                0,
+                None,
+            )
+        };
+
+        let header = format!("{}:", dir.path().join("one.d").display());
+        let lines = display(icon::Theme::Fancy);
+        assert!(lines
+            .lines()
+            .any(|line| line == format!("\u{f115} {}", header)));
+
+        let lines = display(icon::Theme::NoIcon);
+        assert!(lines.lines().any(|line| line == header));
+    }
+
+    #[test]
+    fn test_grid_show_relative_prefix() {
+        let argv = vec!["lsd", "--recursive", "--oneline", "--show-relative-prefix"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one.d/two.d").create_dir_all().unwrap();
+        dir.child("one.d/two.d/three").touch().unwrap();
+        let mut meta = Meta::from_path(Path::new(dir.path()), false, &UsersCache::new()).unwrap();
+        meta.content = meta.recurse_into(42, &flags, &UsersCache::new()).unwrap();
+        let output = inner_display_grid(
+            &DisplayOption::None,
+            &[meta],
+            &flags,
+            &Colors::new(color::Theme::NoLscolors),
+            &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+            0,
+            None,
+        );
+
+        let dir_colour = Colour::Fixed(33);
+        let file_colour = Colour::Fixed(184);
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines.contains(&dir_colour.paint("one.d").to_string().as_str()));
+        assert!(lines.contains(
+            &ANSIStrings(&[
+                dir_colour
+                    .dimmed()
+                    .paint(format!("one.d{}", MAIN_SEPARATOR)),
+                dir_colour.paint("two.d"),
+            ])
+            .to_string()
+            .as_str()
+        ));
+        assert!(lines.contains(
+            &ANSIStrings(&[
+                file_colour
+                    .dimmed()
+                    .paint(format!("one.d{0}two.d{0}", MAIN_SEPARATOR)),
+                file_colour.paint("three"),
+            ])
+            .to_string()
+            .as_str()
+        ));
+    }
+
+    #[test]
+    fn test_tree_crlf_line_ending() {
+        let argv = vec!["lsd", "--tree", "--line-ending", "crlf"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one.d").create_dir_all().unwrap();
+        dir.child("one.d/two").touch().unwrap();
+        let metas = Meta::from_path(Path::new(dir.path()), false, &UsersCache::new())
+            .unwrap()
+            .recurse_into(42, &flags, &UsersCache::new())
+            .unwrap()
+            .unwrap();
+        let output = tree(
+            &metas,
+            &flags,
+            &Colors::new(color::Theme::NoColor),
+            &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+        );
+
+        assert_eq!("one.d\r\n└── two\r\n", output);
+    }
+
+    #[test]
+    fn test_grid_crlf_line_ending() {
+        let argv = vec!["lsd", "--oneline", "--line-ending", "crlf"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one").touch().unwrap();
+        dir.child("two.d").create_dir_all().unwrap();
+        dir.child("two.d/three").touch().unwrap();
+        let mut metas = vec![
+            Meta::from_path(&dir.path().join("one"), false, &UsersCache::new()).unwrap(),
+            Meta::from_path(&dir.path().join("two.d"), false, &UsersCache::new()).unwrap(),
+        ];
+        metas[1].content = metas[1]
+            .recurse_into(42, &flags, &UsersCache::new())
+            .unwrap();
+        let output = inner_display_grid(
+            &DisplayOption::None,
+            &metas,
+            &flags,
+            &Colors::new(color::Theme::NoColor),
+            &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+            0,
+            None,
+        );
+
+        assert_eq!(
+            format!(
+                "{}\r\n\r\n{}:\r\nthree\r\n",
+                dir.path().join("one").display(),
+                dir.path().join("two.d").display()
+            ),
+            output
+        );
+    }
+
+    #[test]
+    fn test_grid_size_percent_block() {
+        let argv = vec!["lsd", "--oneline", "--blocks", "size_percent,name"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("a").write_binary(&[0; 25]).unwrap();
+        dir.child("b").write_binary(&[0; 75]).unwrap();
+        dir.child("c.d").create_dir_all().unwrap();
+        let mut metas = Meta::from_path(Path::new(dir.path()), false, &UsersCache::new())
+            .unwrap()
+            .recurse_into(1, &flags, &UsersCache::new())
+            .unwrap()
+            .unwrap();
+        sort(&mut metas, &sort::assemble_sorters(&flags));
+        let output = inner_display_grid(
+            &DisplayOption::FileName,
+            &metas,
+            &flags,
+            &Colors::new(color::Theme::NoColor),
+            &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+            1,
+            None,
+        );
+
+        assert_eq!(" 25% a\n 75% b\n-    c.d\n", output);
+    }
+
+    #[test]
+    fn test_grid_size_bar_block() {
+        let argv = vec!["lsd", "--oneline", "--blocks", "size_bar,name"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("a").write_binary(&[0; 30]).unwrap();
+        dir.child("b").write_binary(&[0; 100]).unwrap();
+        dir.child("c.d").create_dir_all().unwrap();
+        let mut metas = Meta::from_path(Path::new(dir.path()), false, &UsersCache::new())
+            .unwrap()
+            .recurse_into(1, &flags, &UsersCache::new())
+            .unwrap()
+            .unwrap();
+        sort(&mut metas, &sort::assemble_sorters(&flags));
+        let output = inner_display_grid(
+            &DisplayOption::FileName,
+            &metas,
+            &flags,
+            &Colors::new(color::Theme::NoColor),
+            &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+            1,
+            None,
+        );
+
+        assert_eq!("███░░░░░░░ a\n██████████ b\n           c.d\n", output);
+    }
+
+    #[test]
+    fn test_grid_size_percent_block_with_total_size() {
+        let argv = vec![
+            "lsd",
+            "--oneline",
+            "--total-size",
+            "--blocks",
+            "size_percent,name",
+        ];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("a").write_binary(&[0; 100]).unwrap();
+        dir.child("c.d").create_dir_all().unwrap();
+        let mut metas = Meta::from_path(Path::new(dir.path()), false, &UsersCache::new())
+            .unwrap()
+            .recurse_into(1, &flags, &UsersCache::new())
+            .unwrap()
+            .unwrap();
+        for meta in &mut metas {
+            meta.calculate_total_size(&flags.ignore_globs);
+        }
+        let dir_size = metas
+            .iter()
+            .find(|meta| meta.name.name == "c.d")
+            .unwrap()
+            .size
+            .get_bytes();
+
+        let output = inner_display_grid(
+            &DisplayOption::FileName,
+            &metas,
+            &flags,
+            &Colors::new(color::Theme::NoColor),
+            &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+            1,
+            None,
+        );
+
+        let percent = (dir_size * 100 + (dir_size + 100) / 2) / (dir_size + 100);
+        assert!(output.contains(&format!("{}% c.d", percent)));
+        assert!(!output.contains("-"));
+    }
+
+    #[test]
+    fn test_type_histogram() {
+        let argv = vec!["lsd", "--recursive"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        for name in &[
+            "one.rs",
+            "two.rs",
+            "sub.d/three.RS",
+            "sub.d/notes.txt",
+            "README",
+        ] {
+            dir.child(name).touch().unwrap();
+        }
+        let mut meta = Meta::from_path(Path::new(dir.path()), false, &UsersCache::new()).unwrap();
+        meta.content = meta.recurse_into(42, &flags, &UsersCache::new()).unwrap();
+
+        let output = type_histogram(&[meta], &Colors::new(color::Theme::NoColor));
+        assert_eq!(
+            ".rs       3 ████████████████████\n\
+             .txt      1 ███████\n\
+             directory 1 ███████\n\
+             file      1 ███████\n",
+            output
+        );
+    }
+
+    #[test]
+    fn test_legend() {
+        let output = legend(
+            &Colors::new(color::Theme::NoColor),
+            &Icons::new(icon::Theme::Fancy, " ".to_string()),
+        );
+
+        assert!(output.contains("\u{f115} directory\n"));
+        assert!(output.contains(" symbolic link\n"));
+        assert!(output.contains("modified within the last hour"));
+        assert!(output.contains("size of a file smaller than a megabyte"));
+    }
+
+    #[test]
+    fn test_grid_min_column_width() {
+        let argv = vec!["lsd", "--min-column-width", "6"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("a").touch().unwrap();
+        dir.child("long-name").touch().unwrap();
+        let mut metas = Meta::from_path(Path::new(dir.path()), false, &UsersCache::new())
+            .unwrap()
+            .recurse_into(1, &flags, &UsersCache::new())
+            .unwrap()
+            .unwrap();
+        sort(&mut metas, &sort::assemble_sorters(&flags));
+        let output = inner_display_grid(
+            &DisplayOption::FileName,
+            &metas,
+            &flags,
+            &Colors::new(color::Theme::NoColor),
+            &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+            1,
+            Some(80),
+        );
+
+        // "a" is padded to 6 cells, while "long-name" is already wider.
+        assert_eq!("a       long-name\n", output);
+    }
+
+    #[test]
+    fn test_tree_max_lines() {
+        let argv = vec!["lsd", "--tree", "--max-lines", "3"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one.d/two.d/three").touch().unwrap();
+        dir.child("one.d/two.d/four").touch().unwrap();
+        dir.child("one.d/two.d/five").touch().unwrap();
+        let mut metas = Meta::from_path(Path::new(dir.path()), false, &UsersCache::new())
+            .unwrap()
+            .recurse_into(42, &flags, &UsersCache::new())
+            .unwrap()
+            .unwrap();
+        sort(&mut metas, &sort::assemble_sorters(&flags));
+        let output = tree(
+            &metas,
+            &flags,
+            &Colors::new(color::Theme::NoColor),
+            &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+        );
+
+        assert_eq!("one.d\n└── two.d\n    ├── five\n… (truncated)\n", output);
+    }
+
+    #[test]
+    fn test_tree_name_width() {
+        let argv = vec!["lsd", "--tree", "--tree-name-width", "5"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one.d/abcdefghij").touch().unwrap();
+        dir.child("one.d/klm").touch().unwrap();
+        let mut metas = Meta::from_path(Path::new(dir.path()), false, &UsersCache::new())
+            .unwrap()
+            .recurse_into(42, &flags, &UsersCache::new())
+            .unwrap()
+            .unwrap();
+        sort(&mut metas, &sort::assemble_sorters(&flags));
+        let output = tree(
+            &metas,
+            &flags,
+            &Colors::new(color::Theme::NoColor),
+            &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+        );
+
+        assert_eq!("one.d\n├── abcde\n│   fghij\n└── klm\n", output);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_tree_mark_mounts() {
+        use crate::meta::Device;
+
+        let argv = vec!["lsd", "--tree", "--mark-mounts"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("mnt.d/inner.d").create_dir_all().unwrap();
+        dir.child("file").touch().unwrap();
+        let render = |root: &Meta| {
+            let mut metas = root
+                .recurse_into(42, &flags, &UsersCache::new())
+                .unwrap()
+                .unwrap();
+            sort(&mut metas, &sort::assemble_sorters(&flags));
+            tree(
+                &metas,
+                &flags,
+                &Colors::new(color::Theme::NoColor),
+                &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+            )
+        };
+
+        // the whole listing is on the same filesystem
+        let mut root = Meta::from_path(Path::new(dir.path()), false, &UsersCache::new()).unwrap();
+        assert_eq!("file\nmnt.d\n└── inner.d\n", render(&root));
+
+        // a root on another device makes its directories mount points
+        root.device = Device::none();
+        assert_eq!("file\nmnt.d [mount]\n└── inner.d\n", render(&root));
+    }
+
+    #[test]
+    fn test_tree_name_width_with_blocks() {
+        let argv = vec![
+            "lsd",
+            "--tree",
+            "--tree-name-width",
+            "3",
+            "--blocks",
+            "depth,name",
+        ];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one/abcdef").touch().unwrap();
+        let metas = Meta::from_path(Path::new(dir.path()), false, &UsersCache::new())
+            .unwrap()
+            .recurse_into(42, &flags, &UsersCache::new())
+            .unwrap()
+            .unwrap();
+        let output = tree(
+            &metas,
+            &flags,
+            &Colors::new(color::Theme::NoColor),
+            &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+        );
+
+        assert_eq!("0 one\n1 └── abc\n      def\n", output);
+    }
+
+    #[test]
+    fn test_wrap_visible_colors() {
+        let input = Colour::Fixed(184).paint("abcdef").to_string();
+
+        assert_eq!(
+            vec![
+                format!("{}abcd\u{1b}[0m", Colour::Fixed(184).prefix()),
+                Colour::Fixed(184).paint("ef").to_string(),
+            ],
+            wrap_visible(&input, 4)
+        );
+    }
+
+    #[test]
+    fn test_tree_max_lines_not_reached() {
+        let argv = vec!["lsd", "--tree", "--max-lines", "2"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one.d/two").touch().unwrap();
+        let metas = Meta::from_path(Path::new(dir.path()), false, &UsersCache::new())
+            .unwrap()
+            .recurse_into(42, &flags, &UsersCache::new())
+            .unwrap()
+            .unwrap();
+        let output = tree(
+            &metas,
+            &flags,
+            &Colors::new(color::Theme::NoColor),
+            &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+        );
+
+        assert_eq!("one.d\n└── two\n", output);
+    }
+
+    #[test]
+    fn test_grid_max_lines() {
+        let argv = vec![
+            "lsd",
+            "--oneline",
+            "--max-lines",
+            "2",
+            "--line-ending",
+            "crlf",
+        ];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("one").touch().unwrap();
+        dir.child("three").touch().unwrap();
+        dir.child("two").touch().unwrap();
+        let mut metas = Meta::from_path(Path::new(dir.path()), false, &UsersCache::new())
+            .unwrap()
+            .recurse_into(1, &flags, &UsersCache::new())
+            .unwrap()
+            .unwrap();
+        sort(&mut metas, &sort::assemble_sorters(&flags));
+        let output = truncate_lines(
+            inner_display_grid(
+                &DisplayOption::FileName,
+                &metas,
+                &flags,
+                &Colors::new(color::Theme::NoColor),
+                &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+                1,
+                Some(80),
+            ),
+            &flags,
+        );
+
+        assert_eq!("one\r\nthree\r\n… (truncated)\r\n", output);
+    }
+
+    #[test]
+    fn test_grid_rtl() {
+        let argv = vec!["lsd", "--rtl"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        dir.child("a").touch().unwrap();
+        dir.child("bb").touch().unwrap();
+        dir.child("c").touch().unwrap();
+        let mut metas = Meta::from_path(Path::new(dir.path()), false, &UsersCache::new())
+            .unwrap()
+            .recurse_into(1, &flags, &UsersCache::new())
+            .unwrap()
+            .unwrap();
+        sort(&mut metas, &sort::assemble_sorters(&flags));
+        let render = |term_width| {
+            inner_display_grid(
+                &DisplayOption::FileName,
+                &metas,
+                &flags,
+                &Colors::new(color::Theme::NoColor),
+                &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+                1,
+                Some(term_width),
+            )
+        };
+
+        // the first entry is in the rightmost column
+        assert_eq!("c  bb  a\n", render(80));
+        // the entries are aligned to the right when they do not fit on a line
+        assert_eq!(" a\nbb\n c\n", render(2));
+    }
 }
@@ -1,42 +1,159 @@
+pub mod access_check;
+pub mod anonymize;
+pub mod archive;
 pub mod blocks;
+pub mod bundle_extensions;
+pub mod checksum_verify;
+pub mod collapse_repeats;
 pub mod color;
+pub mod color_by_owner;
+pub mod columns;
+pub mod content_on_trailing_slash;
 pub mod date;
+pub mod date_color_scale;
+pub mod debug_layout;
+pub mod dedup;
 pub mod dereference;
+pub mod dereference_command_line_symlink_to_dir;
+pub mod dim_hidden;
+pub mod dir_child_count;
 pub mod display;
+pub mod emphasize_extension;
+pub mod expand_paths;
+pub mod follow_dir_symlinks;
+pub mod font_check;
+pub mod full_path;
+pub mod git_symbols;
+pub mod glyphs;
+pub mod group_by_date;
+pub mod highlight_sparse;
+pub mod hyperlink;
 pub mod icons;
 pub mod ignore_globs;
 pub mod indicators;
+pub mod inode_hex;
 pub mod layout;
+pub mod legend;
+pub mod line_ending;
+pub mod links_only_multi;
+pub mod mark_mounts;
+pub mod max_lines;
+pub mod min_column_width;
+pub mod no_metadata;
+pub mod numeric;
+pub mod output_format;
+pub mod owner_lookup_timeout;
+pub mod permission;
+pub mod permission_heatmap;
+pub mod progress;
 pub mod recursion;
+pub mod rtl;
+pub mod scan_budget;
+pub mod show_relative_prefix;
+pub mod si_units;
 pub mod size;
+pub mod size_units;
+pub mod size_width;
+pub mod sort_command;
 pub mod sorting;
+pub mod styled_link_target;
 pub mod symlink_arrow;
+pub mod symlink_root;
 pub mod symlinks;
+pub mod theme;
+pub mod time;
+pub mod time_filter;
+pub mod time_zone;
+pub mod top;
+pub mod total;
 pub mod total_size;
+pub mod tree_name_width;
+pub mod type_filter;
+pub mod type_histogram;
+pub mod type_prefix;
 
+pub use access_check::AccessCheck;
+pub use anonymize::Anonymize;
+pub use archive::Archive;
 pub use blocks::Block;
 pub use blocks::Blocks;
+pub use bundle_extensions::BundleExtensions;
+pub use checksum_verify::ChecksumVerify;
+pub use collapse_repeats::CollapseRepeats;
 pub use color::Color;
 pub use color::ColorOption;
+pub use color::ColorTheme;
+pub use color_by_owner::ColorByOwner;
+pub use columns::Columns;
+pub use content_on_trailing_slash::ContentOnTrailingSlash;
 pub use date::DateFlag;
+pub use date_color_scale::DateColorScale;
+pub use debug_layout::DebugLayout;
+pub use dedup::Dedup;
 pub use dereference::Dereference;
+pub use dereference_command_line_symlink_to_dir::DereferenceCommandLineSymlinkToDir;
+pub use dim_hidden::DimHidden;
+pub use dir_child_count::DirChildCount;
 pub use display::Display;
+pub use emphasize_extension::EmphasizeExtension;
+pub use expand_paths::ExpandPaths;
+pub use follow_dir_symlinks::FollowDirSymlinks;
+pub use font_check::FontCheck;
+pub use full_path::FullPath;
+pub use git_symbols::GitSymbols;
+pub use glyphs::Glyphs;
+pub use group_by_date::GroupByDate;
+pub use highlight_sparse::HighlightSparse;
+pub use hyperlink::HyperlinkOption;
 pub use icons::IconOption;
 pub use icons::IconSeparator;
 pub use icons::IconTheme;
 pub use icons::Icons;
 pub use ignore_globs::IgnoreGlobs;
 pub use indicators::Indicators;
+pub use inode_hex::InodeHex;
 pub use layout::Layout;
+pub use legend::Legend;
+pub use line_ending::LineEnding;
+pub use links_only_multi::LinksOnlyMulti;
+pub use mark_mounts::MarkMounts;
+pub use max_lines::MaxLines;
+pub use min_column_width::MinColumnWidth;
+pub use no_metadata::NoMetadata;
+pub use numeric::Numeric;
+pub use output_format::OutputFormat;
+pub use owner_lookup_timeout::OwnerLookupTimeout;
+pub use permission::PermissionFlag;
+pub use permission_heatmap::PermissionHeatmap;
+pub use progress::Progress;
 pub use recursion::Recursion;
+pub use rtl::Rtl;
+pub use scan_budget::ScanBudget;
+pub use show_relative_prefix::ShowRelativePrefix;
+pub use si_units::SiUnits;
 pub use size::SizeFlag;
+pub use size_units::SizeUnits;
+pub use size_width::SizeWidth;
+pub use sort_command::SortCommand;
 pub use sorting::DirGrouping;
 pub use sorting::SortColumn;
+pub use sorting::SortDotfiles;
 pub use sorting::SortOrder;
 pub use sorting::Sorting;
+pub use styled_link_target::StyledLinkTarget;
 pub use symlink_arrow::SymlinkArrow;
+pub use symlink_root::SymlinkRoot;
 pub use symlinks::NoSymlink;
+pub use time::TimeFlag;
+pub use time_filter::TimeFilter;
+pub use time_zone::TimeZoneFlag;
+pub use top::Top;
+pub use total::Total;
 pub use total_size::TotalSize;
+pub use tree_name_width::TreeNameWidth;
+pub use type_filter::TypeFilter;
+pub use type_histogram::TypeHistogram;
+pub use type_prefix::TypePrefix;
 
 use crate::config_file::Config;
 
@@ -48,21 +165,78 @@ use yaml_rust::Yaml;
 /// A struct to hold all set configuration flags for the application.
 #[derive(Clone, Debug, Default)]
 pub struct Flags {
+    pub access_check: AccessCheck,
+    pub anonymize: Anonymize,
+    pub archive: Archive,
     pub blocks: Blocks,
+    pub bundle_extensions: BundleExtensions,
+    pub checksum_verify: ChecksumVerify,
+    pub collapse_repeats: CollapseRepeats,
     pub color: Color,
+    pub color_by_owner: ColorByOwner,
+    pub columns: Columns,
+    pub content_on_trailing_slash: ContentOnTrailingSlash,
     pub date: DateFlag,
+    pub date_color_scale: DateColorScale,
+    pub debug_layout: DebugLayout,
+    pub dedup: Dedup,
     pub dereference: Dereference,
+    pub dereference_command_line_symlink_to_dir: DereferenceCommandLineSymlinkToDir,
+    pub dim_hidden: DimHidden,
+    pub dir_child_count: DirChildCount,
     pub display: Display,
+    pub emphasize_extension: EmphasizeExtension,
+    pub expand_paths: ExpandPaths,
+    pub follow_dir_symlinks: FollowDirSymlinks,
+    pub font_check: FontCheck,
+    pub git_symbols: GitSymbols,
+    pub full_path: FullPath,
+    pub glyphs: Glyphs,
+    pub group_by_date: GroupByDate,
     pub display_indicators: Indicators,
+    pub highlight_sparse: HighlightSparse,
+    pub hyperlink: HyperlinkOption,
     pub icons: Icons,
     pub ignore_globs: IgnoreGlobs,
+    pub inode_hex: InodeHex,
     pub layout: Layout,
+    pub legend: Legend,
+    pub line_ending: LineEnding,
+    pub links_only_multi: LinksOnlyMulti,
+    pub mark_mounts: MarkMounts,
+    pub max_lines: MaxLines,
+    pub min_column_width: MinColumnWidth,
+    pub no_metadata: NoMetadata,
+    pub numeric: Numeric,
     pub no_symlink: NoSymlink,
+    pub output_format: OutputFormat,
+    pub owner_lookup_timeout: OwnerLookupTimeout,
+    pub permission: PermissionFlag,
+    pub permission_heatmap: PermissionHeatmap,
+    pub progress: Progress,
     pub recursion: Recursion,
+    pub rtl: Rtl,
+    pub scan_budget: ScanBudget,
+    pub show_relative_prefix: ShowRelativePrefix,
+    pub si_units: SiUnits,
     pub size: SizeFlag,
+    pub size_units: SizeUnits,
+    pub size_width: SizeWidth,
     pub sorting: Sorting,
+    pub sort_command: SortCommand,
+    pub total: Total,
     pub total_size: TotalSize,
+    pub styled_link_target: StyledLinkTarget,
     pub symlink_arrow: SymlinkArrow,
+    pub symlink_root: SymlinkRoot,
+    pub time: TimeFlag,
+    pub time_filter: TimeFilter,
+    pub time_zone: TimeZoneFlag,
+    pub top: Top,
+    pub tree_name_width: TreeNameWidth,
+    pub type_filter: TypeFilter,
+    pub type_histogram: TypeHistogram,
+    pub type_prefix: TypePrefix,
 }
 
 impl Flags {
@@ -70,25 +244,83 @@ impl Flags {
     ///
     /// # Errors
     ///
-    /// This can return an [Error], when either the building of the ignore globs or the parsing of
-    /// the recursion depth parameter fails.
+    /// This can return an [Error], when either the building of the ignore globs, the parsing of
+    /// the recursion depth parameter or the reading of the checksum manifest fails.
     pub fn configure_from(matches: &ArgMatches, config: &Config) -> Result<Self, Error> {
         Ok(Self {
+            access_check: AccessCheck::configure_from(matches, config),
+            anonymize: Anonymize::configure_from(matches, config),
+            archive: Archive::configure_from(matches, config),
             blocks: Blocks::configure_from(matches, config)?,
+            bundle_extensions: BundleExtensions::configure_from(matches, config),
+            checksum_verify: ChecksumVerify::configure_from(matches)?,
+            collapse_repeats: CollapseRepeats::configure_from(matches, config),
             color: Color::configure_from(matches, config),
+            color_by_owner: ColorByOwner::configure_from(matches, config),
+            columns: Columns::configure_from(matches, config),
+            content_on_trailing_slash: ContentOnTrailingSlash::configure_from(matches, config),
             date: DateFlag::configure_from(matches, config),
+            date_color_scale: DateColorScale::configure_from(matches, config),
+            debug_layout: DebugLayout::configure_from(matches, config),
+            dedup: Dedup::configure_from(matches, config),
             dereference: Dereference::configure_from(matches, config),
+            dereference_command_line_symlink_to_dir:
+                DereferenceCommandLineSymlinkToDir::configure_from(matches, config),
+            dim_hidden: DimHidden::configure_from(matches, config),
+            dir_child_count: DirChildCount::configure_from(matches, config),
             display: Display::configure_from(matches, config),
+            emphasize_extension: EmphasizeExtension::configure_from(matches, config),
+            expand_paths: ExpandPaths::configure_from(matches, config),
+            follow_dir_symlinks: FollowDirSymlinks::configure_from(matches, config),
+            font_check: FontCheck::configure_from(matches, config),
+            git_symbols: GitSymbols::configure_from(matches, config),
+            full_path: FullPath::configure_from(matches, config),
+            glyphs: Glyphs::configure_from(matches, config),
+            group_by_date: GroupByDate::configure_from(matches, config),
             layout: Layout::configure_from(matches, config),
+            legend: Legend::configure_from(matches, config),
+            line_ending: LineEnding::configure_from(matches, config),
+            links_only_multi: LinksOnlyMulti::configure_from(matches, config),
+            show_relative_prefix: ShowRelativePrefix::configure_from(matches, config),
+            si_units: SiUnits::configure_from(matches, config),
             size: SizeFlag::configure_from(matches, config),
+            size_units: SizeUnits::configure_from(matches, config),
+            size_width: SizeWidth::configure_from(matches, config),
             display_indicators: Indicators::configure_from(matches, config),
+            highlight_sparse: HighlightSparse::configure_from(matches, config),
+            hyperlink: HyperlinkOption::configure_from(matches, config),
             icons: Icons::configure_from(matches, config),
             ignore_globs: IgnoreGlobs::configure_from(matches, config)?,
+            inode_hex: InodeHex::configure_from(matches, config),
+            mark_mounts: MarkMounts::configure_from(matches, config),
+            max_lines: MaxLines::configure_from(matches, config),
+            min_column_width: MinColumnWidth::configure_from(matches, config),
+            no_metadata: NoMetadata::configure_from(matches, config),
+            numeric: Numeric::configure_from(matches, config),
             no_symlink: NoSymlink::configure_from(matches, config),
+            output_format: OutputFormat::configure_from(matches, config),
+            owner_lookup_timeout: OwnerLookupTimeout::configure_from(matches, config),
+            permission: PermissionFlag::configure_from(matches, config),
+            permission_heatmap: PermissionHeatmap::configure_from(matches, config),
+            progress: Progress::configure_from(matches, config),
             recursion: Recursion::configure_from(matches, config)?,
+            rtl: Rtl::configure_from(matches, config),
+            scan_budget: ScanBudget::configure_from(matches, config),
             sorting: Sorting::configure_from(matches, config),
+            sort_command: SortCommand::configure_from(matches, config),
+            total: Total::configure_from(matches, config),
             total_size: TotalSize::configure_from(matches, config),
+            styled_link_target: StyledLinkTarget::configure_from(matches, config),
             symlink_arrow: SymlinkArrow::configure_from(matches, config),
+            symlink_root: SymlinkRoot::configure_from(matches, config),
+            time: TimeFlag::configure_from(matches, config),
+            time_filter: TimeFilter::configure_from(matches)?,
+            time_zone: TimeZoneFlag::configure_from(matches, config),
+            top: Top::configure_from(matches, config),
+            tree_name_width: TreeNameWidth::configure_from(matches, config),
+            type_filter: TypeFilter::configure_from(matches, config),
+            type_histogram: TypeHistogram::configure_from(matches, config),
+            type_prefix: TypePrefix::configure_from(matches, config),
         })
     }
 }
@@ -0,0 +1,64 @@
+//! This module defines the [AccessCheck] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to display what the current user is allowed to do with each file.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct AccessCheck(pub bool);
+
+impl Configurable<Self> for AccessCheck {
+    /// Get a potential `AccessCheck` value from [ArgMatches].
+    ///
+    /// If the "access-check" argument is passed, this returns an `AccessCheck` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("access-check") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `AccessCheck` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::AccessCheck;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, AccessCheck::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--access-check"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(AccessCheck(true)),
+            AccessCheck::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, AccessCheck::from_config(&Config::with_none()));
+    }
+}
@@ -0,0 +1,61 @@
+//! This module defines the [Anonymize] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to hide the user and group names behind placeholders.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Anonymize(pub bool);
+
+impl Configurable<Self> for Anonymize {
+    /// Get a potential `Anonymize` value from [ArgMatches].
+    ///
+    /// If the "anonymize" argument is passed, this returns an `Anonymize` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("anonymize") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `Anonymize` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Anonymize;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Anonymize::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--anonymize"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Anonymize(true)), Anonymize::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Anonymize::from_config(&Config::with_none()));
+    }
+}
@@ -0,0 +1,61 @@
+//! This module defines the [Archive] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to list the content of the tar archives instead of the archives.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Archive(pub bool);
+
+impl Configurable<Self> for Archive {
+    /// Get a potential `Archive` value from [ArgMatches].
+    ///
+    /// If the "archive" argument is passed, this returns an `Archive` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("archive") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `Archive` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Archive;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Archive::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--archive"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Archive(true)), Archive::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Archive::from_config(&Config::with_none()));
+    }
+}
@@ -26,9 +26,13 @@ impl Blocks {
     ///
     /// # Errors
     ///
-    /// This errors if any of the [ArgMatches] parameter arguments causes [Block]'s implementation
-    /// of [TryFrom::try_from] to return an [Err].
+    /// This errors if none of the [ArgMatches] parameter arguments is a valid [Block].
     pub fn configure_from(matches: &ArgMatches, config: &Config) -> Result<Self, Error> {
+        // the names are all that is known without the metadata
+        if matches.is_present("no-metadata") {
+            return Ok(Self(vec![Block::Name]));
+        }
+
         let mut result: Result<Self, Error> = if matches.is_present("long") {
             Ok(Self::long())
         } else {
@@ -56,30 +60,32 @@ impl Blocks {
 
     /// Get a potential `Blocks` struct from [ArgMatches].
     ///
-    /// If the "blocks" argument is passed, then this returns a `Blocks` containing the parameter
-    /// values in a [Some]. Otherwise if the "long" argument is passed, this returns
-    /// [Blocks::long]. Finally if none of the previous happened, this returns [None].
+    /// If the "blocks" argument is passed, then this returns a `Blocks` containing the blocks of
+    /// all its occurrences, in order, in a [Some]. Each parameter may hold several blocks
+    /// separated by commas, semicolons or spaces. The invalid blocks are skipped with a warning.
+    /// Otherwise if the "blocks" argument is not passed, this returns [None].
     ///
     /// # Errors
     ///
-    /// This errors if any of the parameter arguments causes [Block]'s implementation of
-    /// [TryFrom::try_from] to return an [Err].
+    /// This errors if none of the parameter arguments is a valid [Block].
     fn from_arg_matches(matches: &ArgMatches) -> Option<Result<Self, Error>> {
         if matches.occurrences_of("blocks") > 0 {
-            if let Some(values) = matches.values_of("blocks") {
+            if matches.values_of("blocks").is_some() {
                 let mut blocks: Vec<Block> = vec![];
-                for value in values {
-                    match Block::try_from(value) {
+                for name in Self::arg_names(matches) {
+                    match Block::try_from(name) {
                         Ok(block) => blocks.push(block),
-                        Err(message) => {
-                            return Some(Err(Error::with_description(
-                                &message,
-                                ErrorKind::ValueValidation,
-                            )))
-                        }
+                        Err(err) => print_error!("{}.", err),
                     }
                 }
-                Some(Ok(Self(blocks)))
+                if blocks.is_empty() {
+                    Some(Err(Error::with_description(
+                        "No valid block name given to --blocks",
+                        ErrorKind::ValueValidation,
+                    )))
+                } else {
+                    Some(Ok(Self(blocks)))
+                }
             } else {
                 None
             }
@@ -88,6 +94,18 @@ impl Blocks {
         }
     }
 
+    /// Get the block names of all the "blocks" arguments, split at the commas, semicolons and
+    /// spaces.
+    pub fn arg_names<'a>(matches: &'a ArgMatches) -> Vec<&'a str> {
+        matches
+            .values_of("blocks")
+            .into_iter()
+            .flatten()
+            .flat_map(|value| value.split(|c: char| c == ',' || c == ';' || c.is_whitespace()))
+            .filter(|name| !name.is_empty())
+            .collect()
+    }
+
     /// Get a potential `Blocks` struct from a [Config].
     ///
     /// If the [Config] contains an array of blocks values,
@@ -165,6 +183,43 @@ pub enum Block {
     Name,
     INode,
     Links,
+    Depth,
+    FreeInodes,
+    SizePercent,
+    SizeBar,
+    GitDirSummary,
+    GitStatus,
+    AgeGap,
+    Device,
+    RealPath,
+    FileCount,
+}
+
+impl Block {
+    /// The name of the block, as given to the "blocks" argument.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Permission => "permission",
+            Self::User => "user",
+            Self::Group => "group",
+            Self::Size => "size",
+            Self::SizeValue => "size_value",
+            Self::Date => "date",
+            Self::Name => "name",
+            Self::INode => "inode",
+            Self::Links => "links",
+            Self::Depth => "depth",
+            Self::FreeInodes => "free_inodes",
+            Self::SizePercent => "size_percent",
+            Self::SizeBar => "size_bar",
+            Self::GitDirSummary => "git_dir_summary",
+            Self::GitStatus => "git",
+            Self::AgeGap => "age_gap",
+            Self::Device => "device",
+            Self::RealPath => "realpath",
+            Self::FileCount => "file_count",
+        }
+    }
 }
 
 impl TryFrom<&str> for Block {
@@ -181,6 +236,16 @@ impl TryFrom<&str> for Block {
             "name" => Ok(Self::Name),
             "inode" => Ok(Self::INode),
             "links" => Ok(Self::Links),
+            "depth" => Ok(Self::Depth),
+            "free_inodes" => Ok(Self::FreeInodes),
+            "size_percent" => Ok(Self::SizePercent),
+            "size_bar" => Ok(Self::SizeBar),
+            "git_dir_summary" => Ok(Self::GitDirSummary),
+            "git" => Ok(Self::GitStatus),
+            "age_gap" => Ok(Self::AgeGap),
+            "device" => Ok(Self::Device),
+            "realpath" => Ok(Self::RealPath),
+            "file_count" => Ok(Self::FileCount),
             _ => Err(format!("Not a valid block name: {}", &string)),
         }
     }
@@ -271,6 +336,24 @@ mod test_blocks {
         assert_eq_ok!(result, target);
     }
 
+    #[test]
+    fn test_configure_from_with_no_metadata() {
+        let argv = vec![
+            "lsd",
+            "--long",
+            "--inode",
+            "--blocks",
+            "size,name",
+            "--no-metadata",
+        ];
+        let target = Ok::<_, Error>(Blocks(vec![Block::Name]));
+
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let result = Blocks::configure_from(&matches, &Config::with_none());
+
+        assert_eq_ok!(result, target);
+    }
+
     #[test]
     fn test_configure_from_prepend_inode_without_long() {
         let argv = vec!["lsd", "--blocks", "permission", "--inode"];
@@ -351,6 +434,51 @@ mod test_blocks {
         });
     }
 
+    #[test]
+    fn test_from_arg_matches_multi_occurences_multi_values() {
+        let argv = vec!["lsd", "--blocks", "permission,size", "--blocks", "name"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let test_blocks = Blocks(vec![Block::Permission, Block::Size, Block::Name]);
+        assert!(matches!(
+            Blocks::from_arg_matches(&matches),
+            Some(Ok(blocks)) if blocks == test_blocks
+        ));
+    }
+
+    #[test]
+    fn test_from_arg_matches_mixed_delimiters() {
+        let argv = vec!["lsd", "--blocks", "permission; size  name,,date"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let test_blocks = Blocks(vec![
+            Block::Permission,
+            Block::Size,
+            Block::Name,
+            Block::Date,
+        ]);
+        assert!(matches!(
+            Blocks::from_arg_matches(&matches),
+            Some(Ok(blocks)) if blocks == test_blocks
+        ));
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid_is_skipped() {
+        let argv = vec!["lsd", "--blocks", "permission,foo", "--blocks", "name"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let test_blocks = Blocks(vec![Block::Permission, Block::Name]);
+        assert!(matches!(
+            Blocks::from_arg_matches(&matches),
+            Some(Ok(blocks)) if blocks == test_blocks
+        ));
+    }
+
+    #[test]
+    fn test_from_arg_matches_only_invalid() {
+        let argv = vec!["lsd", "--blocks", "foo"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(matches!(Blocks::from_arg_matches(&matches), Some(Err(_))));
+    }
+
     #[test]
     fn test_from_arg_matches_multi_values() {
         let argv = vec!["lsd", "--blocks", "permission,name"];
@@ -506,4 +634,81 @@ mod test_block {
     fn test_links() {
         assert_eq!(Ok(Block::Links), Block::try_from("links"));
     }
+
+    #[test]
+    fn test_depth() {
+        assert_eq!(Ok(Block::Depth), Block::try_from("depth"));
+    }
+
+    #[test]
+    fn test_free_inodes() {
+        assert_eq!(Ok(Block::FreeInodes), Block::try_from("free_inodes"));
+    }
+
+    #[test]
+    fn test_size_percent() {
+        assert_eq!(Ok(Block::SizePercent), Block::try_from("size_percent"));
+    }
+
+    #[test]
+    fn test_size_bar() {
+        assert_eq!(Ok(Block::SizeBar), Block::try_from("size_bar"));
+    }
+
+    #[test]
+    fn test_git_dir_summary() {
+        assert_eq!(Ok(Block::GitDirSummary), Block::try_from("git_dir_summary"));
+    }
+
+    #[test]
+    fn test_git_status() {
+        assert_eq!(Ok(Block::GitStatus), Block::try_from("git"));
+    }
+
+    #[test]
+    fn test_age_gap() {
+        assert_eq!(Ok(Block::AgeGap), Block::try_from("age_gap"));
+    }
+
+    #[test]
+    fn test_device() {
+        assert_eq!(Ok(Block::Device), Block::try_from("device"));
+    }
+
+    #[test]
+    fn test_realpath() {
+        assert_eq!(Ok(Block::RealPath), Block::try_from("realpath"));
+    }
+
+    #[test]
+    fn test_file_count() {
+        assert_eq!(Ok(Block::FileCount), Block::try_from("file_count"));
+    }
+
+    #[test]
+    fn test_as_str_round_trip() {
+        for block in &[
+            Block::Permission,
+            Block::User,
+            Block::Group,
+            Block::Size,
+            Block::SizeValue,
+            Block::Date,
+            Block::Name,
+            Block::INode,
+            Block::Links,
+            Block::Depth,
+            Block::FreeInodes,
+            Block::SizePercent,
+            Block::SizeBar,
+            Block::GitDirSummary,
+            Block::GitStatus,
+            Block::AgeGap,
+            Block::Device,
+            Block::RealPath,
+            Block::FileCount,
+        ] {
+            assert_eq!(Ok(*block), Block::try_from(block.as_str()));
+        }
+    }
 }
@@ -0,0 +1,118 @@
+//! This module defines the [BundleExtensions] flag. To set it up from [ArgMatches], a [Config]
+//! and its [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+use crate::meta::{FileType, Name};
+
+use clap::ArgMatches;
+
+/// The flag holding the extensions of the directories to show as single entries, like the
+/// `.app` bundles on macOS.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct BundleExtensions(pub Vec<String>);
+
+impl BundleExtensions {
+    /// Whether the `name` is the one of a directory with one of the bundle extensions.
+    pub fn is_bundle(&self, name: &Name) -> bool {
+        if !matches!(name.file_type(), FileType::Directory { .. }) {
+            return false;
+        }
+
+        match name.extension() {
+            Some(extension) => self
+                .0
+                .iter()
+                .any(|bundle| bundle.eq_ignore_ascii_case(extension)),
+            None => false,
+        }
+    }
+}
+
+impl Configurable<Self> for BundleExtensions {
+    /// Get a potential `BundleExtensions` value from [ArgMatches].
+    ///
+    /// If the "bundle-extension" argument is passed, this returns a `BundleExtensions` with all
+    /// its parameters in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        matches
+            .values_of("bundle-extension")
+            .map(|values| Self(values.map(String::from).collect()))
+    }
+
+    /// Get a potential `BundleExtensions` value from a [Config].
+    ///
+    /// If the `Config::bundle_extensions` has value, this returns it as a `BundleExtensions` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.bundle_extensions.clone().map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BundleExtensions;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+    use crate::meta::{FileType, Name};
+    use std::path::Path;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, BundleExtensions::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_multi() {
+        let argv = vec![
+            "lsd",
+            "--bundle-extension",
+            "app",
+            "--bundle-extension",
+            "framework",
+        ];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(BundleExtensions(vec![
+                "app".to_string(),
+                "framework".to_string()
+            ])),
+            BundleExtensions::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, BundleExtensions::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_extensions() {
+        let mut c = Config::with_none();
+        c.bundle_extensions = Some(vec!["app".to_string()]);
+        assert_eq!(
+            Some(BundleExtensions(vec!["app".to_string()])),
+            BundleExtensions::from_config(&c)
+        );
+    }
+
+    #[test]
+    fn test_is_bundle() {
+        let bundles = BundleExtensions(vec!["app".to_string()]);
+        let dir = FileType::Directory { uid: false };
+        let file = FileType::File {
+            exec: false,
+            uid: false,
+        };
+
+        assert!(bundles.is_bundle(&Name::new(Path::new("Safari.app"), dir)));
+        assert!(bundles.is_bundle(&Name::new(Path::new("Safari.APP"), dir)));
+        assert!(!bundles.is_bundle(&Name::new(Path::new("Safari.app"), file)));
+        assert!(!bundles.is_bundle(&Name::new(Path::new("Safari"), dir)));
+    }
+}
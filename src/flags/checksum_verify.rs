@@ -0,0 +1,80 @@
+//! This module defines the [ChecksumVerify] flag. To set it up from [ArgMatches], use the
+//! [configure_from](ChecksumVerify::configure_from) method.
+
+use crate::meta::checksum::Manifest;
+
+use clap::{ArgMatches, Error, ErrorKind};
+use std::path::Path;
+
+/// The flag holding the checksum manifest to verify the listed files against.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ChecksumVerify(pub Option<Manifest>);
+
+impl ChecksumVerify {
+    /// Get the `ChecksumVerify` value from [ArgMatches], reading the manifest given by the last
+    /// "checksum-verify" argument. It can not be configured by a config file.
+    ///
+    /// # Errors
+    ///
+    /// If the manifest can not be read.
+    pub fn configure_from(matches: &ArgMatches) -> Result<Self, Error> {
+        let path = match matches
+            .values_of_os("checksum-verify")
+            .and_then(|mut values| values.next_back())
+        {
+            Some(path) => Path::new(path),
+            None => return Ok(Self::default()),
+        };
+
+        match Manifest::from_file(path) {
+            Ok(manifest) => Ok(Self(Some(manifest))),
+            Err(err) => Err(Error::with_description(
+                &format!("failed to read the manifest {}: {}", path.display(), err),
+                ErrorKind::InvalidValue,
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ChecksumVerify;
+
+    use crate::app;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_configure_from_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            ChecksumVerify(None),
+            ChecksumVerify::configure_from(&matches).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_configure_from_manifest() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let manifest = tmp_dir.path().join("sha256sums");
+        fs::write(&manifest, "").unwrap();
+
+        let argv = vec!["lsd", "--checksum-verify", manifest.to_str().unwrap()];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(ChecksumVerify::configure_from(&matches)
+            .unwrap()
+            .0
+            .is_some());
+    }
+
+    #[test]
+    fn test_configure_from_missing_manifest() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let manifest = tmp_dir.path().join("missing");
+
+        let argv = vec!["lsd", "--checksum-verify", manifest.to_str().unwrap()];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(ChecksumVerify::configure_from(&matches).is_err());
+    }
+}
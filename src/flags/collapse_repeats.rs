@@ -0,0 +1,64 @@
+//! This module defines the [CollapseRepeats] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to blank the permissions, user and group repeating the previous row.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct CollapseRepeats(pub bool);
+
+impl Configurable<Self> for CollapseRepeats {
+    /// Get a potential `CollapseRepeats` value from [ArgMatches].
+    ///
+    /// If the "collapse-repeats" argument is passed, this returns a `CollapseRepeats` with value
+    /// `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("collapse-repeats") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `CollapseRepeats` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CollapseRepeats;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, CollapseRepeats::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--collapse-repeats"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(CollapseRepeats(true)),
+            CollapseRepeats::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, CollapseRepeats::from_config(&Config::with_none()));
+    }
+}
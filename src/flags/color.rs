@@ -1,6 +1,7 @@
 //! This module defines the [Color]. To set it up from [ArgMatches], a [Config] and its [Default]
 //! value, use its [configure_from](Configurable::configure_from) method.
 
+use super::theme;
 use super::Configurable;
 
 use crate::config_file::Config;
@@ -8,21 +9,51 @@ use crate::print_error;
 
 use clap::ArgMatches;
 use serde::Deserialize;
+use std::path::PathBuf;
 
 /// A collection of flags on how to use colors.
-#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub struct Color {
     /// When to use color.
     pub when: ColorOption,
+    /// Which color theme to use.
+    pub theme: ColorTheme,
+    /// The theme file of RGB colors overriding the ones of the color theme.
+    pub file: Option<PathBuf>,
+    /// The dircolors database read instead of the `LS_COLORS` environment variable.
+    pub ls_colors_file: Option<PathBuf>,
 }
 
 impl Color {
     /// Get a `Color` struct from [ArgMatches], a [Config] or the [Default] values.
     ///
-    /// The [ColorOption] is configured with their respective [Configurable] implementation.
+    /// The [ColorOption] and [ColorTheme] are configured with their respective [Configurable]
+    /// implementation, the theme file is the value of the "theme" naming no bundled theme.
     pub fn configure_from(matches: &ArgMatches, config: &Config) -> Self {
         let when = ColorOption::configure_from(matches, config);
-        Self { when }
+        let theme = ColorTheme::configure_from(matches, config);
+        let file = theme::file_configure_from(matches, config);
+        let ls_colors_file = Self::ls_colors_file_configure_from(matches, config);
+        Self {
+            when,
+            theme,
+            file,
+            ls_colors_file,
+        }
+    }
+
+    /// Get the path given to the last "ls-colors-file" argument, or else the
+    /// `Config::color::ls_colors_file`.
+    fn ls_colors_file_configure_from(matches: &ArgMatches, config: &Config) -> Option<PathBuf> {
+        match matches.values_of("ls-colors-file") {
+            Some(mut values) => values.next_back().map(PathBuf::from),
+            None => config
+                .color
+                .as_ref()?
+                .ls_colors_file
+                .as_ref()
+                .map(PathBuf::from),
+        }
     }
 }
 
@@ -98,6 +129,54 @@ impl Default for ColorOption {
     }
 }
 
+/// The flag showing which color theme to use.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColorTheme {
+    /// The colors for the dark terminals, overridden by the `LS_COLORS` environment variable.
+    Dark,
+    /// The colors for the dark terminals, ignoring the `LS_COLORS` environment variable.
+    Fixed,
+}
+
+impl Configurable<Self> for ColorTheme {
+    /// Get a potential `ColorTheme` variant from [ArgMatches].
+    ///
+    /// If the "color-theme" argument is passed, this returns the variant corresponding to its
+    /// parameter in a [Some]. Otherwise if the "theme" argument is passed, this returns the color
+    /// theme it bundles in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("color-theme") > 0 {
+            match matches.values_of("color-theme")?.next_back() {
+                Some("dark") => Some(Self::Dark),
+                Some("fixed") => Some(Self::Fixed),
+                _ => panic!("This should not be reachable!"),
+            }
+        } else {
+            theme::from_arg_matches(matches).map(|(color, _)| color)
+        }
+    }
+
+    /// Get a potential `ColorTheme` variant from a [Config].
+    ///
+    /// If the `Config::color::theme` has value, this returns it in a [Some]. Otherwise if the
+    /// `Config::theme` names a bundled theme, this returns its color theme in a [Some]. Otherwise
+    /// this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(theme) = config.color.as_ref().and_then(|color| color.theme) {
+            return Some(theme);
+        }
+        theme::from_config(config).map(|(color, _)| color)
+    }
+}
+
+/// The default value for `ColorTheme` is [ColorTheme::Dark].
+impl Default for ColorTheme {
+    fn default() -> Self {
+        Self::Dark
+    }
+}
+
 #[cfg(test)]
 mod test_color_option {
     use super::ColorOption;
@@ -173,6 +252,8 @@ mod test_color_option {
         let mut c = Config::with_none();
         c.color = Some(config_file::Color {
             when: ColorOption::Always,
+            theme: None,
+            ls_colors_file: None,
         });
 
         assert_eq!(Some(ColorOption::Always), ColorOption::from_config(&c));
@@ -183,6 +264,8 @@ mod test_color_option {
         let mut c = Config::with_none();
         c.color = Some(config_file::Color {
             when: ColorOption::Auto,
+            theme: None,
+            ls_colors_file: None,
         });
         assert_eq!(Some(ColorOption::Auto), ColorOption::from_config(&c));
     }
@@ -192,6 +275,8 @@ mod test_color_option {
         let mut c = Config::with_none();
         c.color = Some(config_file::Color {
             when: ColorOption::Never,
+            theme: None,
+            ls_colors_file: None,
         });
         assert_eq!(Some(ColorOption::Never), ColorOption::from_config(&c));
     }
@@ -201,8 +286,97 @@ mod test_color_option {
         let mut c = Config::with_none();
         c.color = Some(config_file::Color {
             when: ColorOption::Always,
+            theme: None,
+            ls_colors_file: None,
         });
         c.classic = Some(true);
         assert_eq!(Some(ColorOption::Never), ColorOption::from_config(&c));
     }
 }
+
+#[cfg(test)]
+mod test_color_theme {
+    use super::ColorTheme;
+
+    use crate::app;
+    use crate::config_file::{self, Config};
+    use crate::flags::color::ColorOption;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, ColorTheme::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_fixed() {
+        let argv = vec!["lsd", "--color-theme", "fixed"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(ColorTheme::Fixed),
+            ColorTheme::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_theme() {
+        let argv = vec!["lsd", "--theme", "fixed-fancy"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(ColorTheme::Fixed),
+            ColorTheme::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, ColorTheme::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_fixed() {
+        let mut c = Config::with_none();
+        c.theme = Some("dark-fancy".into());
+        c.color = Some(config_file::Color {
+            when: ColorOption::Auto,
+            theme: Some(ColorTheme::Fixed),
+            ls_colors_file: None,
+        });
+        assert_eq!(Some(ColorTheme::Fixed), ColorTheme::from_config(&c));
+    }
+}
+
+#[cfg(test)]
+mod test_color {
+    use super::Color;
+
+    use crate::app;
+    use crate::config_file::{self, Config};
+    use crate::flags::color::ColorOption;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_ls_colors_file_from_args_over_config() {
+        let mut c = Config::with_none();
+        c.color = Some(config_file::Color {
+            when: ColorOption::Auto,
+            theme: None,
+            ls_colors_file: Some("config.dircolors".into()),
+        });
+
+        let matches = app::build().get_matches_from_safe(vec!["lsd"]).unwrap();
+        assert_eq!(
+            Some(PathBuf::from("config.dircolors")),
+            Color::configure_from(&matches, &c).ls_colors_file
+        );
+
+        let argv = vec!["lsd", "--ls-colors-file", "args.dircolors"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(PathBuf::from("args.dircolors")),
+            Color::configure_from(&matches, &c).ls_colors_file
+        );
+    }
+}
@@ -0,0 +1,64 @@
+//! This module defines the [ColorByOwner] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to color each user name by a hash of it.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct ColorByOwner(pub bool);
+
+impl Configurable<Self> for ColorByOwner {
+    /// Get a potential `ColorByOwner` value from [ArgMatches].
+    ///
+    /// If the "color-by-owner" argument is passed, this returns a `ColorByOwner` with value `true`
+    /// in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("color-by-owner") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `ColorByOwner` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ColorByOwner;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, ColorByOwner::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--color-by-owner"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(ColorByOwner(true)),
+            ColorByOwner::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, ColorByOwner::from_config(&Config::with_none()));
+    }
+}
@@ -0,0 +1,74 @@
+//! This module defines the [Columns] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag holding the exact number of columns of the grid layout, regardless of the terminal
+/// width.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Columns(pub Option<usize>);
+
+impl Configurable<Self> for Columns {
+    /// Get a potential `Columns` value from [ArgMatches].
+    ///
+    /// If the "columns" argument is passed, this returns a `Columns` with its last parameter
+    /// in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        matches
+            .values_of("columns")?
+            .next_back()
+            .and_then(|columns| columns.parse().ok())
+            .map(|columns| Self(Some(columns)))
+    }
+
+    /// `Columns` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Columns;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Columns::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_columns() {
+        let argv = vec!["lsd", "--columns", "3"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Columns(Some(3))), Columns::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_zero() {
+        let argv = vec!["lsd", "--columns", "0"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--columns", "wide"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Columns::from_config(&Config::with_none()));
+    }
+}
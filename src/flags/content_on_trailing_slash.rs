@@ -0,0 +1,68 @@
+//! This module defines the [ContentOnTrailingSlash] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to list the content of the directory arguments only when they end
+/// with a path separator.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct ContentOnTrailingSlash(pub bool);
+
+impl Configurable<Self> for ContentOnTrailingSlash {
+    /// Get a potential `ContentOnTrailingSlash` value from [ArgMatches].
+    ///
+    /// If the "content-on-trailing-slash" argument is passed, this returns a
+    /// `ContentOnTrailingSlash` with value `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("content-on-trailing-slash") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `ContentOnTrailingSlash` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ContentOnTrailingSlash;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, ContentOnTrailingSlash::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--content-on-trailing-slash"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(ContentOnTrailingSlash(true)),
+            ContentOnTrailingSlash::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(
+            None,
+            ContentOnTrailingSlash::from_config(&Config::with_none())
+        );
+    }
+}
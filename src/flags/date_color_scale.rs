@@ -0,0 +1,64 @@
+//! This module defines the [DateColorScale] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to colour the dates along a gradient of their age in the listing.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct DateColorScale(pub bool);
+
+impl Configurable<Self> for DateColorScale {
+    /// Get a potential `DateColorScale` value from [ArgMatches].
+    ///
+    /// If the "date-color-scale" argument is passed, this returns a `DateColorScale` with value
+    /// `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("date-color-scale") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `DateColorScale` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DateColorScale;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, DateColorScale::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--date-color-scale"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DateColorScale(true)),
+            DateColorScale::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, DateColorScale::from_config(&Config::with_none()));
+    }
+}
@@ -0,0 +1,64 @@
+//! This module defines the [DebugLayout] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to report how the grid layout was fitted on the standard error.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct DebugLayout(pub bool);
+
+impl Configurable<Self> for DebugLayout {
+    /// Get a potential `DebugLayout` value from [ArgMatches].
+    ///
+    /// If the "debug-layout" argument is passed, this returns a `DebugLayout` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("debug-layout") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `DebugLayout` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DebugLayout;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, DebugLayout::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--debug-layout"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DebugLayout(true)),
+            DebugLayout::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, DebugLayout::from_config(&Config::with_none()));
+    }
+}
@@ -0,0 +1,61 @@
+//! This module defines the [Dedup] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to list only once the arguments that are the same file.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Dedup(pub bool);
+
+impl Configurable<Self> for Dedup {
+    /// Get a potential `Dedup` value from [ArgMatches].
+    ///
+    /// If the "dedup" argument is passed, this returns a `Dedup` with value `true` in a [Some].
+    /// Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("dedup") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `Dedup` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Dedup;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Dedup::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--dedup"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Dedup(true)), Dedup::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Dedup::from_config(&Config::with_none()));
+    }
+}
@@ -0,0 +1,72 @@
+//! This module defines the [DereferenceCommandLineSymlinkToDir] flag. To set it up from
+//! [ArgMatches], a [Config] and its [Default] value, use the
+//! [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to list the content of the directory symlinks given as arguments.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct DereferenceCommandLineSymlinkToDir(pub bool);
+
+impl Configurable<Self> for DereferenceCommandLineSymlinkToDir {
+    /// Get a potential `DereferenceCommandLineSymlinkToDir` value from [ArgMatches].
+    ///
+    /// If the "dereference-command-line-symlink-to-dir" argument is passed, this returns a
+    /// `DereferenceCommandLineSymlinkToDir` with value `true` in a [Some]. Otherwise this returns
+    /// [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("dereference-command-line-symlink-to-dir") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `DereferenceCommandLineSymlinkToDir` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DereferenceCommandLineSymlinkToDir;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            None,
+            DereferenceCommandLineSymlinkToDir::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--dereference-command-line-symlink-to-dir"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DereferenceCommandLineSymlinkToDir(true)),
+            DereferenceCommandLineSymlinkToDir::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(
+            None,
+            DereferenceCommandLineSymlinkToDir::from_config(&Config::with_none())
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! This module defines the [DimHidden] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to dim the icons of the hidden files.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct DimHidden(pub bool);
+
+impl Configurable<Self> for DimHidden {
+    /// Get a potential `DimHidden` value from [ArgMatches].
+    ///
+    /// If the "dim-hidden" argument is passed, this returns a `DimHidden` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("dim-hidden") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `DimHidden` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DimHidden;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, DimHidden::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--dim-hidden"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(DimHidden(true)), DimHidden::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, DimHidden::from_config(&Config::with_none()));
+    }
+}
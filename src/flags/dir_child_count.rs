@@ -0,0 +1,65 @@
+//! This module defines the [DirChildCount] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to append the number of their visible entries to the names of the
+/// directories in the grid.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct DirChildCount(pub bool);
+
+impl Configurable<Self> for DirChildCount {
+    /// Get a potential `DirChildCount` value from [ArgMatches].
+    ///
+    /// If the "dir-child-count" argument is passed, this returns a `DirChildCount` with value
+    /// `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("dir-child-count") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `DirChildCount` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DirChildCount;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, DirChildCount::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--dir-child-count"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DirChildCount(true)),
+            DirChildCount::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, DirChildCount::from_config(&Config::with_none()));
+    }
+}
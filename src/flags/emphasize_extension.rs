@@ -0,0 +1,64 @@
+//! This module defines the [EmphasizeExtension] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to set the extensions apart from the stems of the file names.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct EmphasizeExtension(pub bool);
+
+impl Configurable<Self> for EmphasizeExtension {
+    /// Get a potential `EmphasizeExtension` value from [ArgMatches].
+    ///
+    /// If the "emphasize-extension" argument is passed, this returns an `EmphasizeExtension` with
+    /// value `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("emphasize-extension") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `EmphasizeExtension` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::EmphasizeExtension;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, EmphasizeExtension::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--emphasize-extension"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(EmphasizeExtension(true)),
+            EmphasizeExtension::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, EmphasizeExtension::from_config(&Config::with_none()));
+    }
+}
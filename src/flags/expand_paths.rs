@@ -0,0 +1,174 @@
+//! This module defines the [ExpandPaths] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+use crate::print_error;
+
+use clap::ArgMatches;
+use std::env;
+use std::path::PathBuf;
+
+/// The flag showing whether to expand `~` and the environment variables in the path arguments.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct ExpandPaths(pub bool);
+
+impl Configurable<Self> for ExpandPaths {
+    /// Get a potential `ExpandPaths` value from [ArgMatches].
+    ///
+    /// If the "expand-paths" argument is passed, this returns an `ExpandPaths` with value `true`
+    /// in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("expand-paths") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `ExpandPaths` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+impl ExpandPaths {
+    /// Expand a leading `~` to the home directory, and the `$VAR` and `${VAR}` variables to their
+    /// value, when the flag is set. The variables which are not set are left as they are, with a
+    /// warning.
+    pub fn expand(&self, path: PathBuf) -> PathBuf {
+        if !self.0 {
+            return path;
+        }
+
+        let input = match path.to_str() {
+            Some(input) => input,
+            None => return path,
+        };
+
+        let mut output = String::new();
+        let mut rest = input;
+
+        if rest == "~" || rest.starts_with("~/") || rest.starts_with("~\\") {
+            match home_dir() {
+                Some(home) => {
+                    output += &home;
+                    rest = &rest[1..];
+                }
+                None => print_error!("{}: the home directory is unknown.", input),
+            }
+        }
+
+        while let Some(start) = rest.find('$') {
+            output += &rest[..start];
+            let after = &rest[start + 1..];
+
+            let (name, length) = match after.strip_prefix('{') {
+                Some(braced) => match braced.find('}') {
+                    Some(end) => (&braced[..end], end + 2),
+                    None => ("", 0),
+                },
+                None => {
+                    let end = after
+                        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                        .unwrap_or(after.len());
+                    (&after[..end], end)
+                }
+            };
+
+            if name.is_empty() {
+                output += "$";
+                rest = after;
+                continue;
+            }
+
+            match env::var(name) {
+                Ok(value) => output += &value,
+                Err(_) => {
+                    print_error!("{}: the variable {} is not set.", input, name);
+                    output += &rest[start..start + 1 + length];
+                }
+            }
+            rest = &after[length..];
+        }
+        output += rest;
+
+        PathBuf::from(output)
+    }
+}
+
+fn home_dir() -> Option<String> {
+    #[cfg(unix)]
+    let home = env::var("HOME");
+    #[cfg(windows)]
+    let home = env::var("USERPROFILE");
+
+    home.ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::ExpandPaths;
+    use std::env;
+    use std::path::PathBuf;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, ExpandPaths::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--expand-paths"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(ExpandPaths(true)),
+            ExpandPaths::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, ExpandPaths::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_expand_disabled() {
+        let path = PathBuf::from("~/$HOME");
+        assert_eq!(path, ExpandPaths(false).expand(path.clone()));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_expand_home_and_variables() {
+        let home = env::var("HOME").unwrap();
+        let expected = PathBuf::from(format!("{}/foo", home));
+
+        let expand = ExpandPaths(true);
+        assert_eq!(expected, expand.expand(PathBuf::from("~/foo")));
+        assert_eq!(expected, expand.expand(PathBuf::from("$HOME/foo")));
+        assert_eq!(expected, expand.expand(PathBuf::from("${HOME}/foo")));
+    }
+
+    #[test]
+    fn test_expand_unset_variable() {
+        let expand = ExpandPaths(true);
+        assert_eq!(
+            PathBuf::from("$LSD_UNSET_VARIABLE/foo"),
+            expand.expand(PathBuf::from("$LSD_UNSET_VARIABLE/foo"))
+        );
+        assert_eq!(
+            PathBuf::from("a~/${LSD_UNSET_VARIABLE}$"),
+            expand.expand(PathBuf::from("a~/${LSD_UNSET_VARIABLE}$"))
+        );
+    }
+}
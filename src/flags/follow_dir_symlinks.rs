@@ -0,0 +1,85 @@
+//! This module defines the [FollowDirSymlinks] flag. To set it up from [ArgMatches], a [Config]
+//! and its [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to dereference symbolic links pointing to directories.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct FollowDirSymlinks(pub bool);
+
+impl Configurable<Self> for FollowDirSymlinks {
+    /// Get a potential `FollowDirSymlinks` value from [ArgMatches].
+    ///
+    /// If the "follow-dir-symlinks" argument is passed, this returns a `FollowDirSymlinks` with
+    /// value `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("follow-dir-symlinks") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `FollowDirSymlinks` value from a [Config].
+    ///
+    /// If the `Config::follow-dir-symlinks` has value, this returns its value
+    /// as the value of the `FollowDirSymlinks`, in a [Some], Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.follow_dir_symlinks.map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FollowDirSymlinks;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, FollowDirSymlinks::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--follow-dir-symlinks"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(FollowDirSymlinks(true)),
+            FollowDirSymlinks::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, FollowDirSymlinks::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let mut c = Config::with_none();
+        c.follow_dir_symlinks = Some(true);
+        assert_eq!(
+            Some(FollowDirSymlinks(true)),
+            FollowDirSymlinks::from_config(&c)
+        );
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let mut c = Config::with_none();
+        c.follow_dir_symlinks = Some(false);
+        assert_eq!(
+            Some(FollowDirSymlinks(false)),
+            FollowDirSymlinks::from_config(&c)
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! This module defines the [FontCheck] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to hint at the missing Nerd Font when printing the fancy icons.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct FontCheck(pub bool);
+
+impl Configurable<Self> for FontCheck {
+    /// Get a potential `FontCheck` value from [ArgMatches].
+    ///
+    /// If the "font-check" argument is passed, this returns a `FontCheck` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("font-check") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `FontCheck` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FontCheck;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, FontCheck::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--font-check"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(FontCheck(true)), FontCheck::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, FontCheck::from_config(&Config::with_none()));
+    }
+}
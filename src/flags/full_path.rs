@@ -0,0 +1,62 @@
+//! This module defines the [FullPath] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether the raw output prints the paths of the entries, rather than their
+/// names relative to the listed directories.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct FullPath(pub bool);
+
+impl Configurable<Self> for FullPath {
+    /// Get a potential `FullPath` value from [ArgMatches].
+    ///
+    /// If the "full-path" argument is passed, this returns a `FullPath` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("full-path") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `FullPath` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FullPath;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, FullPath::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--full-path"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(FullPath(true)), FullPath::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, FullPath::from_config(&Config::with_none()));
+    }
+}
@@ -0,0 +1,147 @@
+//! This module defines the [GitSymbols] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The symbols of the statuses shown by the git block. The statuses of the whole file replace its
+/// two letters, the other ones replace the letter of the index or of the work tree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GitSymbols {
+    /// The symbol of the unchanged files.
+    pub unmodified: String,
+    /// The symbol of the untracked files.
+    pub untracked: String,
+    /// The symbol of the ignored files.
+    pub ignored: String,
+    /// The letter of the added files.
+    pub added: String,
+    /// The letter of the modified files.
+    pub modified: String,
+    /// The letter of the deleted files.
+    pub deleted: String,
+    /// The letter of the renamed files.
+    pub renamed: String,
+    /// The letter of the copied files.
+    pub copied: String,
+    /// The letter of the files whose type changed.
+    pub type_changed: String,
+    /// The letter of the files with merge conflicts.
+    pub conflicted: String,
+}
+
+impl GitSymbols {
+    /// Get the symbol replacing the status `letter` of `git status --porcelain`, [None] for the
+    /// unchanged side of a file.
+    pub fn letter(&self, letter: u8) -> Option<&str> {
+        match letter {
+            b'A' => Some(&self.added),
+            b'M' => Some(&self.modified),
+            b'D' => Some(&self.deleted),
+            b'R' => Some(&self.renamed),
+            b'C' => Some(&self.copied),
+            b'T' => Some(&self.type_changed),
+            b'U' => Some(&self.conflicted),
+            _ => None,
+        }
+    }
+}
+
+impl Configurable<Self> for GitSymbols {
+    /// `GitSymbols` can not be configured by [ArgMatches]
+    ///
+    /// Return `None`
+    fn from_arg_matches(_: &ArgMatches) -> Option<Self> {
+        None
+    }
+
+    /// Get a potential `GitSymbols` value from a [Config].
+    ///
+    /// If the `Config::git_symbols` has value, returns a `GitSymbols` in a [Some], its unset
+    /// symbols keeping their default. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        let symbols = config.git_symbols.as_ref()?;
+        let default = Self::default();
+
+        Some(Self {
+            unmodified: symbols.unmodified.clone().unwrap_or(default.unmodified),
+            untracked: symbols.untracked.clone().unwrap_or(default.untracked),
+            ignored: symbols.ignored.clone().unwrap_or(default.ignored),
+            added: symbols.added.clone().unwrap_or(default.added),
+            modified: symbols.modified.clone().unwrap_or(default.modified),
+            deleted: symbols.deleted.clone().unwrap_or(default.deleted),
+            renamed: symbols.renamed.clone().unwrap_or(default.renamed),
+            copied: symbols.copied.clone().unwrap_or(default.copied),
+            type_changed: symbols.type_changed.clone().unwrap_or(default.type_changed),
+            conflicted: symbols.conflicted.clone().unwrap_or(default.conflicted),
+        })
+    }
+}
+
+/// The default symbols are the ones of `git status --short`, with `--` for the unchanged files.
+impl Default for GitSymbols {
+    fn default() -> Self {
+        Self {
+            unmodified: String::from("--"),
+            untracked: String::from("??"),
+            ignored: String::from("!!"),
+            added: String::from("A"),
+            modified: String::from("M"),
+            deleted: String::from("D"),
+            renamed: String::from("R"),
+            copied: String::from("C"),
+            type_changed: String::from("T"),
+            conflicted: String::from("U"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::GitSymbols;
+
+    use crate::app;
+    use crate::config_file::{self, Config};
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, GitSymbols::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, GitSymbols::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_partial() {
+        let mut c = Config::with_none();
+        c.git_symbols = Some(config_file::GitSymbols {
+            modified: Some("~".into()),
+            untracked: Some("?".into()),
+            ..config_file::GitSymbols::default()
+        });
+        assert_eq!(
+            Some(GitSymbols {
+                modified: String::from("~"),
+                untracked: String::from("?"),
+                ..GitSymbols::default()
+            }),
+            GitSymbols::from_config(&c)
+        );
+    }
+
+    #[test]
+    fn test_letter() {
+        let symbols = GitSymbols::default();
+        assert_eq!(Some("M"), symbols.letter(b'M'));
+        assert_eq!(Some("T"), symbols.letter(b'T'));
+        assert_eq!(None, symbols.letter(b' '));
+    }
+}
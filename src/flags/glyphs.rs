@@ -0,0 +1,84 @@
+//! This module defines the [Glyphs] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The glyphs marking the states of the entries that can not be fully displayed. They are empty
+/// by default, leaving the states unmarked.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Glyphs {
+    /// The glyph of the symlinks whose target can not be accessed, and of the directories whose
+    /// content can not be read.
+    pub no_access: String,
+    /// The glyph of the symlinks whose target is missing.
+    pub broken: String,
+    /// The glyph of the symlinks whose target is part of a loop, and of the directories leading
+    /// back to a listed one.
+    pub cycle: String,
+}
+
+impl Configurable<Self> for Glyphs {
+    /// `Glyphs` can not be configured by [ArgMatches]
+    ///
+    /// Return `None`
+    fn from_arg_matches(_: &ArgMatches) -> Option<Self> {
+        None
+    }
+
+    /// Get a potential `Glyphs` value from a [Config].
+    ///
+    /// If the `Config::glyphs` has value, returns a `Glyphs` in a [Some], its unset glyphs keeping
+    /// their default. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        let glyphs = config.glyphs.as_ref()?;
+        let default = Self::default();
+
+        Some(Self {
+            no_access: glyphs.no_access.clone().unwrap_or(default.no_access),
+            broken: glyphs.broken.clone().unwrap_or(default.broken),
+            cycle: glyphs.cycle.clone().unwrap_or(default.cycle),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Glyphs;
+
+    use crate::app;
+    use crate::config_file::{self, Config};
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Glyphs::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Glyphs::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_partial() {
+        let mut c = Config::with_none();
+        c.glyphs = Some(config_file::Glyphs {
+            no_access: None,
+            broken: Some("x".into()),
+            cycle: None,
+        });
+        assert_eq!(
+            Some(Glyphs {
+                broken: String::from("x"),
+                ..Glyphs::default()
+            }),
+            Glyphs::from_config(&c)
+        );
+    }
+}
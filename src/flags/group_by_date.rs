@@ -0,0 +1,65 @@
+//! This module defines the [GroupByDate] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to split the entries of the directories into sections by their date,
+/// from today to the older ones.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct GroupByDate(pub bool);
+
+impl Configurable<Self> for GroupByDate {
+    /// Get a potential `GroupByDate` value from [ArgMatches].
+    ///
+    /// If the "group-by-date" argument is passed, this returns a `GroupByDate` with value `true`
+    /// in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("group-by-date") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `GroupByDate` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::GroupByDate;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, GroupByDate::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--group-by-date"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(GroupByDate(true)),
+            GroupByDate::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, GroupByDate::from_config(&Config::with_none()));
+    }
+}
@@ -0,0 +1,64 @@
+//! This module defines the [HighlightSparse] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to highlight the size of the sparse files.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct HighlightSparse(pub bool);
+
+impl Configurable<Self> for HighlightSparse {
+    /// Get a potential `HighlightSparse` value from [ArgMatches].
+    ///
+    /// If the "highlight-sparse" argument is passed, this returns a `HighlightSparse` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("highlight-sparse") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `HighlightSparse` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::HighlightSparse;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, HighlightSparse::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--highlight-sparse"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(HighlightSparse(true)),
+            HighlightSparse::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, HighlightSparse::from_config(&Config::with_none()));
+    }
+}
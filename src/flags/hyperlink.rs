@@ -0,0 +1,148 @@
+//! This module defines the [HyperlinkOption]. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use serde::Deserialize;
+
+/// The flag showing when to wrap the file names in hyperlinks to the files.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HyperlinkOption {
+    Always,
+    Auto,
+    Never,
+}
+
+impl HyperlinkOption {
+    /// Get a Hyperlink value from a [String].
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "always" => Some(Self::Always),
+            "auto" => Some(Self::Auto),
+            "never" => Some(Self::Never),
+            _ => {
+                panic!(
+                    "Hyperlink can only be one of auto, always and never, but got {}.",
+                    value
+                );
+            }
+        }
+    }
+}
+
+impl Configurable<Self> for HyperlinkOption {
+    /// Get a potential `HyperlinkOption` variant from [ArgMatches].
+    ///
+    /// If the "classic" argument is passed, then this returns the [HyperlinkOption::Never]
+    /// variant in a [Some]. Otherwise if the "hyperlink" argument is passed, this returns the
+    /// variant corresponding to its parameter in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("classic") {
+            Some(Self::Never)
+        } else if matches.occurrences_of("hyperlink") > 0 {
+            if let Some(hyperlink) = matches.values_of("hyperlink")?.next_back() {
+                Self::from_str(hyperlink)
+            } else {
+                panic!("Bad hyperlink args. This should not be reachable!");
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `HyperlinkOption` variant from a [Config].
+    ///
+    /// If the `Config::classic` is `true` then this returns the Some(HyperlinkOption::Never),
+    /// Otherwise if the `Config::hyperlink` has value, this returns it in a [Some]. Otherwise
+    /// this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(true) = config.classic {
+            return Some(Self::Never);
+        }
+
+        config.hyperlink
+    }
+}
+
+/// The default value for `HyperlinkOption` is [HyperlinkOption::Never].
+impl Default for HyperlinkOption {
+    fn default() -> Self {
+        Self::Never
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::HyperlinkOption;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, HyperlinkOption::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_always() {
+        let argv = vec!["lsd", "--hyperlink", "always"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(HyperlinkOption::Always),
+            HyperlinkOption::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_hyperlink_multi() {
+        let argv = vec!["lsd", "--hyperlink", "always", "--hyperlink", "auto"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(HyperlinkOption::Auto),
+            HyperlinkOption::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_classic_mode() {
+        let argv = vec!["lsd", "--hyperlink", "always", "--classic"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(HyperlinkOption::Never),
+            HyperlinkOption::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, HyperlinkOption::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_always() {
+        let mut c = Config::with_none();
+        c.hyperlink = Some(HyperlinkOption::Always);
+        assert_eq!(
+            Some(HyperlinkOption::Always),
+            HyperlinkOption::from_config(&c)
+        );
+    }
+
+    #[test]
+    fn test_from_config_classic_mode() {
+        let mut c = Config::with_none();
+        c.classic = Some(true);
+        c.hyperlink = Some(HyperlinkOption::Always);
+        assert_eq!(
+            Some(HyperlinkOption::Never),
+            HyperlinkOption::from_config(&c)
+        );
+    }
+}
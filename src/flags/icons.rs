@@ -1,12 +1,17 @@
 //! This module defines the [IconOption]. To set it up from [ArgMatches], a [Config] and its
 //! [Default] value, use its [configure_from](Configurable::configure_from) method.
 
+use super::theme;
 use super::Configurable;
 
+use crate::app;
 use crate::config_file::Config;
+use crate::meta::FileType;
+use crate::print_error;
 
 use clap::ArgMatches;
 use serde::Deserialize;
+use std::collections::HashMap;
 
 /// A collection of flags on how to use icons.
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
@@ -17,6 +22,12 @@ pub struct Icons {
     pub theme: IconTheme,
     /// String between icon and name.
     pub separator: IconSeparator,
+    /// The number of spaces between icon and name, in place of the separator.
+    pub spacing: IconSpacing,
+    /// The file types displayed without an icon.
+    pub disabled: IconDisabled,
+    /// The icons of the file names and extensions, on top of the default ones.
+    pub overrides: IconOverrides,
 }
 
 impl Icons {
@@ -28,10 +39,25 @@ impl Icons {
         let when = IconOption::configure_from(matches, config);
         let theme = IconTheme::configure_from(matches, config);
         let separator = IconSeparator::configure_from(matches, config);
+        let spacing = IconSpacing::configure_from(matches, config);
+        let disabled = IconDisabled::configure_from(matches, config);
+        let overrides = IconOverrides::configure_from(matches, config);
         Self {
             when,
             theme,
             separator,
+            spacing,
+            disabled,
+            overrides,
+        }
+    }
+
+    /// Get the string between the icons and the names, the spaces of the spacing if it is set,
+    /// or else the separator.
+    pub fn gap(&self) -> String {
+        match self.spacing.0 {
+            Some(spaces) => " ".repeat(spaces),
+            None => self.separator.0.clone(),
         }
     }
 }
@@ -104,7 +130,8 @@ impl Configurable<Self> for IconTheme {
     /// Get a potential `IconTheme` variant from [ArgMatches].
     ///
     /// If the argument is passed, this returns the variant corresponding to its parameter in a
-    /// [Some]. Otherwise this returns [None].
+    /// [Some]. Otherwise if the "theme" argument is passed, this returns the icon theme it
+    /// bundles in a [Some]. Otherwise this returns [None].
     fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
         if matches.occurrences_of("icon-theme") > 0 {
             match matches.values_of("icon-theme")?.last() {
@@ -113,14 +140,15 @@ impl Configurable<Self> for IconTheme {
                 _ => panic!("This should not be reachable!"),
             }
         } else {
-            None
+            theme::from_arg_matches(matches).map(|(_, icon)| icon)
         }
     }
 
     /// Get a potential `IconTheme` variant from a [Config].
     ///
     /// If the `Config::icons::theme` has value and is one of "fancy" or "unicode",
-    /// this returns its corresponding variant in a [Some].
+    /// this returns its corresponding variant in a [Some]. Otherwise if the `Config::theme`
+    /// names a bundled theme, this returns its icon theme in a [Some].
     /// Otherwise this returns [None].
     fn from_config(config: &Config) -> Option<Self> {
         if let Some(icon) = &config.icons {
@@ -128,7 +156,9 @@ impl Configurable<Self> for IconTheme {
                 return Some(theme);
             }
         }
-        None
+        // an unknown name is already reported by the color theme
+        let themes = config.theme.as_deref().and_then(theme::lookup);
+        themes.map(|(_, icon)| icon)
     }
 }
 
@@ -146,20 +176,26 @@ pub struct IconSeparator(pub String);
 impl Configurable<Self> for IconSeparator {
     /// Get a potential `IconSeparator` variant from [ArgMatches].
     ///
-    /// If the argument is passed, this returns the variant corresponding to its parameter in a
-    /// [Some]. Otherwise this returns [None].
-    fn from_arg_matches(_matches: &ArgMatches) -> Option<Self> {
-        None
+    /// If the "icon-separator" argument is passed, this returns an `IconSeparator` with its last
+    /// parameter in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        matches
+            .values_of("icon-separator")?
+            .next_back()
+            .map(|separator| IconSeparator(separator.to_string()))
     }
 
     /// Get a potential `IconSeparator` variant from a [Config].
     ///
-    /// This returns its corresponding variant in a [Some].
-    /// Otherwise this returns [None].
+    /// If the `Config::icons::separator` has value and is one or two columns wide, this returns
+    /// its corresponding variant in a [Some]. Otherwise this returns [None].
     fn from_config(config: &Config) -> Option<Self> {
         if let Some(icon) = &config.icons {
             if let Some(separator) = icon.separator.clone() {
-                return Some(IconSeparator(separator));
+                match app::validate_icon_separator(&separator) {
+                    Ok(()) => return Some(IconSeparator(separator)),
+                    Err(err) => print_error!("Not a valid icon separator: {}.", err),
+                }
             }
         }
         None
@@ -173,6 +209,162 @@ impl Default for IconSeparator {
     }
 }
 
+/// The flag holding the number of spaces between the icons and the names, in place of the
+/// [IconSeparator].
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct IconSpacing(pub Option<usize>);
+
+impl Configurable<Self> for IconSpacing {
+    /// Get a potential `IconSpacing` value from [ArgMatches].
+    ///
+    /// If the "icon-spacing" argument is passed, this returns an `IconSpacing` with its last
+    /// parameter in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        matches
+            .values_of("icon-spacing")?
+            .next_back()
+            .and_then(|spaces| spaces.parse().ok())
+            .map(|spaces| Self(Some(spaces)))
+    }
+
+    /// Get a potential `IconSpacing` value from a [Config].
+    ///
+    /// If the `Config::icons::spacing` has value, this returns it as an `IconSpacing` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        let spaces = config.icons.as_ref()?.spacing?;
+        Some(Self(Some(spaces)))
+    }
+}
+
+/// A file type whose icon can be disabled.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IconFileType {
+    File,
+    Dir,
+    Symlink,
+    Socket,
+    Pipe,
+    CharDevice,
+    BlockDevice,
+    Special,
+}
+
+impl From<FileType> for IconFileType {
+    fn from(file_type: FileType) -> Self {
+        match file_type {
+            FileType::File { .. } => Self::File,
+            FileType::Directory { .. } => Self::Dir,
+            FileType::SymLink { .. } => Self::Symlink,
+            FileType::Socket => Self::Socket,
+            FileType::Pipe => Self::Pipe,
+            FileType::CharDevice => Self::CharDevice,
+            FileType::BlockDevice => Self::BlockDevice,
+            FileType::Special => Self::Special,
+        }
+    }
+}
+
+/// The flag holding the file types displayed without an icon.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct IconDisabled(pub Vec<IconFileType>);
+
+impl IconDisabled {
+    /// Check if the icon of the `file_type` entries is disabled.
+    pub fn contains(&self, file_type: FileType) -> bool {
+        self.0.contains(&IconFileType::from(file_type))
+    }
+}
+
+impl Configurable<Self> for IconDisabled {
+    /// `IconDisabled` can not be configured by [ArgMatches]
+    ///
+    /// Return `None`
+    fn from_arg_matches(_: &ArgMatches) -> Option<Self> {
+        None
+    }
+
+    /// Get a potential `IconDisabled` value from a [Config].
+    ///
+    /// If the `Config::icons::disabled` has value, this returns its file types in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config
+            .icons
+            .as_ref()
+            .and_then(|icons| icons.disabled.clone())
+            .map(Self)
+    }
+}
+
+/// The flag holding the icons of the file names and extensions given by the user.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct IconOverrides {
+    pub name: HashMap<String, String>,
+    pub extension: HashMap<String, String>,
+}
+
+impl IconOverrides {
+    /// Parse the `icon` given as a single glyph or as its codepoint, like `\uf0e7` or `\u{f0e7}`.
+    fn parse_icon(icon: &str) -> Option<String> {
+        let mut chars = icon.chars();
+        if let (Some(glyph), None) = (chars.next(), chars.next()) {
+            return Some(glyph.to_string());
+        }
+
+        let codepoint = icon.strip_prefix("\\u")?;
+        let codepoint = codepoint
+            .strip_prefix('{')
+            .and_then(|codepoint| codepoint.strip_suffix('}'))
+            .unwrap_or(codepoint);
+        u32::from_str_radix(codepoint, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .map(|glyph| glyph.to_string())
+    }
+
+    /// Get the valid icons of `icons`, by their lower-case key, warning about the others.
+    fn parse_icons(icons: &HashMap<String, String>) -> HashMap<String, String> {
+        icons
+            .iter()
+            .filter_map(|(key, icon)| match Self::parse_icon(icon) {
+                Some(icon) => Some((key.to_lowercase(), icon)),
+                None => {
+                    print_error!("Not a valid icon for {}: {}.", key, icon);
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+impl Configurable<Self> for IconOverrides {
+    /// `IconOverrides` can not be configured by [ArgMatches]
+    ///
+    /// Return `None`
+    fn from_arg_matches(_: &ArgMatches) -> Option<Self> {
+        None
+    }
+
+    /// Get a potential `IconOverrides` value from a [Config].
+    ///
+    /// If the `Config::icons` has value, this returns the valid icons of its `name` and
+    /// `extension` maps in a [Some], the invalid ones keeping their default icon. Otherwise this
+    /// returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        let icons = config.icons.as_ref()?;
+        let parse = |icons: &Option<HashMap<String, String>>| {
+            icons.as_ref().map(Self::parse_icons).unwrap_or_default()
+        };
+
+        Some(Self {
+            name: parse(&icons.name),
+            extension: parse(&icons.extension),
+        })
+    }
+}
+
 #[cfg(test)]
 mod test_icon_option {
     use super::IconOption;
@@ -250,6 +442,10 @@ mod test_icon_option {
             when: Some(IconOption::Always),
             theme: None,
             separator: None,
+            spacing: None,
+            disabled: None,
+            name: None,
+            extension: None,
         });
         assert_eq!(Some(IconOption::Always), IconOption::from_config(&c));
     }
@@ -261,6 +457,10 @@ mod test_icon_option {
             when: Some(IconOption::Auto),
             theme: None,
             separator: None,
+            spacing: None,
+            disabled: None,
+            name: None,
+            extension: None,
         });
         assert_eq!(Some(IconOption::Auto), IconOption::from_config(&c));
     }
@@ -272,6 +472,10 @@ mod test_icon_option {
             when: Some(IconOption::Never),
             theme: None,
             separator: None,
+            spacing: None,
+            disabled: None,
+            name: None,
+            extension: None,
         });
         assert_eq!(Some(IconOption::Never), IconOption::from_config(&c));
     }
@@ -284,6 +488,10 @@ mod test_icon_option {
             when: Some(IconOption::Always),
             theme: None,
             separator: None,
+            spacing: None,
+            disabled: None,
+            name: None,
+            extension: None,
         });
         assert_eq!(Some(IconOption::Never), IconOption::from_config(&c));
     }
@@ -346,6 +554,10 @@ mod test_icon_theme {
             when: None,
             theme: Some(IconTheme::Fancy),
             separator: None,
+            spacing: None,
+            disabled: None,
+            name: None,
+            extension: None,
         });
         assert_eq!(Some(IconTheme::Fancy), IconTheme::from_config(&c));
     }
@@ -357,6 +569,10 @@ mod test_icon_theme {
             when: None,
             theme: Some(IconTheme::Unicode),
             separator: None,
+            spacing: None,
+            disabled: None,
+            name: None,
+            extension: None,
         });
         assert_eq!(Some(IconTheme::Unicode), IconTheme::from_config(&c));
     }
@@ -366,9 +582,39 @@ mod test_icon_theme {
 mod test_icon_separator {
     use super::IconSeparator;
 
+    use crate::app;
     use crate::config_file::{Config, Icons};
     use crate::flags::Configurable;
 
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, IconSeparator::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_two_spaces() {
+        let argv = vec!["lsd", "--icon-separator", "  "];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(IconSeparator("  ".to_string())),
+            IconSeparator::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_too_wide() {
+        let argv = vec!["lsd", "--icon-separator", " | "];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_arg_matches_empty() {
+        let argv = vec!["lsd", "--icon-separator", ""];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
     #[test]
     fn test_from_config_default() {
         let mut c = Config::with_none();
@@ -376,6 +622,10 @@ mod test_icon_separator {
             when: None,
             theme: None,
             separator: Some(" ".to_string()),
+            spacing: None,
+            disabled: None,
+            name: None,
+            extension: None,
         });
         let expected = Some(IconSeparator(" ".to_string()));
         assert_eq!(expected, IconSeparator::from_config(&c));
@@ -388,8 +638,194 @@ mod test_icon_separator {
             when: None,
             theme: None,
             separator: Some(" |".to_string()),
+            spacing: None,
+            disabled: None,
+            name: None,
+            extension: None,
         });
         let expected = Some(IconSeparator(" |".to_string()));
         assert_eq!(expected, IconSeparator::from_config(&c));
     }
+
+    #[test]
+    fn test_from_config_too_wide() {
+        let mut c = Config::with_none();
+        c.icons = Some(Icons {
+            when: None,
+            theme: None,
+            separator: Some(" | ".to_string()),
+            spacing: None,
+            disabled: None,
+            name: None,
+            extension: None,
+        });
+        assert_eq!(None, IconSeparator::from_config(&c));
+    }
+}
+
+#[cfg(test)]
+mod test_icon_spacing {
+    use super::{IconSeparator, IconSpacing, Icons};
+
+    use crate::app;
+    use crate::config_file::{self, Config};
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, IconSpacing::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_spaces() {
+        let argv = vec!["lsd", "--icon-spacing", "3"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(IconSpacing(Some(3))),
+            IconSpacing::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--icon-spacing", "-1"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config() {
+        let mut c = Config::with_none();
+        c.icons = Some(config_file::Icons {
+            when: None,
+            theme: None,
+            separator: None,
+            spacing: Some(2),
+            disabled: None,
+            name: None,
+            extension: None,
+        });
+        assert_eq!(Some(IconSpacing(Some(2))), IconSpacing::from_config(&c));
+    }
+
+    #[test]
+    fn test_gap() {
+        let icons = Icons {
+            separator: IconSeparator(" |".to_string()),
+            ..Icons::default()
+        };
+        assert_eq!(" |", icons.gap());
+
+        let icons = Icons {
+            spacing: IconSpacing(Some(3)),
+            ..icons
+        };
+        assert_eq!("   ", icons.gap());
+    }
+}
+
+#[cfg(test)]
+mod test_icon_disabled {
+    use super::{IconDisabled, IconFileType};
+
+    use crate::app;
+    use crate::config_file::{Config, Icons};
+    use crate::flags::Configurable;
+    use crate::meta::FileType;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, IconDisabled::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, IconDisabled::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_disabled() {
+        let mut c = Config::with_none();
+        c.icons = Some(Icons {
+            when: None,
+            theme: None,
+            separator: None,
+            spacing: None,
+            disabled: Some(vec![IconFileType::Socket, IconFileType::Pipe]),
+            name: None,
+            extension: None,
+        });
+
+        let disabled = IconDisabled::from_config(&c).unwrap();
+        assert!(disabled.contains(FileType::Socket));
+        assert!(disabled.contains(FileType::Pipe));
+        assert!(!disabled.contains(FileType::File {
+            uid: false,
+            exec: false
+        }));
+    }
+}
+
+#[cfg(test)]
+mod test_icon_overrides {
+    use super::IconOverrides;
+
+    use crate::config_file::{Config, Icons};
+    use crate::flags::Configurable;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_parse_icon() {
+        assert_eq!(
+            Some("\u{f0e7}".to_string()),
+            IconOverrides::parse_icon("\u{f0e7}")
+        );
+        assert_eq!(
+            Some("\u{f0e7}".to_string()),
+            IconOverrides::parse_icon("\\uf0e7")
+        );
+        assert_eq!(
+            Some("\u{f0e7}".to_string()),
+            IconOverrides::parse_icon("\\u{f0e7}")
+        );
+        assert_eq!(None, IconOverrides::parse_icon("two"));
+        assert_eq!(None, IconOverrides::parse_icon("\\uzzzz"));
+        assert_eq!(None, IconOverrides::parse_icon(""));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, IconOverrides::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_skip_invalid() {
+        let mut extension = HashMap::new();
+        extension.insert("ZIG".to_string(), "\\uf0e7".to_string());
+        extension.insert("bad".to_string(), "not an icon".to_string());
+
+        let mut c = Config::with_none();
+        c.icons = Some(Icons {
+            when: None,
+            theme: None,
+            separator: None,
+            spacing: None,
+            disabled: None,
+            name: None,
+            extension: Some(extension),
+        });
+
+        let mut expected = HashMap::new();
+        expected.insert("zig".to_string(), "\u{f0e7}".to_string());
+        assert_eq!(
+            Some(IconOverrides {
+                name: HashMap::new(),
+                extension: expected,
+            }),
+            IconOverrides::from_config(&c)
+        );
+    }
 }
@@ -4,13 +4,24 @@
 use crate::config_file::Config;
 
 use clap::{ArgMatches, Error, ErrorKind};
-use globset::{Glob, GlobSet, GlobSetBuilder};
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
+use std::path::Path;
 
 /// The struct holding a [GlobSet] and methods to build it.
 #[derive(Clone, Debug)]
 pub struct IgnoreGlobs(pub GlobSet);
 
 impl IgnoreGlobs {
+    /// Check if the file at `path` is ignored, its name or its path relative to the listed `root`
+    /// matching a glob. The wildcards do not match the path separators, so that only the globs
+    /// with a separator can match a path.
+    pub fn is_match(&self, path: &Path, root: &Path) -> bool {
+        if path.file_name().is_some_and(|name| self.0.is_match(name)) {
+            return true;
+        }
+        self.0.is_match(path.strip_prefix(root).unwrap_or(path))
+    }
+
     /// Returns a value from either [ArgMatches], a [Config] or a [Default] value. The first value
     /// that is not [None] is used. The order of precedence for the value used is:
     /// - [from_arg_matches](IgnoreGlobs::from_arg_matches)
@@ -19,7 +30,7 @@ impl IgnoreGlobs {
     ///
     /// # Errors
     ///
-    /// If either of the [Glob::new] or [GlobSetBuilder.build] methods return an [Err].
+    /// If either of the [GlobBuilder::build] or [GlobSetBuilder.build] methods return an [Err].
     pub fn configure_from(matches: &ArgMatches, config: &Config) -> Result<Self, Error> {
         let mut result: Result<Self, Error> = Ok(Default::default());
 
@@ -96,7 +107,7 @@ impl IgnoreGlobs {
     ///
     /// This method is mainly a helper to wrap the handling of potential errors.
     fn create_glob(pattern: &str) -> Result<Glob, Error> {
-        match Glob::new(pattern) {
+        match GlobBuilder::new(pattern).literal_separator(true).build() {
             Ok(glob) => Ok(glob),
             Err(err) => Err(Error::with_description(
                 &err.to_string(),
@@ -132,6 +143,7 @@ mod test {
 
     use crate::app;
     use crate::config_file::Config;
+    use std::path::Path;
 
     // The following tests are implemented using match expressions instead of the assert_eq macro,
     // because clap::Error does not implement PartialEq.
@@ -192,4 +204,36 @@ mod test {
             _ => false,
         });
     }
+
+    #[test]
+    fn test_is_match_name_and_path() {
+        let argv = vec![
+            "lsd",
+            "--ignore-glob",
+            "*.pyc",
+            "--ignore-glob",
+            "build/*.o",
+        ];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let globs = IgnoreGlobs::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let root = Path::new(".");
+        assert!(globs.is_match(Path::new("src/pkg/module.pyc"), root));
+        assert!(globs.is_match(Path::new("build/main.o"), root));
+        assert!(globs.is_match(Path::new("./build/main.o"), root));
+        assert!(!globs.is_match(Path::new("src/build/main.o"), root));
+        assert!(!globs.is_match(Path::new("main.o"), root));
+    }
+
+    #[test]
+    fn test_is_match_path_relative_to_root() {
+        let argv = vec!["lsd", "--ignore-glob", "build/*.o"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let globs = IgnoreGlobs::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let root = Path::new("proj");
+        assert!(globs.is_match(Path::new("proj/build/main.o"), root));
+        assert!(!globs.is_match(Path::new("proj/src/build/main.o"), root));
+        assert!(!globs.is_match(Path::new("proj/build/main.o"), Path::new(".")));
+    }
 }
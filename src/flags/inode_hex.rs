@@ -0,0 +1,61 @@
+//! This module defines the [InodeHex] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to display the inode numbers in hexadecimal.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct InodeHex(pub bool);
+
+impl Configurable<Self> for InodeHex {
+    /// Get a potential `InodeHex` value from [ArgMatches].
+    ///
+    /// If the "inode-hex" argument is passed, this returns an `InodeHex` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("inode-hex") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `InodeHex` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::InodeHex;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, InodeHex::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--inode-hex"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(InodeHex(true)), InodeHex::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, InodeHex::from_config(&Config::with_none()));
+    }
+}
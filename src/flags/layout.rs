@@ -3,7 +3,7 @@
 
 use crate::config_file::Config;
 
-use super::Configurable;
+use super::{Blocks, Configurable};
 
 use clap::ArgMatches;
 use serde::Deserialize;
@@ -22,8 +22,8 @@ impl Configurable<Layout> for Layout {
     /// Get a potential `Layout` variant from [ArgMatches].
     ///
     /// If any of the "tree", "long" or "oneline" arguments is passed, this returns the
-    /// corresponding `Layout` variant in a [Some]. Otherwise if the number of passed "blocks"
-    /// arguments is greater than 1, this also returns the [OneLine](Layout::OneLine) variant.
+    /// corresponding `Layout` variant in a [Some]. Otherwise if the number of passed block names
+    /// is greater than 1, this also returns the [OneLine](Layout::OneLine) variant.
     /// Finally if neither of them is passed, this returns [None].
     fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
         if matches.is_present("tree") {
@@ -31,7 +31,7 @@ impl Configurable<Layout> for Layout {
         } else if matches.is_present("long")
             || matches.is_present("oneline")
             || matches.is_present("inode")
-            || matches!(matches.values_of("blocks"), Some(values) if values.len() > 1)
+            || Blocks::arg_names(matches).len() > 1
         // TODO: handle this differently
         {
             Some(Self::OneLine)
@@ -0,0 +1,61 @@
+//! This module defines the [Legend] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to print the meaning of the colors and icons instead of a listing.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Legend(pub bool);
+
+impl Configurable<Self> for Legend {
+    /// Get a potential `Legend` value from [ArgMatches].
+    ///
+    /// If the "legend" argument is passed, this returns a `Legend` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("legend") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `Legend` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Legend;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Legend::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--legend"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Legend(true)), Legend::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Legend::from_config(&Config::with_none()));
+    }
+}
@@ -0,0 +1,149 @@
+//! This module defines the [LineEnding] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use serde::Deserialize;
+
+/// The flag showing which line ending to terminate the output lines with.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LineEnding {
+    /// The variant to end the lines with a line feed.
+    Lf,
+    /// The variant to end the lines with a carriage return and a line feed.
+    Crlf,
+    /// The variant to end the entries with a NUL byte, for `xargs -0`.
+    #[serde(skip_deserializing)]
+    Nul,
+}
+
+impl LineEnding {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "lf" => Some(Self::Lf),
+            "crlf" => Some(Self::Crlf),
+            _ => {
+                panic!(
+                    "Line ending can only be one of lf or crlf, but got {}.",
+                    value
+                );
+            }
+        }
+    }
+
+    /// The characters terminating a line.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::Crlf => "\r\n",
+            Self::Nul => "\0",
+        }
+    }
+}
+
+impl Configurable<Self> for LineEnding {
+    /// Get a potential `LineEnding` variant from [ArgMatches].
+    ///
+    /// If the "null" argument is passed, this returns [LineEnding::Nul] in a [Some]. Otherwise
+    /// if the "line-ending" argument is passed with "lf" or "crlf", the corresponding
+    /// `LineEnding` variant is returned in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("null") {
+            return Some(Self::Nul);
+        }
+        if matches.occurrences_of("line-ending") > 0 {
+            if let Some(line_ending) = matches.values_of("line-ending")?.next_back() {
+                return Self::from_str(line_ending);
+            }
+        }
+        None
+    }
+
+    /// Get a potential `LineEnding` variant from a [Config].
+    ///
+    /// If the `Config::line_ending` has value, this returns it in a [Some]. Otherwise this
+    /// returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.line_ending
+    }
+}
+
+/// The default value for `LineEnding` is [LineEnding::Lf].
+impl Default for LineEnding {
+    fn default() -> Self {
+        Self::Lf
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LineEnding;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, LineEnding::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_lf() {
+        let argv = vec!["lsd", "--line-ending", "lf"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(LineEnding::Lf), LineEnding::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_crlf() {
+        let argv = vec!["lsd", "--line-ending", "crlf"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(LineEnding::Crlf),
+            LineEnding::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_null() {
+        let argv = vec!["lsd", "--line-ending", "crlf", "-0"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(LineEnding::Nul),
+            LineEnding::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_line_ending_multi() {
+        let argv = vec!["lsd", "--line-ending", "crlf", "--line-ending", "lf"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(LineEnding::Lf), LineEnding::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, LineEnding::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_lf() {
+        let mut c = Config::with_none();
+        c.line_ending = Some(LineEnding::Lf);
+        assert_eq!(Some(LineEnding::Lf), LineEnding::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_crlf() {
+        let mut c = Config::with_none();
+        c.line_ending = Some(LineEnding::Crlf);
+        assert_eq!(Some(LineEnding::Crlf), LineEnding::from_config(&c));
+    }
+}
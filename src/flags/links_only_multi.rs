@@ -0,0 +1,64 @@
+//! This module defines the [LinksOnlyMulti] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to dim the link counts of the files with a single hard link.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct LinksOnlyMulti(pub bool);
+
+impl Configurable<Self> for LinksOnlyMulti {
+    /// Get a potential `LinksOnlyMulti` value from [ArgMatches].
+    ///
+    /// If the "links-only-multi" argument is passed, this returns a `LinksOnlyMulti` with value
+    /// `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("links-only-multi") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `LinksOnlyMulti` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LinksOnlyMulti;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, LinksOnlyMulti::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--links-only-multi"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(LinksOnlyMulti(true)),
+            LinksOnlyMulti::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, LinksOnlyMulti::from_config(&Config::with_none()));
+    }
+}
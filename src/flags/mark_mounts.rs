@@ -0,0 +1,64 @@
+//! This module defines the [MarkMounts] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to mark the directories crossing into another filesystem.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct MarkMounts(pub bool);
+
+impl Configurable<Self> for MarkMounts {
+    /// Get a potential `MarkMounts` value from [ArgMatches].
+    ///
+    /// If the "mark-mounts" argument is passed, this returns an `MarkMounts` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("mark-mounts") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `MarkMounts` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MarkMounts;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, MarkMounts::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--mark-mounts"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(MarkMounts(true)),
+            MarkMounts::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, MarkMounts::from_config(&Config::with_none()));
+    }
+}
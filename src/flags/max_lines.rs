@@ -0,0 +1,76 @@
+//! This module defines the [MaxLines] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag holding the maximum number of output lines, after which the output is truncated.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct MaxLines(pub Option<usize>);
+
+impl Configurable<Self> for MaxLines {
+    /// Get a potential `MaxLines` value from [ArgMatches].
+    ///
+    /// If the "max-lines" argument is passed, this returns a `MaxLines` with its last parameter
+    /// in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        matches
+            .values_of("max-lines")?
+            .next_back()
+            .and_then(|lines| lines.parse().ok())
+            .map(|lines| Self(Some(lines)))
+    }
+
+    /// `MaxLines` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MaxLines;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, MaxLines::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_lines() {
+        let argv = vec!["lsd", "--max-lines", "20"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(MaxLines(Some(20))),
+            MaxLines::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_zero() {
+        let argv = vec!["lsd", "--max-lines", "0"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--max-lines", "many"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, MaxLines::from_config(&Config::with_none()));
+    }
+}
@@ -0,0 +1,70 @@
+//! This module defines the [MinColumnWidth] flag. To set it up from [ArgMatches], a [Config] and
+//! its [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag holding the minimum width of the columns of the grid layout.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct MinColumnWidth(pub usize);
+
+impl Configurable<Self> for MinColumnWidth {
+    /// Get a potential `MinColumnWidth` value from [ArgMatches].
+    ///
+    /// If the "min-column-width" argument is passed, this returns a `MinColumnWidth` with its
+    /// last parameter in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        matches
+            .values_of("min-column-width")?
+            .next_back()
+            .and_then(|width| width.parse().ok())
+            .map(Self)
+    }
+
+    /// `MinColumnWidth` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MinColumnWidth;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, MinColumnWidth::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_width() {
+        let argv = vec!["lsd", "--min-column-width", "12"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(MinColumnWidth(12)),
+            MinColumnWidth::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--min-column-width", "wide"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, MinColumnWidth::from_config(&Config::with_none()));
+    }
+}
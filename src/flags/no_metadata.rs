@@ -0,0 +1,64 @@
+//! This module defines the [NoMetadata] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to list the names only, without reading the metadata of the files.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct NoMetadata(pub bool);
+
+impl Configurable<Self> for NoMetadata {
+    /// Get a potential `NoMetadata` value from [ArgMatches].
+    ///
+    /// If the "no-metadata" argument is passed, this returns a `NoMetadata` with value `true` in
+    /// a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("no-metadata") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `NoMetadata` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::NoMetadata;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, NoMetadata::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--no-metadata"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(NoMetadata(true)),
+            NoMetadata::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, NoMetadata::from_config(&Config::with_none()));
+    }
+}
@@ -0,0 +1,61 @@
+//! This module defines the [Numeric] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to display the user and group ids instead of their names.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Numeric(pub bool);
+
+impl Configurable<Self> for Numeric {
+    /// Get a potential `Numeric` value from [ArgMatches].
+    ///
+    /// If the "numeric" argument is passed, this returns a `Numeric` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("numeric") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `Numeric` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Numeric;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Numeric::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--numeric"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Numeric(true)), Numeric::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Numeric::from_config(&Config::with_none()));
+    }
+}
@@ -0,0 +1,151 @@
+//! This module defines the [OutputFormat] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing which format to print the listing in.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// The variant to print the listing for the terminal, in the chosen layout.
+    Text,
+    /// The variant to print a header row and one comma separated row per entry.
+    Csv,
+    /// The variant to print an array of one object per entry, nesting the content of the
+    /// directories.
+    Json,
+    /// The variant to print one literal name per line, without any formatting.
+    Raw,
+    /// The variant to print an HTML table of one row per entry, styled by inline CSS.
+    Html,
+}
+
+impl OutputFormat {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "text" => Some(Self::Text),
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            "raw" => Some(Self::Raw),
+            "html" => Some(Self::Html),
+            _ => {
+                panic!(
+                    "Format can only be one of text, csv, json, raw or html, but got {}.",
+                    value
+                );
+            }
+        }
+    }
+}
+
+impl Configurable<Self> for OutputFormat {
+    /// Get a potential `OutputFormat` variant from [ArgMatches].
+    ///
+    /// If the "json" or the "raw" argument is passed, this returns [OutputFormat::Json] or
+    /// [OutputFormat::Raw] in a [Some], the "null" argument being the same as "raw". Otherwise if
+    /// the "format" argument is passed with "text", "csv", "json", "raw" or "html", the
+    /// corresponding `OutputFormat` variant is returned in a [Some]. Otherwise this returns
+    /// [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("json") {
+            return Some(Self::Json);
+        }
+        if matches.is_present("raw") || matches.is_present("null") {
+            return Some(Self::Raw);
+        }
+        if matches.occurrences_of("format") > 0 {
+            if let Some(format) = matches.values_of("format")?.next_back() {
+                return Self::from_str(format);
+            }
+        }
+        None
+    }
+
+    /// `OutputFormat` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+/// The default value for `OutputFormat` is [OutputFormat::Text].
+impl Default for OutputFormat {
+    fn default() -> Self {
+        Self::Text
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::OutputFormat;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, OutputFormat::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_csv() {
+        let argv = vec!["lsd", "--format", "csv"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(OutputFormat::Csv),
+            OutputFormat::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_format_multi() {
+        let argv = vec!["lsd", "--format", "csv", "--format", "text"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(OutputFormat::Text),
+            OutputFormat::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_json() {
+        let argv = vec!["lsd", "--format", "csv", "--json"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(OutputFormat::Json),
+            OutputFormat::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_raw() {
+        let argv = vec!["lsd", "--raw"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(OutputFormat::Raw),
+            OutputFormat::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_html() {
+        let argv = vec!["lsd", "--format", "html"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(OutputFormat::Html),
+            OutputFormat::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, OutputFormat::from_config(&Config::with_none()));
+    }
+}
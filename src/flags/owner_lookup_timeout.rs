@@ -0,0 +1,71 @@
+//! This module defines the [OwnerLookupTimeout] flag. To set it up from [ArgMatches], a [Config]
+//! and its [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag holding how many milliseconds to wait for a user or group name before falling back
+/// to the numeric id.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct OwnerLookupTimeout(pub Option<u64>);
+
+impl Configurable<Self> for OwnerLookupTimeout {
+    /// Get a potential `OwnerLookupTimeout` value from [ArgMatches].
+    ///
+    /// If the "owner-lookup-timeout" argument is passed, this returns an `OwnerLookupTimeout`
+    /// with its last parameter in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        matches
+            .values_of("owner-lookup-timeout")?
+            .next_back()
+            .and_then(|timeout| timeout.parse().ok())
+            .map(|timeout| Self(Some(timeout)))
+    }
+
+    /// `OwnerLookupTimeout` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::OwnerLookupTimeout;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, OwnerLookupTimeout::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_timeout() {
+        let argv = vec!["lsd", "--owner-lookup-timeout", "250"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(OwnerLookupTimeout(Some(250))),
+            OwnerLookupTimeout::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--owner-lookup-timeout", "soon"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, OwnerLookupTimeout::from_config(&Config::with_none()));
+    }
+}
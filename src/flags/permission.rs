@@ -0,0 +1,123 @@
+//! This module defines the [PermissionFlag]. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use serde::Deserialize;
+
+/// The flag showing how to display the permissions.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PermissionFlag {
+    /// The variant of the permission letters, like `rwxr-xr-x`.
+    Rwx,
+    /// The variant of the four octal digits of the mode, like `0755`.
+    Octal,
+}
+
+impl PermissionFlag {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "rwx" => Some(Self::Rwx),
+            "octal" => Some(Self::Octal),
+            _ => {
+                panic!(
+                    "Permission can only be one of rwx or octal, but got {}.",
+                    value
+                );
+            }
+        }
+    }
+}
+
+impl Configurable<Self> for PermissionFlag {
+    /// Get a potential `PermissionFlag` variant from [ArgMatches].
+    ///
+    /// If the "permission" argument is passed, this returns the variant corresponding to its
+    /// last parameter in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("classic") {
+            Some(Self::Rwx)
+        } else {
+            Self::from_str(matches.values_of("permission")?.next_back()?)
+        }
+    }
+
+    /// Get a potential `PermissionFlag` variant from a [Config].
+    ///
+    /// If the `Config::permission` has value, this returns it in a [Some]. Otherwise this
+    /// returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(true) = config.classic {
+            Some(Self::Rwx)
+        } else {
+            config.permission
+        }
+    }
+}
+
+/// The default value for `PermissionFlag` is [PermissionFlag::Rwx].
+impl Default for PermissionFlag {
+    fn default() -> Self {
+        Self::Rwx
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PermissionFlag;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, PermissionFlag::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_octal() {
+        let argv = vec!["lsd", "--permission", "octal"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(PermissionFlag::Octal),
+            PermissionFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_permission_multi() {
+        let argv = vec!["lsd", "--permission", "octal", "--permission", "rwx"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(PermissionFlag::Rwx),
+            PermissionFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, PermissionFlag::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_octal() {
+        let mut c = Config::with_none();
+        c.permission = Some(PermissionFlag::Octal);
+        assert_eq!(Some(PermissionFlag::Octal), PermissionFlag::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_classic_mode() {
+        let mut c = Config::with_none();
+        c.classic = Some(true);
+        c.permission = Some(PermissionFlag::Octal);
+        assert_eq!(Some(PermissionFlag::Rwx), PermissionFlag::from_config(&c));
+    }
+}
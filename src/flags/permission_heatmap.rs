@@ -0,0 +1,64 @@
+//! This module defines the [PermissionHeatmap] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to color the background of the permission triads by access level.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct PermissionHeatmap(pub bool);
+
+impl Configurable<Self> for PermissionHeatmap {
+    /// Get a potential `PermissionHeatmap` value from [ArgMatches].
+    ///
+    /// If the "permission-heatmap" argument is passed, this returns a `PermissionHeatmap` with value
+    /// `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("permission-heatmap") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `PermissionHeatmap` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PermissionHeatmap;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, PermissionHeatmap::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--permission-heatmap"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(PermissionHeatmap(true)),
+            PermissionHeatmap::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, PermissionHeatmap::from_config(&Config::with_none()));
+    }
+}
@@ -0,0 +1,61 @@
+//! This module defines the [Rtl] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to lay the grid out from right to left.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Rtl(pub bool);
+
+impl Configurable<Self> for Rtl {
+    /// Get a potential `Rtl` value from [ArgMatches].
+    ///
+    /// If the "rtl" argument is passed, this returns a `Rtl` with value `true` in a [Some].
+    /// Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("rtl") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `Rtl` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Rtl;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Rtl::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--rtl"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Rtl(true)), Rtl::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Rtl::from_config(&Config::with_none()));
+    }
+}
@@ -0,0 +1,81 @@
+//! This module defines the [ScanBudget] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app;
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag holding the number of bytes of files to scan, after which the recursion stops.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct ScanBudget(pub Option<u64>);
+
+impl Configurable<Self> for ScanBudget {
+    /// Get a potential `ScanBudget` value from [ArgMatches].
+    ///
+    /// If the "scan-budget" argument is passed, this returns a `ScanBudget` with the size of its
+    /// last parameter in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        matches
+            .values_of("scan-budget")?
+            .next_back()
+            .and_then(app::parse_byte_size)
+            .map(|bytes| Self(Some(bytes)))
+    }
+
+    /// `ScanBudget` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ScanBudget;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, ScanBudget::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_units() {
+        for (arg, bytes) in &[
+            ("512", 512),
+            ("2K", 2048),
+            ("100MiB", 100 * 1024 * 1024),
+            ("3kb", 3000),
+            ("1G", 1 << 30),
+        ] {
+            let argv = vec!["lsd", "--scan-budget", arg];
+            let matches = app::build().get_matches_from_safe(argv).unwrap();
+            assert_eq!(
+                Some(ScanBudget(Some(*bytes))),
+                ScanBudget::from_arg_matches(&matches)
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        for arg in &["0", "12XB", "MiB", "-1K"] {
+            let argv = vec!["lsd", "--scan-budget", arg];
+            assert!(app::build().get_matches_from_safe(argv).is_err());
+        }
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, ScanBudget::from_config(&Config::with_none()));
+    }
+}
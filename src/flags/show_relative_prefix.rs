@@ -0,0 +1,65 @@
+//! This module defines the [ShowRelativePrefix] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to prefix the recursively listed names with their relative
+/// directory.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct ShowRelativePrefix(pub bool);
+
+impl Configurable<Self> for ShowRelativePrefix {
+    /// Get a potential `ShowRelativePrefix` value from [ArgMatches].
+    ///
+    /// If the "show-relative-prefix" argument is passed, this returns a `ShowRelativePrefix` with
+    /// value `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("show-relative-prefix") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `ShowRelativePrefix` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ShowRelativePrefix;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, ShowRelativePrefix::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--show-relative-prefix"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(ShowRelativePrefix(true)),
+            ShowRelativePrefix::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, ShowRelativePrefix::from_config(&Config::with_none()));
+    }
+}
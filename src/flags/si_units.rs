@@ -0,0 +1,61 @@
+//! This module defines the [SiUnits] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to display the sizes in the powers of 1000, rather than of 1024.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct SiUnits(pub bool);
+
+impl Configurable<Self> for SiUnits {
+    /// Get a potential `SiUnits` value from [ArgMatches].
+    ///
+    /// If the "si" argument is passed, this returns an `SiUnits` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("si") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `SiUnits` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SiUnits;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, SiUnits::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--si"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(SiUnits(true)), SiUnits::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, SiUnits::from_config(&Config::with_none()));
+    }
+}
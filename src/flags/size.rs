@@ -40,10 +40,10 @@ impl Configurable<Self> for SizeFlag {
     /// Get a potential `SizeFlag` variant from [ArgMatches].
     ///
     /// If any of the "default", "short" or "bytes" arguments is passed, the corresponding
-    /// `SizeFlag` variant is returned in a [Some]. If neither of them is passed, this returns
-    /// [None].
+    /// `SizeFlag` variant is returned in a [Some], the "bytes" argument being the same as
+    /// "--size bytes". If neither of them is passed, this returns [None].
     fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
-        if matches.is_present("classic") {
+        if matches.is_present("classic") || matches.is_present("bytes") {
             return Some(Self::Bytes);
         } else if matches.occurrences_of("size") > 0 {
             if let Some(size) = matches.values_of("size")?.last() {
@@ -113,6 +113,24 @@ mod test {
         assert_eq!(Some(SizeFlag::Bytes), SizeFlag::from_arg_matches(&matches));
     }
 
+    #[test]
+    fn test_from_arg_matches_bytes_flag() {
+        let args = vec!["lsd", "-B"];
+        let matches = app::build().get_matches_from_safe(args).unwrap();
+        assert_eq!(Some(SizeFlag::Bytes), SizeFlag::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_bytes_flag_overridden() {
+        let args = vec!["lsd", "--bytes", "--size", "short"];
+        let matches = app::build().get_matches_from_safe(args).unwrap();
+        assert_eq!(Some(SizeFlag::Short), SizeFlag::from_arg_matches(&matches));
+
+        let args = vec!["lsd", "--size", "short", "--bytes"];
+        let matches = app::build().get_matches_from_safe(args).unwrap();
+        assert_eq!(Some(SizeFlag::Bytes), SizeFlag::from_arg_matches(&matches));
+    }
+
     #[test]
     fn test_from_arg_matches_size_multi() {
         let args = vec!["lsd", "--size", "bytes", "--size", "short"];
@@ -0,0 +1,147 @@
+//! This module defines the [SizeUnits] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+use crate::meta::{FileType, Name, Unit};
+use crate::print_error;
+
+use clap::ArgMatches;
+
+/// The flag holding the units in which to display the sizes of the files, by extension, whatever
+/// their magnitude.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct SizeUnits(pub Vec<(String, Unit)>);
+
+impl SizeUnits {
+    /// Get the unit of the size of the file `name`, if its extension has one.
+    pub fn unit_of(&self, name: &Name) -> Option<Unit> {
+        if !matches!(name.file_type(), FileType::File { .. }) {
+            return None;
+        }
+
+        let extension = name.extension()?;
+        self.0
+            .iter()
+            .find(|(configured, _)| configured.eq_ignore_ascii_case(extension))
+            .map(|(_, unit)| unit.clone())
+    }
+}
+
+/// Get the [Unit] of a `value` like MB.
+fn parse_unit(value: &str) -> Option<Unit> {
+    match value.to_ascii_uppercase().as_str() {
+        "B" => Some(Unit::Byte),
+        "K" | "KB" => Some(Unit::Kilo),
+        "M" | "MB" => Some(Unit::Mega),
+        "G" | "GB" => Some(Unit::Giga),
+        "T" | "TB" => Some(Unit::Tera),
+        _ => None,
+    }
+}
+
+impl Configurable<Self> for SizeUnits {
+    /// `SizeUnits` can not be configured by [ArgMatches]
+    ///
+    /// Return `None`
+    fn from_arg_matches(_: &ArgMatches) -> Option<Self> {
+        None
+    }
+
+    /// Get a potential `SizeUnits` value from a [Config].
+    ///
+    /// If the `Config::size_units` has value, this returns its extensions with a valid unit as a
+    /// `SizeUnits` in a [Some], the invalid units being skipped with a warning. Otherwise this
+    /// returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        let units = config.size_units.as_ref()?;
+
+        let mut extensions: Vec<_> = units
+            .iter()
+            .filter_map(|(extension, unit)| match parse_unit(unit) {
+                Some(unit) => Some((extension.clone(), unit)),
+                None => {
+                    print_error!(
+                        "Config size unit of {} can only be one of B, KB, MB, GB or TB, got {}.",
+                        extension,
+                        unit
+                    );
+                    None
+                }
+            })
+            .collect();
+        extensions.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        Some(Self(extensions))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SizeUnits;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+    use crate::meta::{FileType, Name, Unit};
+    use std::path::Path;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, SizeUnits::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, SizeUnits::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_skips_invalid_units() {
+        let mut c = Config::with_none();
+        c.size_units = Some(
+            vec![
+                ("mp4".to_string(), "MB".to_string()),
+                ("flac".to_string(), "mb".to_string()),
+                ("txt".to_string(), "lines".to_string()),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        assert_eq!(
+            Some(SizeUnits(vec![
+                ("flac".to_string(), Unit::Mega),
+                ("mp4".to_string(), Unit::Mega),
+            ])),
+            SizeUnits::from_config(&c)
+        );
+    }
+
+    #[test]
+    fn test_unit_of() {
+        let units = SizeUnits(vec![("mp4".to_string(), Unit::Mega)]);
+        let file = FileType::File {
+            uid: false,
+            exec: false,
+        };
+
+        assert_eq!(
+            Some(Unit::Mega),
+            units.unit_of(&Name::new(Path::new("clip.MP4"), file))
+        );
+        assert_eq!(
+            None,
+            units.unit_of(&Name::new(Path::new("notes.txt"), file))
+        );
+        assert_eq!(
+            None,
+            units.unit_of(&Name::new(
+                Path::new("clips.mp4"),
+                FileType::Directory { uid: false }
+            ))
+        );
+    }
+}
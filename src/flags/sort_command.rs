@@ -0,0 +1,74 @@
+//! This module defines the [SortCommand] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag holding an external command used to sort the entries.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct SortCommand(pub Option<String>);
+
+impl Configurable<Self> for SortCommand {
+    /// Get a potential `SortCommand` value from [ArgMatches].
+    ///
+    /// If the "sort-command" argument is passed, this returns a `SortCommand` with its last
+    /// parameter in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        matches
+            .values_of("sort-command")?
+            .next_back()
+            .map(|command| Self(Some(command.to_string())))
+    }
+
+    /// `SortCommand` can not be configured by a [Config], as it would run an arbitrary command
+    /// on every invocation.
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SortCommand;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, SortCommand::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_command() {
+        let argv = vec!["lsd", "--sort-command", "sort -r"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SortCommand(Some("sort -r".to_string()))),
+            SortCommand::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_multiple() {
+        let argv = vec!["lsd", "--sort-command", "sort", "--sort-command", "sort -r"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SortCommand(Some("sort -r".to_string()))),
+            SortCommand::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, SortCommand::from_config(&Config::with_none()));
+    }
+}
@@ -14,21 +14,41 @@ pub struct Sorting {
     pub column: SortColumn,
     pub order: SortOrder,
     pub dir_grouping: DirGrouping,
+    pub dotfiles: SortDotfiles,
+    /// Whether the [SortColumn::Version] compares the names ignoring their case.
+    pub version_ignore_case: bool,
 }
 
 impl Sorting {
     /// Get a `Sorting` struct from [ArgMatches], a [Config] or the [Default] values.
     ///
-    /// The [SortColumn], [SortOrder] and [DirGrouping] are configured with their respective
-    /// [Configurable] implementation.
+    /// The [SortColumn], [SortOrder], [DirGrouping] and [SortDotfiles] are configured with their respective
+    /// [Configurable] implementation. If `Config::sorting::interleave-dirs` is `true`, the
+    /// [DirGrouping] is forced to [DirGrouping::None], whatever the arguments. The version sort
+    /// ignores the case when `Config::sorting::version-ignore-case` is `true`.
     pub fn configure_from(matches: &ArgMatches, config: &Config) -> Self {
         let column = SortColumn::configure_from(matches, config);
         let order = SortOrder::configure_from(matches, config);
-        let dir_grouping = DirGrouping::configure_from(matches, config);
+        let interleave_dirs = config
+            .sorting
+            .as_ref()
+            .and_then(|sorting| sorting.interleave_dirs);
+        let dir_grouping = match interleave_dirs {
+            Some(true) => DirGrouping::None,
+            _ => DirGrouping::configure_from(matches, config),
+        };
+        let dotfiles = SortDotfiles::configure_from(matches, config);
+        let version_ignore_case = config
+            .sorting
+            .as_ref()
+            .and_then(|sorting| sorting.version_ignore_case)
+            .unwrap_or(false);
         Self {
             column,
             order,
             dir_grouping,
+            dotfiles,
+            version_ignore_case,
         }
     }
 }
@@ -40,6 +60,8 @@ pub enum SortColumn {
     Extension,
     Name,
     Time,
+    /// The variant of the birth time, falling back to the time of the last modification.
+    Created,
     Size,
     Version,
 }
@@ -56,6 +78,8 @@ impl Configurable<Self> for SortColumn {
         };
         if matches.is_present("timesort") || sort == Some("time") {
             Some(Self::Time)
+        } else if sort == Some("created") {
+            Some(Self::Created)
         } else if matches.is_present("sizesort") || sort == Some("size") {
             Some(Self::Size)
         } else if matches.is_present("extensionsort") || sort == Some("extension") {
@@ -164,13 +188,19 @@ impl Configurable<Self> for DirGrouping {
     /// Get a potential `DirGrouping` variant from [ArgMatches].
     ///
     /// If the "classic" argument is passed, then this returns the [DirGrouping::None] variant in a
-    /// [Some]. Otherwise if the argument is passed, this returns the variant corresponding to its
-    /// parameter in a [Some]. Otherwise this returns [None].
+    /// [Some]. Otherwise if the "group-directories-last" argument is passed, this returns the
+    /// [DirGrouping::Last] variant in a [Some]. Otherwise if the "group-dirs" argument is passed, this
+    /// returns the variant corresponding to its parameter in a [Some]. Otherwise this returns
+    /// [None].
     fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
         if matches.is_present("classic") {
             return Some(Self::None);
         }
 
+        if matches.is_present("group-directories-last") {
+            return Some(Self::Last);
+        }
+
         if matches.occurrences_of("group-dirs") > 0 {
             if let Some(group_dirs) = matches.values_of("group-dirs")?.last() {
                 return Self::from_str(group_dirs);
@@ -204,6 +234,63 @@ impl Default for DirGrouping {
     }
 }
 
+/// The flag showing where to place the dotfiles.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortDotfiles {
+    /// The variant to leave the dotfiles where the sort column puts them.
+    #[serde(skip)]
+    None,
+    First,
+    Last,
+    /// The variant to sort the dotfiles by their name without the leading dot.
+    Mixed,
+}
+
+impl SortDotfiles {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "first" => Some(Self::First),
+            "last" => Some(Self::Last),
+            "mixed" => Some(Self::Mixed),
+            _ => panic!(
+                "Sort dotfiles can only be one of first, last or mixed, but got {}.",
+                value
+            ),
+        }
+    }
+}
+
+impl Configurable<Self> for SortDotfiles {
+    /// Get a potential `SortDotfiles` variant from [ArgMatches].
+    ///
+    /// If the "sort-dotfiles" argument is passed, this returns the variant corresponding to its
+    /// parameter in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("sort-dotfiles") > 0 {
+            if let Some(dotfiles) = matches.values_of("sort-dotfiles")?.next_back() {
+                return Self::from_str(dotfiles);
+            }
+        }
+        None
+    }
+
+    /// Get a potential `SortDotfiles` variant from a [Config].
+    ///
+    /// If `Config::sorting::dotfiles` has value and is one of "first", "last" or "mixed", this
+    /// returns its corresponding variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.sorting.as_ref().and_then(|sort| sort.dotfiles)
+    }
+}
+
+/// The default value for `SortDotfiles` is [SortDotfiles::None].
+impl Default for SortDotfiles {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
 #[cfg(test)]
 mod test_sort_column {
     use super::SortColumn;
@@ -239,6 +326,16 @@ mod test_sort_column {
         );
     }
 
+    #[test]
+    fn test_from_arg_matches_created() {
+        let argv = vec!["lsd", "--sort", "created"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SortColumn::Created),
+            SortColumn::from_arg_matches(&matches)
+        );
+    }
+
     #[test]
     fn test_from_arg_matches_size() {
         let argv = vec!["lsd", "--sizesort"];
@@ -322,6 +419,9 @@ mod test_sort_column {
             column: None,
             reverse: None,
             dir_grouping: None,
+            interleave_dirs: None,
+            version_ignore_case: None,
+            dotfiles: None,
         });
 
         assert_eq!(None, SortColumn::from_config(&c));
@@ -334,6 +434,9 @@ mod test_sort_column {
             column: Some(SortColumn::Extension),
             reverse: None,
             dir_grouping: None,
+            interleave_dirs: None,
+            version_ignore_case: None,
+            dotfiles: None,
         });
         assert_eq!(Some(SortColumn::Extension), SortColumn::from_config(&c));
     }
@@ -345,6 +448,9 @@ mod test_sort_column {
             column: Some(SortColumn::Name),
             reverse: None,
             dir_grouping: None,
+            interleave_dirs: None,
+            version_ignore_case: None,
+            dotfiles: None,
         });
         assert_eq!(Some(SortColumn::Name), SortColumn::from_config(&c));
     }
@@ -356,6 +462,9 @@ mod test_sort_column {
             column: Some(SortColumn::Time),
             reverse: None,
             dir_grouping: None,
+            interleave_dirs: None,
+            version_ignore_case: None,
+            dotfiles: None,
         });
         assert_eq!(Some(SortColumn::Time), SortColumn::from_config(&c));
     }
@@ -367,6 +476,9 @@ mod test_sort_column {
             column: Some(SortColumn::Size),
             reverse: None,
             dir_grouping: None,
+            interleave_dirs: None,
+            version_ignore_case: None,
+            dotfiles: None,
         });
         assert_eq!(Some(SortColumn::Size), SortColumn::from_config(&c));
     }
@@ -378,6 +490,9 @@ mod test_sort_column {
             column: Some(SortColumn::Version),
             reverse: None,
             dir_grouping: None,
+            interleave_dirs: None,
+            version_ignore_case: None,
+            dotfiles: None,
         });
         assert_eq!(Some(SortColumn::Version), SortColumn::from_config(&c));
     }
@@ -428,6 +543,9 @@ mod test_sort_order {
             column: None,
             reverse: None,
             dir_grouping: None,
+            interleave_dirs: None,
+            version_ignore_case: None,
+            dotfiles: None,
         });
         assert_eq!(None, SortOrder::from_config(&c));
     }
@@ -439,6 +557,9 @@ mod test_sort_order {
             column: None,
             reverse: Some(true),
             dir_grouping: None,
+            interleave_dirs: None,
+            version_ignore_case: None,
+            dotfiles: None,
         });
         assert_eq!(Some(SortOrder::Reverse), SortOrder::from_config(&c));
     }
@@ -450,6 +571,9 @@ mod test_sort_order {
             column: None,
             reverse: Some(false),
             dir_grouping: None,
+            interleave_dirs: None,
+            version_ignore_case: None,
+            dotfiles: None,
         });
         assert_eq!(Some(SortOrder::Default), SortOrder::from_config(&c));
     }
@@ -528,6 +652,33 @@ mod test_dir_grouping {
         );
     }
 
+    #[test]
+    fn test_from_arg_matches_group_directories_last() {
+        let argv = vec!["lsd", "--group-directories-last"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DirGrouping::Last),
+            DirGrouping::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_group_directories_last_overridden() {
+        let argv = vec!["lsd", "--group-directories-last", "--group-dirs", "first"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DirGrouping::First),
+            DirGrouping::from_arg_matches(&matches)
+        );
+
+        let argv = vec!["lsd", "--group-dirs", "first", "--group-directories-last"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DirGrouping::Last),
+            DirGrouping::from_arg_matches(&matches)
+        );
+    }
+
     #[test]
     fn test_from_config_empty() {
         assert_eq!(None, DirGrouping::from_config(&Config::with_none()));
@@ -540,6 +691,9 @@ mod test_dir_grouping {
             column: None,
             reverse: None,
             dir_grouping: Some(DirGrouping::First),
+            interleave_dirs: None,
+            version_ignore_case: None,
+            dotfiles: None,
         });
         assert_eq!(Some(DirGrouping::First), DirGrouping::from_config(&c));
     }
@@ -551,6 +705,9 @@ mod test_dir_grouping {
             column: None,
             reverse: None,
             dir_grouping: Some(DirGrouping::Last),
+            interleave_dirs: None,
+            version_ignore_case: None,
+            dotfiles: None,
         });
         assert_eq!(Some(DirGrouping::Last), DirGrouping::from_config(&c));
     }
@@ -562,6 +719,9 @@ mod test_dir_grouping {
             column: None,
             reverse: None,
             dir_grouping: None,
+            interleave_dirs: None,
+            version_ignore_case: None,
+            dotfiles: None,
         });
         assert_eq!(None, DirGrouping::from_config(&c));
     }
@@ -573,8 +733,83 @@ mod test_dir_grouping {
             column: None,
             reverse: None,
             dir_grouping: Some(DirGrouping::Last),
+            interleave_dirs: None,
+            version_ignore_case: None,
+            dotfiles: None,
         });
         c.classic = Some(true);
         assert_eq!(Some(DirGrouping::None), DirGrouping::from_config(&c));
     }
+
+    #[test]
+    fn test_configure_from_interleave_dirs() {
+        let argv = vec!["lsd", "--group-dirs", "first"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let mut c = Config::with_none();
+        c.sorting = Some(Sorting {
+            column: None,
+            reverse: None,
+            dir_grouping: Some(DirGrouping::Last),
+            interleave_dirs: Some(true),
+            version_ignore_case: None,
+            dotfiles: None,
+        });
+        assert_eq!(
+            DirGrouping::None,
+            super::Sorting::configure_from(&matches, &c).dir_grouping
+        );
+
+        c.sorting = Some(Sorting {
+            column: None,
+            reverse: None,
+            dir_grouping: Some(DirGrouping::Last),
+            interleave_dirs: Some(false),
+            version_ignore_case: None,
+            dotfiles: None,
+        });
+        assert_eq!(
+            DirGrouping::First,
+            super::Sorting::configure_from(&matches, &c).dir_grouping
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_sort_dotfiles {
+    use super::SortDotfiles;
+
+    use crate::app;
+    use crate::config_file::{Config, Sorting};
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, SortDotfiles::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_last() {
+        let argv = vec!["lsd", "--sort-dotfiles", "first", "--sort-dotfiles", "last"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SortDotfiles::Last),
+            SortDotfiles::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_mixed() {
+        let mut c = Config::with_none();
+        c.sorting = Some(Sorting {
+            column: None,
+            reverse: None,
+            dir_grouping: None,
+            interleave_dirs: None,
+            version_ignore_case: None,
+            dotfiles: Some(SortDotfiles::Mixed),
+        });
+        assert_eq!(Some(SortDotfiles::Mixed), SortDotfiles::from_config(&c));
+    }
 }
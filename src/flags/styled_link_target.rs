@@ -0,0 +1,64 @@
+//! This module defines the [StyledLinkTarget] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to color the symlink targets and prefix their icon, by their type.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct StyledLinkTarget(pub bool);
+
+impl Configurable<Self> for StyledLinkTarget {
+    /// Get a potential `StyledLinkTarget` value from [ArgMatches].
+    ///
+    /// If the "styled-link-target" argument is passed, this returns a `StyledLinkTarget` with value
+    /// `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("styled-link-target") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `StyledLinkTarget` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::StyledLinkTarget;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, StyledLinkTarget::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--styled-link-target"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(StyledLinkTarget(true)),
+            StyledLinkTarget::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, StyledLinkTarget::from_config(&Config::with_none()));
+    }
+}
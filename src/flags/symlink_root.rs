@@ -0,0 +1,66 @@
+//! This module defines the [SymlinkRoot] flag. To set it up from [ArgMatches], a [Config] and
+//! its [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use std::path::PathBuf;
+
+/// The flag holding the directory absolute symlink targets are resolved against, as if it was
+/// the root of the file system.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct SymlinkRoot(pub Option<PathBuf>);
+
+impl Configurable<Self> for SymlinkRoot {
+    /// Get a potential `SymlinkRoot` value from [ArgMatches].
+    ///
+    /// If the "symlink-root" argument is passed, this returns a `SymlinkRoot` with its last
+    /// parameter in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        matches
+            .values_of_os("symlink-root")?
+            .next_back()
+            .map(|root| Self(Some(PathBuf::from(root))))
+    }
+
+    /// `SymlinkRoot` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SymlinkRoot;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, SymlinkRoot::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_root() {
+        let argv = vec!["lsd", "--symlink-root", "/srv/container"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SymlinkRoot(Some(PathBuf::from("/srv/container")))),
+            SymlinkRoot::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, SymlinkRoot::from_config(&Config::with_none()));
+    }
+}
@@ -0,0 +1,145 @@
+//! This module defines the registry of the bundled themes, each pairing a [ColorTheme] with an
+//! [IconTheme] under a single name given to `--theme` or to the `theme` config. A value not naming
+//! a bundled theme is the path of a theme file of RGB colors.
+
+use super::color::ColorTheme;
+use super::icons::IconTheme;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use std::path::PathBuf;
+
+/// The bundled themes, by name.
+pub const THEMES: &[(&str, ColorTheme, IconTheme)] = &[
+    ("dark-fancy", ColorTheme::Dark, IconTheme::Fancy),
+    ("dark-unicode", ColorTheme::Dark, IconTheme::Unicode),
+    ("fixed-fancy", ColorTheme::Fixed, IconTheme::Fancy),
+    ("fixed-unicode", ColorTheme::Fixed, IconTheme::Unicode),
+];
+
+/// Get the color and icon themes bundled under `name`.
+pub fn lookup(name: &str) -> Option<(ColorTheme, IconTheme)> {
+    THEMES
+        .iter()
+        .find(|(theme, _, _)| *theme == name)
+        .map(|(_, color, icon)| (*color, *icon))
+}
+
+/// Get the themes bundled under the name of the last "theme" argument, if it is passed and names
+/// a bundled theme.
+pub fn from_arg_matches(matches: &ArgMatches) -> Option<(ColorTheme, IconTheme)> {
+    lookup(matches.values_of("theme")?.next_back()?)
+}
+
+/// Get the themes bundled under the name of the `Config::theme`, if it has value and names a
+/// bundled theme.
+pub fn from_config(config: &Config) -> Option<(ColorTheme, IconTheme)> {
+    lookup(config.theme.as_ref()?)
+}
+
+/// Get the path of the theme file given to the last "theme" argument, or else to the
+/// `Config::theme`, if the value does not name a bundled theme.
+pub fn file_configure_from(matches: &ArgMatches, config: &Config) -> Option<PathBuf> {
+    let value = match matches.values_of("theme") {
+        Some(mut values) => values.next_back()?,
+        None => config.theme.as_ref()?,
+    };
+
+    match lookup(value) {
+        Some(_) => None,
+        None => Some(PathBuf::from(value)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{file_configure_from, lookup, THEMES};
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::{ColorTheme, Flags, IconTheme};
+
+    #[test]
+    fn test_lookup() {
+        assert_eq!(
+            Some((ColorTheme::Dark, IconTheme::Fancy)),
+            lookup("dark-fancy")
+        );
+        assert_eq!(
+            Some((ColorTheme::Fixed, IconTheme::Unicode)),
+            lookup("fixed-unicode")
+        );
+        assert_eq!(None, lookup("solarized"));
+    }
+
+    #[test]
+    fn test_file_configure_from() {
+        let argv = vec!["lsd", "--theme", "themes/solarized.yaml"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some("themes/solarized.yaml".into()),
+            file_configure_from(&matches, &Config::with_none())
+        );
+
+        let argv = vec!["lsd", "--theme", "dark-fancy"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let mut c = Config::with_none();
+        c.theme = Some("themes/solarized.yaml".into());
+        assert_eq!(None, file_configure_from(&matches, &c));
+
+        let matches = app::build().get_matches_from_safe(vec!["lsd"]).unwrap();
+        assert_eq!(
+            Some("themes/solarized.yaml".into()),
+            file_configure_from(&matches, &c)
+        );
+    }
+
+    #[test]
+    fn test_possible_values_match_registry() {
+        for (name, _, _) in THEMES {
+            let argv = vec!["lsd", "--theme", name];
+            assert!(app::build().get_matches_from_safe(argv).is_ok(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_configure_from_theme() {
+        let argv = vec!["lsd", "--theme", "fixed-unicode"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        assert_eq!(ColorTheme::Fixed, flags.color.theme);
+        assert_eq!(IconTheme::Unicode, flags.icons.theme);
+    }
+
+    #[test]
+    fn test_configure_from_theme_overridden() {
+        let argv = vec![
+            "lsd",
+            "--theme",
+            "dark-unicode",
+            "--color-theme",
+            "fixed",
+            "--icon-theme",
+            "fancy",
+        ];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        assert_eq!(ColorTheme::Fixed, flags.color.theme);
+        assert_eq!(IconTheme::Fancy, flags.icons.theme);
+    }
+
+    #[test]
+    fn test_configure_from_config_theme() {
+        let mut c = Config::with_none();
+        c.theme = Some("fixed-unicode".into());
+        let argv = vec!["lsd", "--icon-theme", "fancy"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &c).unwrap();
+
+        assert_eq!(ColorTheme::Fixed, flags.color.theme);
+        assert_eq!(IconTheme::Fancy, flags.icons.theme);
+    }
+}
@@ -0,0 +1,126 @@
+//! This module defines the [TimeFlag]. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use serde::Deserialize;
+
+/// The flag showing which time stamp of the files to display and to sort by.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimeFlag {
+    /// The variant of the time of the last modification.
+    Modified,
+    /// The variant of the time of the last access.
+    Accessed,
+    /// The variant of the time of the creation, the birth time.
+    Created,
+}
+
+impl TimeFlag {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "modified" => Some(Self::Modified),
+            "accessed" => Some(Self::Accessed),
+            "created" => Some(Self::Created),
+            _ => {
+                panic!(
+                    "Time can only be one of modified, accessed or created, but got {}.",
+                    value
+                );
+            }
+        }
+    }
+}
+
+impl Configurable<Self> for TimeFlag {
+    /// Get a potential `TimeFlag` variant from [ArgMatches].
+    ///
+    /// If the "time" argument is passed, this returns the variant corresponding to its last
+    /// parameter in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("classic") {
+            Some(Self::Modified)
+        } else {
+            Self::from_str(matches.values_of("time")?.next_back()?)
+        }
+    }
+
+    /// Get a potential `TimeFlag` variant from a [Config].
+    ///
+    /// If the `Config::time` has value, this returns it in a [Some]. Otherwise this returns
+    /// [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(true) = config.classic {
+            Some(Self::Modified)
+        } else {
+            config.time
+        }
+    }
+}
+
+/// The default value for `TimeFlag` is [TimeFlag::Modified].
+impl Default for TimeFlag {
+    fn default() -> Self {
+        Self::Modified
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TimeFlag;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, TimeFlag::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_accessed() {
+        let argv = vec!["lsd", "--time", "accessed"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(TimeFlag::Accessed),
+            TimeFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_time_multi() {
+        let argv = vec!["lsd", "--time", "accessed", "--time", "created"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(TimeFlag::Created),
+            TimeFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, TimeFlag::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_created() {
+        let mut c = Config::with_none();
+        c.time = Some(TimeFlag::Created);
+        assert_eq!(Some(TimeFlag::Created), TimeFlag::from_config(&c));
+    }
+
+    #[test]
+    fn test_from_config_classic_mode() {
+        let mut c = Config::with_none();
+        c.classic = Some(true);
+        c.time = Some(TimeFlag::Accessed);
+        assert_eq!(Some(TimeFlag::Modified), TimeFlag::from_config(&c));
+    }
+}
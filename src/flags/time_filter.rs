@@ -0,0 +1,157 @@
+//! This module defines the [TimeFilter] flag. To set it up from [ArgMatches], use the
+//! [configure_from](TimeFilter::configure_from) method.
+
+use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone};
+use clap::{ArgMatches, Error, ErrorKind};
+
+/// The formats of the absolute points in time, in the local time zone.
+const DATE_TIME_FORMATS: &[&str] = &["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"];
+
+/// The flag holding the bounds of the modification times of the listed entries, as unix
+/// timestamps.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct TimeFilter {
+    /// The time after which the entries have to be modified.
+    pub newer_than: Option<i64>,
+    /// The time before which the entries have to be modified.
+    pub older_than: Option<i64>,
+}
+
+impl TimeFilter {
+    /// Get the `TimeFilter` value from [ArgMatches], reading the point in time given by the last
+    /// "newer-than" and "older-than" arguments. It can not be configured by a config file.
+    ///
+    /// # Errors
+    ///
+    /// If a point in time is neither a date nor a duration, like 2h.
+    pub fn configure_from(matches: &ArgMatches) -> Result<Self, Error> {
+        let now = Local::now().timestamp();
+        let bound = |name| match matches.values_of(name).and_then(|mut v| v.next_back()) {
+            Some(value) => match parse_time(value, now) {
+                Some(time) => Ok(Some(time)),
+                None => Err(Error::with_description(
+                    &format!(
+                        "invalid value '{}' for --{}, expected a date like 2021-03-07 \
+                         [13:45[:30]] or a duration like 30m, 2h, 3d or 1w",
+                        value, name
+                    ),
+                    ErrorKind::InvalidValue,
+                )),
+            },
+            None => Ok(None),
+        };
+
+        Ok(Self {
+            newer_than: bound("newer-than")?,
+            older_than: bound("older-than")?,
+        })
+    }
+
+    /// Check whether the `modified` timestamp is within the bounds.
+    pub fn matches(&self, modified: i64) -> bool {
+        self.newer_than.is_none_or(|time| modified > time)
+            && self.older_than.is_none_or(|time| modified < time)
+    }
+
+    /// Check whether any bound is set.
+    pub fn is_active(&self) -> bool {
+        self.newer_than.is_some() || self.older_than.is_some()
+    }
+}
+
+/// Parse the `value` into a unix timestamp, either a local date with an optional time or a
+/// duration before `now`, made of a number and one of the s, m, h, d or w units.
+fn parse_time(value: &str, now: i64) -> Option<i64> {
+    let split = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(0);
+    if split > 0 {
+        let (amount, unit) = value.split_at(split);
+        let seconds = match unit {
+            "s" => Some(1),
+            "m" => Some(60),
+            "h" => Some(3600),
+            "d" => Some(86400),
+            "w" => Some(7 * 86400),
+            _ => None,
+        };
+        if let Some(seconds) = seconds {
+            let amount: i64 = amount.parse().ok()?;
+            return amount.checked_mul(seconds).map(|ago| now - ago);
+        }
+    }
+
+    let date_time = DATE_TIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .map(|date| date.and_hms(0, 0, 0))
+        })?;
+    Local
+        .from_local_datetime(&date_time)
+        .earliest()
+        .map(|time| time.timestamp())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_time, TimeFilter};
+
+    use crate::app;
+    use chrono::{Local, TimeZone};
+
+    #[test]
+    fn test_parse_time_duration() {
+        assert_eq!(Some(1000 - 30), parse_time("30s", 1000));
+        assert_eq!(Some(10_000 - 2 * 3600), parse_time("2h", 10_000));
+        assert_eq!(Some(-3 * 86400), parse_time("3d", 0));
+        assert_eq!(Some(-7 * 86400), parse_time("1w", 0));
+        assert_eq!(None, parse_time("2y", 0));
+        assert_eq!(None, parse_time("h", 0));
+    }
+
+    #[test]
+    fn test_parse_time_date() {
+        let expected = Local.ymd(2021, 3, 7).and_hms(13, 45, 0).timestamp();
+        assert_eq!(Some(expected), parse_time("2021-03-07 13:45", 0));
+        assert_eq!(Some(expected + 30), parse_time("2021-03-07T13:45:30", 0));
+
+        let midnight = Local.ymd(2021, 3, 7).and_hms(0, 0, 0).timestamp();
+        assert_eq!(Some(midnight), parse_time("2021-03-07", 0));
+        assert_eq!(None, parse_time("2021-13-07", 0));
+    }
+
+    #[test]
+    fn test_configure_from_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let filter = TimeFilter::configure_from(&matches).unwrap();
+        assert_eq!(TimeFilter::default(), filter);
+        assert!(!filter.is_active());
+    }
+
+    #[test]
+    fn test_configure_from_bounds() {
+        let argv = vec![
+            "lsd",
+            "--newer-than",
+            "2021-03-07",
+            "--older-than",
+            "2021-03-08",
+        ];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let filter = TimeFilter::configure_from(&matches).unwrap();
+
+        let day = Local.ymd(2021, 3, 7).and_hms(12, 0, 0).timestamp();
+        assert!(filter.matches(day));
+        assert!(!filter.matches(day - 86400));
+        assert!(!filter.matches(day + 86400));
+    }
+
+    #[test]
+    fn test_configure_from_invalid() {
+        let argv = vec!["lsd", "--newer-than", "yesterday"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(TimeFilter::configure_from(&matches).is_err());
+    }
+}
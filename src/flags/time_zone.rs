@@ -0,0 +1,107 @@
+//! This module defines the [TimeZoneFlag]. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use serde::Deserialize;
+
+/// The flag showing in which time zone to display the dates.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimeZoneFlag {
+    /// The variant of the local time zone, the one of the `TZ` environment variable if set.
+    Local,
+    /// The variant of the coordinated universal time.
+    Utc,
+}
+
+impl TimeZoneFlag {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "local" => Some(Self::Local),
+            "utc" => Some(Self::Utc),
+            _ => {
+                panic!(
+                    "Time zone can only be one of local or utc, but got {}.",
+                    value
+                );
+            }
+        }
+    }
+}
+
+impl Configurable<Self> for TimeZoneFlag {
+    /// Get a potential `TimeZoneFlag` variant from [ArgMatches].
+    ///
+    /// If the "time-zone" argument is passed, this returns the variant corresponding to its last
+    /// parameter in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        Self::from_str(matches.values_of("time-zone")?.next_back()?)
+    }
+
+    /// Get a potential `TimeZoneFlag` variant from a [Config].
+    ///
+    /// If the `Config::time_zone` has value, this returns it in a [Some]. Otherwise this returns
+    /// [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        config.time_zone
+    }
+}
+
+/// The default value for `TimeZoneFlag` is [TimeZoneFlag::Local].
+impl Default for TimeZoneFlag {
+    fn default() -> Self {
+        Self::Local
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TimeZoneFlag;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, TimeZoneFlag::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_utc() {
+        let argv = vec!["lsd", "--time-zone", "utc"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(TimeZoneFlag::Utc),
+            TimeZoneFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_time_zone_multi() {
+        let argv = vec!["lsd", "--time-zone", "utc", "--time-zone", "local"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(TimeZoneFlag::Local),
+            TimeZoneFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, TimeZoneFlag::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_utc() {
+        let mut c = Config::with_none();
+        c.time_zone = Some(TimeZoneFlag::Utc);
+        assert_eq!(Some(TimeZoneFlag::Utc), TimeZoneFlag::from_config(&c));
+    }
+}
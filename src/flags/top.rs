@@ -0,0 +1,74 @@
+//! This module defines the [Top] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag holding the number of the largest files of the whole trees to list, in the place of
+/// the trees.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Top(pub Option<usize>);
+
+impl Configurable<Self> for Top {
+    /// Get a potential `Top` value from [ArgMatches].
+    ///
+    /// If the "top" argument is passed, this returns a `Top` with its last parameter in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        matches
+            .values_of("top")?
+            .next_back()
+            .and_then(|count| count.parse().ok())
+            .map(|count| Self(Some(count)))
+    }
+
+    /// `Top` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Top;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Top::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_count() {
+        let argv = vec!["lsd", "--top", "20"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Top(Some(20))), Top::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_zero() {
+        let argv = vec!["lsd", "--top", "0"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--top", "many"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Top::from_config(&Config::with_none()));
+    }
+}
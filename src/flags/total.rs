@@ -0,0 +1,62 @@
+//! This module defines the [Total] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to print the `total` allocated disk space above the listing of each
+/// directory.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Total(pub bool);
+
+impl Configurable<Self> for Total {
+    /// Get a potential `Total` value from [ArgMatches].
+    ///
+    /// If the "total" argument is passed, this returns a `Total` with value `true` in a [Some].
+    /// Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("total") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `Total` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Total;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Total::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--total"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Total(true)), Total::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Total::from_config(&Config::with_none()));
+    }
+}
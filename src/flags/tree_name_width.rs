@@ -0,0 +1,77 @@
+//! This module defines the [TreeNameWidth] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag holding the maximum width of the tree names, beyond which they wrap onto continuation
+/// lines.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct TreeNameWidth(pub Option<usize>);
+
+impl Configurable<Self> for TreeNameWidth {
+    /// Get a potential `TreeNameWidth` value from [ArgMatches].
+    ///
+    /// If the "tree-name-width" argument is passed, this returns a `TreeNameWidth` with its last
+    /// parameter in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        matches
+            .values_of("tree-name-width")?
+            .next_back()
+            .and_then(|width| width.parse().ok())
+            .map(|width| Self(Some(width)))
+    }
+
+    /// `TreeNameWidth` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TreeNameWidth;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, TreeNameWidth::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_width() {
+        let argv = vec!["lsd", "--tree-name-width", "40"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(TreeNameWidth(Some(40))),
+            TreeNameWidth::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_zero() {
+        let argv = vec!["lsd", "--tree-name-width", "0"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--tree-name-width", "many"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, TreeNameWidth::from_config(&Config::with_none()));
+    }
+}
@@ -0,0 +1,120 @@
+//! This module defines the [TypeFilter] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+use crate::meta::FileType;
+
+use clap::ArgMatches;
+
+/// The flag showing which types of entries to list.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum TypeFilter {
+    /// The variant listing all the entries.
+    All,
+    /// The variant listing the directories only.
+    Dirs,
+    /// The variant listing the entries other than the directories only.
+    Files,
+}
+
+impl TypeFilter {
+    /// Whether an entry of the `file_type` is listed. The symlinks are classified by their target,
+    /// unless `no_symlink`, in which case they are neither directories nor files.
+    pub fn keeps(&self, file_type: FileType, no_symlink: bool) -> bool {
+        if let (FileType::SymLink { .. }, true) = (file_type, no_symlink) {
+            return *self == Self::All;
+        }
+
+        match self {
+            Self::All => true,
+            Self::Dirs => file_type.is_dirlike(),
+            Self::Files => !file_type.is_dirlike(),
+        }
+    }
+}
+
+impl Configurable<Self> for TypeFilter {
+    /// Get a potential `TypeFilter` variant from [ArgMatches].
+    ///
+    /// If the "only-dirs" or the "only-files" argument is passed, this returns
+    /// [TypeFilter::Dirs] or [TypeFilter::Files] in a [Some], the last one winning as they
+    /// override each other. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("only-dirs") {
+            Some(Self::Dirs)
+        } else if matches.is_present("only-files") {
+            Some(Self::Files)
+        } else {
+            None
+        }
+    }
+
+    /// `TypeFilter` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+/// The default value for `TypeFilter` is [TypeFilter::All].
+impl Default for TypeFilter {
+    fn default() -> Self {
+        Self::All
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TypeFilter;
+
+    use crate::app;
+    use crate::flags::Configurable;
+    use crate::meta::FileType;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, TypeFilter::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_only_dirs() {
+        let argv = vec!["lsd", "--only-dirs"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(TypeFilter::Dirs),
+            TypeFilter::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_last_wins() {
+        let argv = vec!["lsd", "--only-dirs", "--only-files"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(TypeFilter::Files),
+            TypeFilter::from_arg_matches(&matches)
+        );
+
+        let argv = vec!["lsd", "--only-files", "--only-dirs"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(TypeFilter::Dirs),
+            TypeFilter::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_keeps_symlinks() {
+        let link = FileType::SymLink { is_dir: true };
+        assert!(TypeFilter::Dirs.keeps(link, false));
+        assert!(!TypeFilter::Files.keeps(link, false));
+        assert!(!TypeFilter::Dirs.keeps(link, true));
+        assert!(!TypeFilter::Files.keeps(link, true));
+        assert!(TypeFilter::All.keeps(link, true));
+    }
+}
@@ -0,0 +1,64 @@
+//! This module defines the [TypeHistogram] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to print the counts of the listed file types after the listing.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct TypeHistogram(pub bool);
+
+impl Configurable<Self> for TypeHistogram {
+    /// Get a potential `TypeHistogram` value from [ArgMatches].
+    ///
+    /// If the "type-histogram" argument is passed, this returns a `TypeHistogram` with value
+    /// `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("type-histogram") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `TypeHistogram` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TypeHistogram;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, TypeHistogram::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--type-histogram"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(TypeHistogram(true)),
+            TypeHistogram::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, TypeHistogram::from_config(&Config::with_none()));
+    }
+}
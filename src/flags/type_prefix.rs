@@ -0,0 +1,64 @@
+//! This module defines the [TypePrefix] flag. To set it up from [ArgMatches], a [Config] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+/// The flag showing whether to prefix the names with the letter of their file type.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct TypePrefix(pub bool);
+
+impl Configurable<Self> for TypePrefix {
+    /// Get a potential `TypePrefix` value from [ArgMatches].
+    ///
+    /// If the "type-prefix" argument is passed, this returns a `TypePrefix` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("type-prefix") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `TypePrefix` can not be configured by a [Config].
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TypePrefix;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, TypePrefix::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--type-prefix"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(TypePrefix(true)),
+            TypePrefix::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, TypePrefix::from_config(&Config::with_none()));
+    }
+}
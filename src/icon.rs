@@ -1,3 +1,5 @@
+use crate::flags::icons::{IconDisabled, IconOverrides};
+use crate::flags::BundleExtensions;
 use crate::meta::{FileType, Name};
 use std::collections::HashMap;
 
@@ -7,6 +9,10 @@ pub struct Icons {
     icons_by_extension: HashMap<&'static str, &'static str>,
     default_folder_icon: &'static str,
     default_file_icon: &'static str,
+    default_bundle_icon: &'static str,
+    bundle_extensions: BundleExtensions,
+    disabled: IconDisabled,
+    overrides: IconOverrides,
     icon_separator: String,
 }
 
@@ -17,6 +23,38 @@ pub enum Theme {
     Unicode,
 }
 
+/// The terminals known to render their own fixed fonts, without the Nerd Font glyphs.
+const PLAIN_TERMS: &[&str] = &["dumb", "linux", "vt100", "vt102", "vt220", "cons25"];
+
+/// Guess from the environment, read through `var`, whether the terminal lacks the Nerd Font
+/// glyphs of the fancy icons, returning the hint to print then.
+///
+/// This is best effort: the consoles and dumb terminals of `TERM` have no Nerd Font, and the
+/// glyphs can not be rendered under a locale which is set but is not UTF-8.
+pub fn font_hint<F>(var: F) -> Option<&'static str>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let plain_term = var("TERM").is_some_and(|term| PLAIN_TERMS.contains(&term.as_str()));
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| var(name))
+        .find(|value| !value.is_empty());
+    let plain_locale = locale.is_some_and(|locale| {
+        let locale = locale.to_lowercase();
+        !locale.contains("utf-8") && !locale.contains("utf8")
+    });
+
+    if plain_term || plain_locale {
+        Some(
+            "the icons need a Nerd Font, which this terminal does not seem to have. \
+             Install one, or pass `--icon never` or `--icon-theme unicode`.",
+        )
+    } else {
+        None
+    }
+}
+
 // In order to add a new icon, write the unicode value like "\ue5fb" then
 // run the command below in vim:
 //
@@ -24,22 +62,31 @@ pub enum Theme {
 impl Icons {
     pub fn new(theme: Theme, icon_separator: String) -> Self {
         let display_icons = theme == Theme::Fancy || theme == Theme::Unicode;
-        let (icons_by_name, icons_by_extension, default_file_icon, default_folder_icon) =
-            if theme == Theme::Fancy {
-                (
-                    Self::get_default_icons_by_name(),
-                    Self::get_default_icons_by_extension(),
-                    "\u{f016}", // 
-                    "\u{f115}", // 
-                )
-            } else {
-                (
-                    HashMap::new(),
-                    HashMap::new(),
-                    "\u{1f5cb}", // 🗋
-                    "\u{1f5c1}", // 🗁
-                )
-            };
+        let (
+            icons_by_name,
+            icons_by_extension,
+            default_file_icon,
+            default_folder_icon,
+            default_bundle_icon,
+        ) = if theme == Theme::Fancy {
+            (
+                Self::get_default_icons_by_name(),
+                Self::get_default_icons_by_extension(),
+                "\u{f016}", // 
+                "\u{f115}", // 
+                "\u{f487}", // 
+            )
+        } else {
+            // The variation selector U+FE0F forces the emoji presentation, so these icons
+            // are rendered with a consistent width of two cells.
+            (
+                HashMap::new(),
+                HashMap::new(),
+                "\u{1f5cb}\u{fe0f}", // 🗋️
+                "\u{1f5c1}\u{fe0f}", // 🗁️
+                "\u{1f4e6}",         // 📦
+            )
+        };
 
         Self {
             display_icons,
@@ -47,10 +94,33 @@ impl Icons {
             icons_by_extension,
             default_file_icon,
             default_folder_icon,
+            default_bundle_icon,
+            bundle_extensions: BundleExtensions::default(),
+            disabled: IconDisabled::default(),
+            overrides: IconOverrides::default(),
             icon_separator,
         }
     }
 
+    /// Show the directories with one of the `bundle_extensions` with the bundle icon.
+    pub fn with_bundle_extensions(mut self, bundle_extensions: BundleExtensions) -> Self {
+        self.bundle_extensions = bundle_extensions;
+        self
+    }
+
+    /// Show the entries of the `disabled` file types without an icon.
+    pub fn with_disabled(mut self, disabled: IconDisabled) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Show the entries with one of the file names or extensions of `overrides` with its icon,
+    /// rather than with the default one.
+    pub fn with_overrides(mut self, overrides: IconOverrides) -> Self {
+        self.overrides = overrides;
+        self
+    }
+
     pub fn get(&self, name: &Name) -> String {
         if !self.display_icons {
             return String::new();
@@ -58,8 +128,13 @@ impl Icons {
 
         // Check file types
         let file_type: FileType = name.file_type();
+        if self.disabled.contains(file_type) {
+            return String::new();
+        }
 
-        let icon = if let FileType::Directory { .. } = file_type {
+        let icon = if self.bundle_extensions.is_bundle(name) {
+            self.default_bundle_icon
+        } else if let FileType::Directory { .. } = file_type {
             self.default_folder_icon
         } else if let FileType::SymLink { is_dir: true } = file_type {
             "\u{f482}" // ""
@@ -75,15 +150,19 @@ impl Icons {
             "\u{fc29}" // "ﰩ"
         } else if let FileType::Special = file_type {
             "\u{f2dc}" // ""
-        } else if let Some(icon) = self
-            .icons_by_name
-            .get(name.file_name().to_lowercase().as_str())
-        {
+        } else if let Some(icon) = Self::lookup(
+            &self.overrides.name,
+            &self.icons_by_name,
+            &name.file_name().to_lowercase(),
+        ) {
             // Use the known names.
             icon
         } else if let Some(icon) = name.extension().and_then(|extension| {
-            self.icons_by_extension
-                .get(extension.to_lowercase().as_str())
+            Self::lookup(
+                &self.overrides.extension,
+                &self.icons_by_extension,
+                &extension.to_lowercase(),
+            )
         }) {
             // Use the known extensions.
             icon
@@ -95,6 +174,18 @@ impl Icons {
         format!("{}{}", icon, self.icon_separator)
     }
 
+    /// Get the icon of the lower-case `key`, from the `overrides` of the user first.
+    fn lookup<'a>(
+        overrides: &'a HashMap<String, String>,
+        defaults: &HashMap<&'static str, &'static str>,
+        key: &str,
+    ) -> Option<&'a str> {
+        overrides
+            .get(key)
+            .map(String::as_str)
+            .or_else(|| defaults.get(key).copied())
+    }
+
     fn get_default_icons_by_name() -> HashMap<&'static str, &'static str> {
         let mut m = HashMap::new();
 
@@ -345,17 +436,48 @@ impl Icons {
 
 #[cfg(test)]
 mod test {
-    use super::{Icons, Theme};
+    use super::{font_hint, Icons, Theme};
+    use crate::flags::BundleExtensions;
     use crate::meta::Meta;
-    use std::fs::File;
+    use crate::meta::UsersCache;
+    use std::fs::{create_dir, File};
     use tempfile::tempdir;
 
+    fn env<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |name| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| value.to_string())
+        }
+    }
+
+    #[test]
+    fn test_font_hint() {
+        assert!(font_hint(env(&[("TERM", "linux")])).is_some());
+        assert!(font_hint(env(&[("TERM", "xterm-kitty"), ("LANG", "C")])).is_some());
+        assert!(font_hint(env(&[
+            ("TERM", "xterm-256color"),
+            ("LC_ALL", "POSIX"),
+            ("LANG", "en_US.UTF-8")
+        ]))
+        .is_some());
+
+        assert!(font_hint(env(&[])).is_none());
+        assert!(font_hint(env(&[("TERM", "xterm-256color"), ("LANG", "en_US.UTF-8")])).is_none());
+        assert!(font_hint(env(&[
+            ("TERM", "wezterm"),
+            ("LC_ALL", ""),
+            ("LANG", "C.utf8")
+        ]))
+        .is_none());
+    }
+
     #[test]
     fn get_no_icon() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path().join("file.txt");
         File::create(&file_path).expect("failed to create file");
-        let meta = Meta::from_path(&file_path, false).unwrap();
+        let meta = Meta::from_path(&file_path, false, &UsersCache::new()).unwrap();
 
         let icon = Icons::new(Theme::NoIcon, " ".to_string());
         let icon = icon.get(&meta.name);
@@ -368,7 +490,7 @@ mod test {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path().join("file");
         File::create(&file_path).expect("failed to create file");
-        let meta = Meta::from_path(&file_path, false).unwrap();
+        let meta = Meta::from_path(&file_path, false, &UsersCache::new()).unwrap();
 
         let icon = Icons::new(Theme::Fancy, " ".to_string());
         let icon_str = icon.get(&meta.name);
@@ -381,19 +503,22 @@ mod test {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path().join("file");
         File::create(&file_path).expect("failed to create file");
-        let meta = Meta::from_path(&file_path, false).unwrap();
+        let meta = Meta::from_path(&file_path, false, &UsersCache::new()).unwrap();
 
         let icon = Icons::new(Theme::Unicode, " ".to_string());
         let icon_str = icon.get(&meta.name);
 
-        assert_eq!(icon_str, format!("{}{}", "\u{1f5cb}", icon.icon_separator));
+        assert_eq!(
+            icon_str,
+            format!("{}{}", "\u{1f5cb}\u{fe0f}", icon.icon_separator)
+        );
     }
 
     #[test]
     fn get_directory_icon() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path();
-        let meta = Meta::from_path(&file_path.to_path_buf(), false).unwrap();
+        let meta = Meta::from_path(&file_path.to_path_buf(), false, &UsersCache::new()).unwrap();
 
         let icon = Icons::new(Theme::Fancy, " ".to_string());
         let icon_str = icon.get(&meta.name);
@@ -401,23 +526,46 @@ mod test {
         assert_eq!(icon_str, format!("{}{}", "\u{f115}", icon.icon_separator)); // 
     }
 
+    #[test]
+    fn get_bundle_icon() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let bundle_path = tmp_dir.path().join("Safari.app");
+        create_dir(&bundle_path).expect("failed to create dir");
+        let meta = Meta::from_path(&bundle_path, false, &UsersCache::new()).unwrap();
+
+        let icon = Icons::new(Theme::Fancy, " ".to_string());
+        assert_eq!(
+            icon.get(&meta.name),
+            format!("{}{}", "\u{f115}", icon.icon_separator)
+        );
+
+        let icon = icon.with_bundle_extensions(BundleExtensions(vec!["app".to_string()]));
+        assert_eq!(
+            icon.get(&meta.name),
+            format!("{}{}", "\u{f487}", icon.icon_separator)
+        );
+    }
+
     #[test]
     fn get_directory_icon_unicode() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path();
-        let meta = Meta::from_path(&file_path.to_path_buf(), false).unwrap();
+        let meta = Meta::from_path(&file_path.to_path_buf(), false, &UsersCache::new()).unwrap();
 
         let icon = Icons::new(Theme::Unicode, " ".to_string());
         let icon_str = icon.get(&meta.name);
 
-        assert_eq!(icon_str, format!("{}{}", "\u{1f5c1}", icon.icon_separator));
+        assert_eq!(
+            icon_str,
+            format!("{}{}", "\u{1f5c1}\u{fe0f}", icon.icon_separator)
+        );
     }
 
     #[test]
     fn get_directory_icon_with_ext() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
         let file_path = tmp_dir.path();
-        let meta = Meta::from_path(&file_path.to_path_buf(), false).unwrap();
+        let meta = Meta::from_path(&file_path.to_path_buf(), false, &UsersCache::new()).unwrap();
 
         let icon = Icons::new(Theme::Fancy, " ".to_string());
         let icon_str = icon.get(&meta.name);
@@ -432,7 +580,7 @@ mod test {
         for (file_name, file_icon) in &Icons::get_default_icons_by_name() {
             let file_path = tmp_dir.path().join(file_name);
             File::create(&file_path).expect("failed to create file");
-            let meta = Meta::from_path(&file_path, false).unwrap();
+            let meta = Meta::from_path(&file_path, false, &UsersCache::new()).unwrap();
 
             let icon = Icons::new(Theme::Fancy, " ".to_string());
             let icon_str = icon.get(&meta.name);
@@ -441,6 +589,48 @@ mod test {
         }
     }
 
+    #[test]
+    fn get_overridden_icon() {
+        use crate::config_file::{self, Config};
+        use crate::flags::icons::IconOverrides;
+        use crate::flags::Configurable;
+        use std::collections::HashMap;
+
+        let map = |entries: &[(&str, &str)]| -> HashMap<String, String> {
+            entries
+                .iter()
+                .map(|(key, icon)| (key.to_string(), icon.to_string()))
+                .collect()
+        };
+        let mut config = Config::with_none();
+        config.icons = Some(config_file::Icons {
+            when: None,
+            theme: None,
+            separator: None,
+            spacing: None,
+            disabled: None,
+            name: Some(map(&[("readme", "\\uf48a")])),
+            extension: Some(map(&[("zig", "\u{e6a9}"), ("rs", "bad")])),
+        });
+        let icon = Icons::new(Theme::Fancy, " ".to_string()).with_overrides(
+            IconOverrides::configure_from(&clap::ArgMatches::new(), &config),
+        );
+
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        for (file_name, file_icon) in &[
+            ("main.zig", "\u{e6a9}"),
+            ("README", "\u{f48a}"),
+            // the invalid overrides keep the default icon
+            ("main.rs", "\u{e7a8}"),
+        ] {
+            let file_path = tmp_dir.path().join(file_name);
+            File::create(&file_path).expect("failed to create file");
+            let meta = Meta::from_path(&file_path, false, &UsersCache::new()).unwrap();
+
+            assert_eq!(format!("{} ", file_icon), icon.get(&meta.name));
+        }
+    }
+
     #[test]
     fn get_icon_by_extension() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
@@ -448,7 +638,7 @@ mod test {
         for (ext, file_icon) in &Icons::get_default_icons_by_extension() {
             let file_path = tmp_dir.path().join(format!("file.{}", ext));
             File::create(&file_path).expect("failed to create file");
-            let meta = Meta::from_path(&file_path, false).unwrap();
+            let meta = Meta::from_path(&file_path, false, &UsersCache::new()).unwrap();
 
             let icon = Icons::new(Theme::Fancy, " ".to_string());
             let icon_str = icon.get(&meta.name);
@@ -456,4 +646,29 @@ mod test {
             assert_eq!(icon_str, format!("{}{}", file_icon, icon.icon_separator));
         }
     }
+
+    #[test]
+    #[cfg(unix)]
+    fn get_disabled_icon() {
+        use crate::flags::icons::{IconDisabled, IconFileType};
+        use std::os::unix::net::UnixListener;
+
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let socket_path = tmp_dir.path().join("socket");
+        let _listener = UnixListener::bind(&socket_path).expect("failed to create socket");
+        let file_path = tmp_dir.path().join("file");
+        File::create(&file_path).expect("failed to create file");
+
+        let icon = Icons::new(Theme::Fancy, " ".to_string())
+            .with_disabled(IconDisabled(vec![IconFileType::Socket]));
+
+        let socket = Meta::from_path(&socket_path, false, &UsersCache::new()).unwrap();
+        assert_eq!("", icon.get(&socket.name));
+
+        let file = Meta::from_path(&file_path, false, &UsersCache::new()).unwrap();
+        assert_eq!(
+            format!("{}{}", "\u{f016}", icon.icon_separator),
+            icon.get(&file.name)
+        );
+    }
 }
@@ -36,6 +36,7 @@ mod display;
 mod flags;
 mod icon;
 mod meta;
+mod output;
 mod sort;
 
 use crate::config_file::Config;
@@ -96,7 +97,7 @@ fn main() {
     // for example:
     // * to all files matched
     // '*' remain as '*'
-    let inputs = matches
+    let inputs: Vec<PathBuf> = matches
         .values_of("FILE")
         .expect("failed to retrieve cli value")
         .map(PathBuf::from)
@@ -108,6 +109,10 @@ fn main() {
         Config::default()
     };
     let flags = Flags::configure_from(&matches, &config).unwrap_or_else(|err| err.exit());
+    let inputs = inputs
+        .into_iter()
+        .map(|input| flags.expand_paths.expand(input))
+        .collect();
     let core = Core::new(flags);
 
     core.run(inputs);
@@ -0,0 +1,125 @@
+use crate::color::{ColoredString, Colors, Elem};
+use ansi_term::ANSIStrings;
+use std::path::Path;
+
+/// What the current user is actually allowed to do with a file, taking the ownership and the
+/// access control lists into account, unlike the raw permission bits.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Access {
+    read: bool,
+    write: bool,
+    execute: bool,
+}
+
+impl From<&Path> for Access {
+    #[cfg(unix)]
+    fn from(path: &Path) -> Self {
+        use std::ffi::CString;
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = match CString::new(path.as_os_str().as_bytes()) {
+            Ok(path) => path,
+            Err(_) => return Self::none(),
+        };
+        let check = |mode| unsafe { libc::access(path.as_ptr(), mode) == 0 };
+
+        Self {
+            read: check(libc::R_OK),
+            write: check(libc::W_OK),
+            execute: check(libc::X_OK),
+        }
+    }
+
+    #[cfg(windows)]
+    fn from(path: &Path) -> Self {
+        let metadata = path.metadata();
+        Self {
+            read: metadata.is_ok(),
+            write: metadata.map_or(false, |m| !m.permissions().readonly()),
+            execute: false,
+        }
+    }
+}
+
+impl Access {
+    /// No access at all, for the entries without a path on the disk.
+    pub fn none() -> Self {
+        Self {
+            read: false,
+            write: false,
+            execute: false,
+        }
+    }
+
+    pub fn render(&self, colors: &Colors) -> ColoredString<'static> {
+        let bit = |bit, chr: &'static str, elem: &Elem| {
+            if bit {
+                colors.colorize(String::from(chr), elem)
+            } else {
+                colors.colorize(String::from("-"), &Elem::NoAccess)
+            }
+        };
+
+        let strings: &[ColoredString] = &[
+            bit(self.read, "r", &Elem::Read),
+            bit(self.write, "w", &Elem::Write),
+            bit(self.execute, "x", &Elem::Exec),
+        ];
+
+        ColoredString::from(ANSIStrings(strings).to_string())
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod test {
+    use super::Access;
+    use crate::color::{Colors, Theme};
+    use std::fs::{self, File};
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::tempdir;
+
+    fn render(access: Access) -> String {
+        access.render(&Colors::new(Theme::NoColor)).to_string()
+    }
+
+    #[test]
+    fn test_access_owned_file() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("file.txt");
+        File::create(&file_path).expect("failed to create file");
+
+        fs::set_permissions(&file_path, fs::Permissions::from_mode(0o644))
+            .expect("failed to set permissions");
+        assert_eq!("rw-", render(Access::from(file_path.as_path())));
+
+        fs::set_permissions(&file_path, fs::Permissions::from_mode(0o755))
+            .expect("failed to set permissions");
+        assert_eq!("rwx", render(Access::from(file_path.as_path())));
+    }
+
+    #[test]
+    fn test_access_inaccessible_file() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("file.txt");
+        File::create(&file_path).expect("failed to create file");
+        fs::set_permissions(&file_path, fs::Permissions::from_mode(0o000))
+            .expect("failed to set permissions");
+
+        // the superuser can read and write anything, whatever the permission bits say
+        let expected = if unsafe { libc::geteuid() } == 0 {
+            "rw-"
+        } else {
+            "---"
+        };
+        assert_eq!(expected, render(Access::from(file_path.as_path())));
+    }
+
+    #[test]
+    fn test_access_missing_file() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("missing");
+
+        assert_eq!("---", render(Access::from(file_path.as_path())));
+    }
+}
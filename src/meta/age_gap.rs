@@ -0,0 +1,94 @@
+use crate::color::{ColoredString, Colors, Elem};
+use std::fs::Metadata;
+use std::time::SystemTime;
+
+/// The time between the creation and the last modification of a file.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct AgeGap {
+    seconds: Option<i64>,
+}
+
+impl<'a> From<&'a Metadata> for AgeGap {
+    fn from(meta: &'a Metadata) -> Self {
+        match (meta.created(), meta.modified()) {
+            (Ok(created), Ok(modified)) => Self::new(created, modified),
+            _ => Self::none(),
+        }
+    }
+}
+
+impl AgeGap {
+    pub fn new(created: SystemTime, modified: SystemTime) -> Self {
+        let seconds = match modified.duration_since(created) {
+            Ok(gap) => gap.as_secs() as i64,
+            // the modification time can be set before the creation, e.g. by `cp -p`
+            Err(err) => -(err.duration().as_secs() as i64),
+        };
+
+        Self {
+            seconds: Some(seconds),
+        }
+    }
+
+    pub fn none() -> Self {
+        Self { seconds: None }
+    }
+
+    pub fn render(&self, colors: &Colors) -> ColoredString<'static> {
+        let seconds = match self.seconds {
+            Some(seconds) => seconds,
+            None => return colors.colorize(String::from("-"), &Elem::NonFile),
+        };
+
+        let sign = if seconds < 0 { '-' } else { '+' };
+        let gap = seconds.unsigned_abs();
+        let gap = match gap {
+            0..=59 => format!("{}s", gap),
+            60..=3599 => format!("{}m", gap / 60),
+            3600..=86399 => format!("{}h", gap / 3600),
+            _ => format!("{}d", gap / 86400),
+        };
+
+        colors.colorize(format!("{}{}", sign, gap), &Elem::AgeGap)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::AgeGap;
+    use crate::color::{Colors, Theme};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn render(created: u64, modified: u64) -> String {
+        let created = UNIX_EPOCH + Duration::from_secs(created);
+        let modified = UNIX_EPOCH + Duration::from_secs(modified);
+
+        AgeGap::new(created, modified)
+            .render(&Colors::new(Theme::NoColor))
+            .to_string()
+    }
+
+    #[test]
+    fn test_render_age_gap() {
+        assert_eq!("+0s", render(1000, 1000));
+        assert_eq!("+42s", render(1000, 1042));
+        assert_eq!("+5m", render(1000, 1000 + 5 * 60 + 10));
+        assert_eq!("+23h", render(1000, 1000 + 23 * 3600));
+        assert_eq!("+3d", render(1000, 1000 + 3 * 86400 + 7200));
+    }
+
+    #[test]
+    fn test_render_age_gap_modified_before_creation() {
+        assert_eq!("-2h", render(1000 + 2 * 3600, 1000));
+    }
+
+    #[test]
+    fn test_render_age_gap_without_birth_time() {
+        assert_eq!(
+            "-",
+            AgeGap::none()
+                .render(&Colors::new(Theme::NoColor))
+                .to_string()
+        );
+    }
+}
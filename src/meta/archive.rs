@@ -0,0 +1,667 @@
+use super::{
+    AgeGap, Date, Device, FileCount, FileType, FreeInodes, GitDirSummary, GitStatus, INode,
+    Indicator, Links, Meta, Name, Owner,
+};
+use super::{Permissions, Size, SymLink, TargetState};
+use crate::flags::{Display, Flags, Layout};
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, Error, ErrorKind, Read};
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, UNIX_EPOCH};
+
+const BLOCK_SIZE: usize = 512;
+
+/// An entry read from the headers of a tar archive.
+#[derive(Clone, Debug, Default)]
+struct Entry {
+    path: PathBuf,
+    mode: u32,
+    uid: u64,
+    gid: u64,
+    user: String,
+    group: String,
+    size: u64,
+    mtime: u64,
+    kind: u8,
+    link: String,
+}
+
+/// Check if the path looks like a tar archive, compressed with gzip or not.
+pub fn is_archive(path: &Path) -> bool {
+    let name = match path.file_name() {
+        Some(name) => name.to_string_lossy().to_lowercase(),
+        None => return false,
+    };
+
+    name.ends_with(".tar") || name.ends_with(".tar.gz") || name.ends_with(".tgz")
+}
+
+/// Read the entries of the tar archive at `path` into the content of `meta`, turning it into a
+/// directory. The archive is streamed, without extracting anything on the disk.
+///
+/// The compressed archives are decoded by the `gzip` command, which has to be installed.
+pub fn read_into(meta: &mut Meta, depth: usize, flags: &Flags) -> io::Result<()> {
+    let entries = if is_gzip(&meta.path) {
+        let mut child = Command::new("gzip")
+            .arg("-dc")
+            .arg(&meta.path)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|err| match err.kind() {
+                ErrorKind::NotFound => Error::new(
+                    ErrorKind::NotFound,
+                    "gzip not found, it is needed to read the compressed archives",
+                ),
+                _ => err,
+            })?;
+        let stdout = child
+            .stdout
+            .take()
+            .expect("failed to capture the gzip output");
+        let entries = read_entries(stdout);
+        if !child.wait()?.success() && entries.is_ok() {
+            return Err(Error::new(ErrorKind::InvalidData, "invalid gzip archive"));
+        }
+        entries?
+    } else {
+        read_entries(File::open(&meta.path)?)?
+    };
+
+    let mut content = build_tree(&meta.path, entries);
+    if flags.total_size.0 {
+        content.iter_mut().for_each(total_size);
+    }
+    prune(&mut content, depth, flags, &meta.path);
+
+    meta.file_type = FileType::Directory { uid: false };
+    meta.content = if depth == 0 { None } else { Some(content) };
+
+    Ok(())
+}
+
+fn is_gzip(path: &Path) -> bool {
+    let name = path.to_string_lossy().to_lowercase();
+    name.ends_with(".gz") || name.ends_with(".tgz")
+}
+
+fn read_entries<R: Read>(mut reader: R) -> io::Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+    let mut long_name: Option<String> = None;
+    let mut long_link: Option<String> = None;
+    let mut pax: Vec<(String, String)> = Vec::new();
+    let mut header = [0u8; BLOCK_SIZE];
+
+    loop {
+        if !read_block(&mut reader, &mut header)? || header.iter().all(|&b| b == 0) {
+            break;
+        }
+
+        if !checksum_matches(&header) {
+            return Err(Error::new(ErrorKind::InvalidData, "invalid tar header"));
+        }
+
+        let mut entry = parse_header(&header)?;
+        match entry.kind {
+            // GNU long names and long link targets, applied to the next header
+            b'L' | b'K' => {
+                let data = read_data(&mut reader, entry.size)?;
+                let value = field_string(&data);
+                if entry.kind == b'L' {
+                    long_name = Some(value);
+                } else {
+                    long_link = Some(value);
+                }
+                continue;
+            }
+            // PAX extended headers, applied to the next header
+            b'x' => {
+                pax = parse_pax(&read_data(&mut reader, entry.size)?);
+                continue;
+            }
+            // PAX global headers
+            b'g' => {
+                skip_data(&mut reader, entry.size)?;
+                continue;
+            }
+            _ => (),
+        }
+
+        if let Some(name) = long_name.take() {
+            entry.path = PathBuf::from(name);
+        }
+        if let Some(link) = long_link.take() {
+            entry.link = link;
+        }
+        for (key, value) in pax.drain(..) {
+            match key.as_str() {
+                "path" => entry.path = PathBuf::from(value),
+                "linkpath" => entry.link = value,
+                "uname" => entry.user = value,
+                "gname" => entry.group = value,
+                "uid" => entry.uid = value.parse().unwrap_or(entry.uid),
+                "gid" => entry.gid = value.parse().unwrap_or(entry.gid),
+                "size" => entry.size = value.parse().unwrap_or(entry.size),
+                "mtime" => {
+                    let seconds = value.split('.').next().unwrap_or_default();
+                    entry.mtime = seconds.parse().unwrap_or(entry.mtime);
+                }
+                _ => (),
+            }
+        }
+
+        // only the regular files have their data stored in the archive
+        match entry.kind {
+            b'0' | 0 | b'7' => skip_data(&mut reader, entry.size)?,
+            _ => entry.size = 0,
+        }
+
+        entry.path = normalize(&entry.path);
+        if entry.path.as_os_str().is_empty() {
+            continue;
+        }
+
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+/// Read a whole block, returning `false` at the end of the stream.
+fn read_block<R: Read>(reader: &mut R, block: &mut [u8; BLOCK_SIZE]) -> io::Result<bool> {
+    let mut filled = 0;
+    while filled < BLOCK_SIZE {
+        match reader.read(&mut block[filled..]) {
+            Ok(0) if filled == 0 => return Ok(false),
+            Ok(0) => {
+                return Err(Error::new(
+                    ErrorKind::UnexpectedEof,
+                    "truncated tar archive",
+                ))
+            }
+            Ok(n) => filled += n,
+            Err(err) if err.kind() == ErrorKind::Interrupted => (),
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(true)
+}
+
+fn padded_size(size: u64) -> u64 {
+    size.div_ceil(BLOCK_SIZE as u64) * BLOCK_SIZE as u64
+}
+
+fn read_data<R: Read>(reader: &mut R, size: u64) -> io::Result<Vec<u8>> {
+    let mut data = Vec::new();
+    reader.take(padded_size(size)).read_to_end(&mut data)?;
+    if (data.len() as u64) < size {
+        return Err(Error::new(
+            ErrorKind::UnexpectedEof,
+            "truncated tar archive",
+        ));
+    }
+    data.truncate(size as usize);
+
+    Ok(data)
+}
+
+fn skip_data<R: Read>(reader: &mut R, size: u64) -> io::Result<()> {
+    let padded = padded_size(size);
+    if io::copy(&mut reader.take(padded), &mut io::sink())? < padded {
+        return Err(Error::new(
+            ErrorKind::UnexpectedEof,
+            "truncated tar archive",
+        ));
+    }
+
+    Ok(())
+}
+
+fn checksum_matches(header: &[u8; BLOCK_SIZE]) -> bool {
+    let expected = match parse_number(&header[148..156]) {
+        Some(sum) => sum,
+        None => return false,
+    };
+
+    // the checksum is computed with its own field filled with spaces
+    let sum: u64 = header
+        .iter()
+        .enumerate()
+        .map(|(i, &b)| if (148..156).contains(&i) { b' ' } else { b } as u64)
+        .sum();
+
+    sum == expected
+}
+
+fn parse_header(header: &[u8; BLOCK_SIZE]) -> io::Result<Entry> {
+    let number = |range: std::ops::Range<usize>| {
+        parse_number(&header[range])
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "invalid tar header"))
+    };
+
+    let mut name = field_string(&header[0..100]);
+    // the POSIX ustar format splits the long paths between a prefix and the name, whereas the
+    // GNU format stores other fields there
+    if &header[257..263] == b"ustar\0" {
+        let prefix = field_string(&header[345..500]);
+        if !prefix.is_empty() {
+            name = format!("{}/{}", prefix, name);
+        }
+    }
+
+    Ok(Entry {
+        path: PathBuf::from(name),
+        mode: number(100..108)? as u32,
+        uid: number(108..116)?,
+        gid: number(116..124)?,
+        size: number(124..136)?,
+        mtime: number(136..148)?,
+        kind: header[156],
+        link: field_string(&header[157..257]),
+        user: field_string(&header[265..297]),
+        group: field_string(&header[297..329]),
+    })
+}
+
+/// Parse a numeric field, stored either in octal or in base-256 for the big values.
+fn parse_number(field: &[u8]) -> Option<u64> {
+    if field.first().is_some_and(|b| b & 0x80 != 0) {
+        return Some(
+            field[1..]
+                .iter()
+                .fold((field[0] & 0x7f) as u64, |n, &b| (n << 8) | b as u64),
+        );
+    }
+
+    let text = String::from_utf8_lossy(field);
+    let text = text.trim_matches(|c: char| c == '\0' || c == ' ');
+    if text.is_empty() {
+        return Some(0);
+    }
+
+    u64::from_str_radix(text, 8).ok()
+}
+
+fn field_string(field: &[u8]) -> String {
+    let end = field.iter().position(|&b| b == 0).unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..end]).into_owned()
+}
+
+/// Parse the `<length> <key>=<value>\n` records of a PAX extended header.
+fn parse_pax(data: &[u8]) -> Vec<(String, String)> {
+    let mut records = Vec::new();
+    let mut rest = data;
+
+    while let Some(space) = rest.iter().position(|&b| b == b' ') {
+        let length: usize = match String::from_utf8_lossy(&rest[..space]).parse() {
+            Ok(length) if length > space && length <= rest.len() => length,
+            _ => break,
+        };
+
+        let record = String::from_utf8_lossy(&rest[space + 1..length]);
+        let record = record.strip_suffix('\n').unwrap_or(&record);
+        if let Some((key, value)) = record.split_once('=') {
+            records.push((key.to_string(), value.to_string()));
+        }
+
+        rest = &rest[length..];
+    }
+
+    records
+}
+
+/// Drop the `.`, `..` and `/` components of an archived path, so that every entry stays within
+/// the archive.
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| matches!(c, Component::Normal(_)))
+        .collect()
+}
+
+fn build_meta(archive: &Path, entry: &Entry, paths: &BTreeMap<PathBuf, Entry>) -> Meta {
+    let permissions = Permissions::from_mode(entry.mode);
+    let file_type = match entry.kind {
+        b'5' => FileType::Directory {
+            uid: permissions.setuid,
+        },
+        b'2' => FileType::SymLink {
+            is_dir: link_target(entry, paths).is_some_and(|t| t.kind == b'5'),
+        },
+        b'3' => FileType::CharDevice,
+        b'4' => FileType::BlockDevice,
+        b'6' => FileType::Pipe,
+        _ => FileType::File {
+            exec: permissions.is_executable(),
+            uid: permissions.setuid,
+        },
+    };
+
+    let symlink = if entry.kind == b'2' {
+        SymLink::new(
+            Some(entry.link.clone()),
+            link_target(entry, paths).is_some(),
+        )
+    } else {
+        SymLink::new(None, false)
+    };
+
+    let user = if entry.user.is_empty() {
+        entry.uid.to_string()
+    } else {
+        entry.user.clone()
+    };
+    let group = if entry.group.is_empty() {
+        entry.gid.to_string()
+    } else {
+        entry.group.clone()
+    };
+
+    let path = archive.join(&entry.path);
+    let mtime = UNIX_EPOCH + Duration::from_secs(entry.mtime);
+
+    Meta {
+        name: Name::new(&path, file_type),
+        path,
+        permissions,
+        date: Date::from(mtime),
+        owner: Owner::new(user, group).with_ids(entry.uid as u32, entry.gid as u32),
+        file_type,
+        size: Size::new(entry.size),
+        symlink,
+        indicator: Indicator::from(file_type),
+        inode: INode::none(),
+        links: Links::none(),
+        free_inodes: FreeInodes::none(),
+        age_gap: AgeGap::none(),
+        device: Device::none(),
+        mount_point: false,
+        file_count: FileCount::none(),
+        child_count: FileCount::none(),
+        archived: true,
+        content_state: TargetState::Valid,
+        git_status: GitStatus::none(),
+        git_summary: GitDirSummary::none(),
+        content: if entry.kind == b'5' {
+            Some(vec![])
+        } else {
+            None
+        },
+    }
+}
+
+/// Resolve the target of a symlink among the entries of the archive.
+fn link_target<'a>(entry: &Entry, paths: &'a BTreeMap<PathBuf, Entry>) -> Option<&'a Entry> {
+    let target = Path::new(&entry.link);
+    if target.is_absolute() {
+        return None;
+    }
+
+    let mut resolved = PathBuf::new();
+    let parent = entry.path.parent().unwrap_or_else(|| Path::new(""));
+    for component in parent.join(target).components() {
+        match component {
+            Component::ParentDir if !resolved.pop() => return None,
+            Component::Normal(name) => resolved.push(name),
+            _ => (),
+        }
+    }
+
+    paths.get(&resolved)
+}
+
+/// Build the hierarchy of the entries, adding the directories which are only implied by the
+/// paths of their content.
+fn build_tree(archive: &Path, entries: Vec<Entry>) -> Vec<Meta> {
+    let mut paths: BTreeMap<PathBuf, Entry> = BTreeMap::new();
+    for entry in entries {
+        let mut parent = entry.path.parent();
+        while let Some(dir) = parent.filter(|p| !p.as_os_str().is_empty()) {
+            paths.entry(dir.to_path_buf()).or_insert_with(|| Entry {
+                path: dir.to_path_buf(),
+                mode: 0o755,
+                kind: b'5',
+                mtime: entry.mtime,
+                ..Entry::default()
+            });
+            parent = dir.parent();
+        }
+        // the last occurrence of a path wins, like on extraction
+        paths.insert(entry.path.clone(), entry);
+    }
+
+    let mut metas: BTreeMap<PathBuf, Meta> = paths
+        .values()
+        .map(|entry| (entry.path.clone(), build_meta(archive, entry, &paths)))
+        .collect();
+
+    // attach the deepest entries first, so that every directory is complete when it is moved
+    let mut keys: Vec<PathBuf> = metas.keys().cloned().collect();
+    keys.sort_by_key(|p| std::cmp::Reverse(p.components().count()));
+
+    let mut root = Vec::new();
+    for key in keys {
+        let meta = metas
+            .remove(&key)
+            .expect("the entry should not be attached yet");
+        let parent = key.parent().filter(|p| !p.as_os_str().is_empty());
+        match parent.and_then(|p| metas.get_mut(p)) {
+            Some(dir) => dir.content.get_or_insert_with(Vec::new).push(meta),
+            None => root.push(meta),
+        }
+    }
+
+    root
+}
+
+/// Sum up the sizes of the directory content, before the depth prunes it.
+fn total_size(meta: &mut Meta) {
+    if let Some(content) = &mut meta.content {
+        content.iter_mut().for_each(total_size);
+        let bytes = content.iter().map(|m| m.size.get_bytes()).sum::<u64>();
+        meta.size = Size::new(meta.size.get_bytes() + bytes);
+    }
+}
+
+/// Apply the depth and the display filters of the flags, like for the listing of a directory.
+fn prune(content: &mut Vec<Meta>, depth: usize, flags: &Flags, archive: &Path) {
+    content.retain(|meta| {
+        let name = meta.name.name.as_str();
+        if flags.ignore_globs.is_match(&meta.path, archive) {
+            return false;
+        }
+        if flags.display == Display::VisibleOnly && name.starts_with('.') {
+            return false;
+        }
+        if flags.layout == Layout::Tree && flags.display == Display::DirectoryOnly {
+            return meta.file_type.is_dirlike();
+        }
+        true
+    });
+
+    for meta in content {
+        if depth <= 1 || flags.bundle_extensions.is_bundle(&meta.name) {
+            meta.content = None;
+        } else if let Some(inner) = &mut meta.content {
+            prune(inner, depth - 1, flags, archive);
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use super::{is_archive, normalize, read_into};
+    use crate::app;
+    use crate::color::{Colors, Theme};
+    use crate::config_file::Config;
+    use crate::flags::Flags;
+    use crate::meta::UsersCache;
+    use crate::meta::{FileType, Meta};
+    use std::fs::{self, File};
+    use std::io::Write;
+    use std::os::unix::fs::{symlink, PermissionsExt};
+    use std::path::Path;
+    use std::process::Command;
+    use tempfile::tempdir;
+
+    fn create_tree(root: &Path) {
+        fs::create_dir_all(root.join("dir")).unwrap();
+
+        let mut file = File::create(root.join("dir/file.txt")).unwrap();
+        file.write_all(b"hello").unwrap();
+        fs::set_permissions(root.join("dir/file.txt"), fs::Permissions::from_mode(0o640)).unwrap();
+
+        let mut script = File::create(root.join("script.sh")).unwrap();
+        script.write_all(b"#!/bin/sh\n").unwrap();
+        fs::set_permissions(root.join("script.sh"), fs::Permissions::from_mode(0o755)).unwrap();
+
+        symlink("script.sh", root.join("link")).unwrap();
+    }
+
+    fn list(archive: &Path, argv: Vec<&str>, depth: usize) -> Meta {
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let mut meta = Meta::from_path(archive, false, &UsersCache::new()).unwrap();
+        read_into(&mut meta, depth, &flags).unwrap();
+        meta
+    }
+
+    fn check_content(meta: &Meta) {
+        let colors = Colors::new(Theme::NoColor);
+        let content = meta.content.as_ref().expect("the archive should be listed");
+
+        let mut names: Vec<&str> = content.iter().map(|m| m.name.name.as_str()).collect();
+        names.sort_unstable();
+        assert_eq!(vec!["dir", "link", "script.sh"], names);
+
+        let script = content.iter().find(|m| m.name.name == "script.sh").unwrap();
+        assert_eq!(10, script.size.get_bytes());
+        assert_eq!("rwxr-xr-x", script.permissions.render(&colors).to_string());
+        assert_eq!(
+            FileType::File {
+                exec: true,
+                uid: false
+            },
+            script.file_type
+        );
+
+        let link = content.iter().find(|m| m.name.name == "link").unwrap();
+        assert_eq!(FileType::SymLink { is_dir: false }, link.file_type);
+        assert_eq!(Some("script.sh".to_string()), link.symlink.symlink_string());
+
+        let dir = content.iter().find(|m| m.name.name == "dir").unwrap();
+        let inner = dir.content.as_ref().expect("dir should be listed");
+        assert_eq!(1, inner.len());
+        assert_eq!("file.txt", inner[0].name.name);
+        assert_eq!(5, inner[0].size.get_bytes());
+        assert_eq!(
+            "rw-r-----",
+            inner[0].permissions.render(&colors).to_string()
+        );
+    }
+
+    #[test]
+    fn test_is_archive() {
+        assert!(is_archive(Path::new("backup.tar")));
+        assert!(is_archive(Path::new("backup.TAR.GZ")));
+        assert!(is_archive(Path::new("backup.tgz")));
+        assert!(!is_archive(Path::new("backup.zip")));
+    }
+
+    #[test]
+    fn test_read_tar() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        create_tree(&tmp_dir.path().join("src"));
+        let archive = tmp_dir.path().join("archive.tar");
+
+        let status = Command::new("tar")
+            .arg("-cf")
+            .arg(&archive)
+            .arg("-C")
+            .arg(tmp_dir.path().join("src"))
+            .args(["dir", "script.sh", "link"])
+            .status()
+            .expect("failed to run tar");
+        assert!(status.success());
+
+        let meta = list(&archive, vec!["lsd", "--tree", "--archive"], usize::MAX);
+        assert!(matches!(meta.file_type, FileType::Directory { .. }));
+        assert!(!meta.archived);
+        check_content(&meta);
+        assert!(meta.content.unwrap().iter().all(|m| m.archived));
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(Path::new("dir/file"), normalize(Path::new("./dir/file")));
+        assert_eq!(Path::new("etc/passwd"), normalize(Path::new("/etc/passwd")));
+        assert_eq!(
+            Path::new("dir/file"),
+            normalize(Path::new("../dir/../file"))
+        );
+        assert_eq!(Path::new(""), normalize(Path::new("../..")));
+    }
+
+    #[test]
+    fn test_read_tar_gz() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        create_tree(&tmp_dir.path().join("src"));
+        let archive = tmp_dir.path().join("archive.tar.gz");
+
+        let status = Command::new("tar")
+            .arg("-czf")
+            .arg(&archive)
+            .arg("-C")
+            .arg(tmp_dir.path().join("src"))
+            .arg(".")
+            .status()
+            .expect("failed to run tar");
+        assert!(status.success());
+
+        let meta = list(&archive, vec!["lsd", "--tree", "--archive"], usize::MAX);
+        check_content(&meta);
+    }
+
+    #[test]
+    fn test_read_tar_depth() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        create_tree(&tmp_dir.path().join("src"));
+        let archive = tmp_dir.path().join("archive.tar");
+
+        let status = Command::new("tar")
+            .arg("-cf")
+            .arg(&archive)
+            .arg("-C")
+            .arg(tmp_dir.path().join("src"))
+            .arg("dir/file.txt")
+            .status()
+            .expect("failed to run tar");
+        assert!(status.success());
+
+        let meta = list(&archive, vec!["lsd", "--archive"], 1);
+        let content = meta.content.as_ref().unwrap();
+        assert_eq!(1, content.len());
+        assert_eq!("dir", content[0].name.name);
+        assert!(matches!(content[0].file_type, FileType::Directory { .. }));
+        assert!(content[0].content.is_none());
+    }
+
+    #[test]
+    fn test_read_invalid_archive() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let archive = tmp_dir.path().join("archive.tar");
+        fs::write(&archive, vec![b'x'; 1024]).unwrap();
+
+        let matches = app::build()
+            .get_matches_from_safe(vec!["lsd", "--archive"])
+            .unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+        let mut meta = Meta::from_path(&archive, false, &UsersCache::new()).unwrap();
+        assert!(read_into(&mut meta, 1, &flags).is_err());
+    }
+}
@@ -0,0 +1,108 @@
+use std::cell::RefCell;
+
+thread_local! {
+    /// The budget of the running scan, [None] when `--scan-budget` is not passed.
+    static BUDGET: RefCell<Option<Budget>> = const { RefCell::new(None) };
+}
+
+/// A number of bytes of files to scan, after which the recursion stops.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+struct Budget {
+    limit: u64,
+    spent: u64,
+}
+
+/// Limit the following scans to `limit` bytes of files.
+pub fn start(limit: u64) {
+    BUDGET.with(|cell| *cell.borrow_mut() = Some(Budget { limit, spent: 0 }));
+}
+
+/// Count the `bytes` of one more scanned file in the started budget.
+pub fn spend(bytes: u64) {
+    BUDGET.with(|cell| {
+        if let Some(budget) = cell.borrow_mut().as_mut() {
+            budget.spent = budget.spent.saturating_add(bytes);
+        }
+    });
+}
+
+/// Whether a budget is started, so that the scans have to be counted in order.
+pub fn is_started() -> bool {
+    BUDGET.with(|cell| cell.borrow().is_some())
+}
+
+/// Whether the started budget is spent, so that the scan has to stop.
+pub fn exhausted() -> bool {
+    BUDGET.with(|cell| {
+        cell.borrow()
+            .is_some_and(|budget| budget.spent >= budget.limit)
+    })
+}
+
+/// Disable the budget once the scans are done, returning the note to print if it was spent.
+pub fn finish() -> Option<String> {
+    let budget = BUDGET.with(|cell| cell.borrow_mut().take())?;
+    if budget.spent < budget.limit {
+        return None;
+    }
+
+    Some(format!(
+        "the scan budget of {} bytes was reached, the listing is partial",
+        budget.limit
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{exhausted, finish, spend, start};
+    use crate::flags::Flags;
+    use crate::meta::Meta;
+    use crate::meta::UsersCache;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_budget_spend() {
+        start(10);
+        spend(4);
+        assert!(!exhausted());
+        spend(6);
+        assert!(exhausted());
+        assert_eq!(
+            Some("the scan budget of 10 bytes was reached, the listing is partial".to_string()),
+            finish()
+        );
+        assert!(!exhausted());
+        assert_eq!(None, finish());
+    }
+
+    #[test]
+    fn test_budget_recurse_into() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        fs::create_dir(tmp_dir.path().join("sub")).unwrap();
+        for name in &["sub/a", "sub/b", "sub/c", "sub/d"] {
+            fs::write(tmp_dir.path().join(name), [0; 100]).unwrap();
+        }
+
+        let count = |metas: &[Meta]| metas[0].content.as_ref().map_or(0, |content| content.len());
+        let meta = Meta::from_path(tmp_dir.path(), false, &UsersCache::new()).unwrap();
+
+        // the whole tree fits in the budget
+        start(1 << 20);
+        let metas = meta
+            .recurse_into(42, &Flags::default(), &UsersCache::new())
+            .unwrap()
+            .unwrap();
+        assert_eq!(4, count(&metas));
+        assert_eq!(None, finish());
+
+        // the scan stops once two files are spent
+        start(200);
+        let metas = meta
+            .recurse_into(42, &Flags::default(), &UsersCache::new())
+            .unwrap()
+            .unwrap();
+        assert!(count(&metas) < 4, "{} entries", count(&metas));
+        assert!(finish().is_some());
+    }
+}
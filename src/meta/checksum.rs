@@ -0,0 +1,250 @@
+use crate::color::{ColoredString, Colors, Elem};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// The expected SHA-256 sums of a `sha256sum`-style manifest, by canonical path.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    sums: HashMap<PathBuf, String>,
+}
+
+/// The verification state of a file against a [Manifest].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Checksum {
+    Match,
+    Mismatch,
+    Missing,
+}
+
+impl Manifest {
+    /// Read the manifest at `path`, whose file names are relative to its directory.
+    pub fn from_file(path: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        let base = path.parent().unwrap_or_else(|| Path::new("."));
+
+        Ok(Self::parse(&content, base))
+    }
+
+    /// Parse the `<hex sum>  <file name>` lines of a manifest, the binary mode ones marking the
+    /// name with a `*` included. The malformed lines are ignored.
+    pub fn parse(content: &str, base: &Path) -> Self {
+        let mut sums = HashMap::new();
+
+        for line in content.lines() {
+            let (sum, name) = match line.split_once(' ') {
+                Some(parts) => parts,
+                None => continue,
+            };
+            let name = name.strip_prefix(|c| c == ' ' || c == '*').unwrap_or(name);
+            if sum.len() != 64 || !sum.chars().all(|c| c.is_ascii_hexdigit()) || name.is_empty() {
+                continue;
+            }
+
+            let path = base.join(name);
+            let path = path.canonicalize().unwrap_or(path);
+            sums.insert(path, sum.to_lowercase());
+        }
+
+        Self { sums }
+    }
+
+    /// Compare the SHA-256 sum of the file at `path` with the expected one.
+    pub fn verify(&self, path: &Path) -> Checksum {
+        let expected = match path.canonicalize().ok().and_then(|p| self.sums.get(&p)) {
+            Some(expected) => expected,
+            None => return Checksum::Missing,
+        };
+
+        match File::open(path).and_then(sha256) {
+            Ok(sum) if &sum == expected => Checksum::Match,
+            _ => Checksum::Mismatch,
+        }
+    }
+}
+
+impl Checksum {
+    pub fn render(self, colors: &Colors) -> ColoredString<'static> {
+        match self {
+            Checksum::Match => colors.colorize(String::from("✓"), &Elem::ChecksumMatch),
+            Checksum::Mismatch => colors.colorize(String::from("✗"), &Elem::ChecksumMismatch),
+            Checksum::Missing => colors.colorize(String::from("?"), &Elem::ChecksumMissing),
+        }
+    }
+}
+
+const ROUND_CONSTANTS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// Compute the SHA-256 sum of the content of `reader`, as lowercase hexadecimal.
+fn sha256<R: Read>(mut reader: R) -> io::Result<String> {
+    let mut state: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+
+    let mut length: u64 = 0;
+    let mut block = [0u8; 64];
+    let mut filled = 0;
+    let mut buffer = [0u8; 8192];
+
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        length += read as u64;
+
+        for &byte in &buffer[..read] {
+            block[filled] = byte;
+            filled += 1;
+            if filled == block.len() {
+                compress(&mut state, &block);
+                filled = 0;
+            }
+        }
+    }
+
+    // pad with a one bit, zeros and the length in bits
+    block[filled] = 0x80;
+    block[filled + 1..].iter_mut().for_each(|b| *b = 0);
+    if filled >= 56 {
+        compress(&mut state, &block);
+        block = [0u8; 64];
+    }
+    block[56..].copy_from_slice(&(length * 8).to_be_bytes());
+    compress(&mut state, &block);
+
+    Ok(state.iter().map(|word| format!("{:08x}", word)).collect())
+}
+
+fn compress(state: &mut [u32; 8], block: &[u8; 64]) {
+    let mut schedule = [0u32; 64];
+    for (i, word) in block.chunks(4).enumerate() {
+        schedule[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+    }
+    for i in 16..64 {
+        let s0 = schedule[i - 15].rotate_right(7)
+            ^ schedule[i - 15].rotate_right(18)
+            ^ (schedule[i - 15] >> 3);
+        let s1 = schedule[i - 2].rotate_right(17)
+            ^ schedule[i - 2].rotate_right(19)
+            ^ (schedule[i - 2] >> 10);
+        schedule[i] = schedule[i - 16]
+            .wrapping_add(s0)
+            .wrapping_add(schedule[i - 7])
+            .wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for i in 0..64 {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let choice = (e & f) ^ (!e & g);
+        let temp1 = h
+            .wrapping_add(s1)
+            .wrapping_add(choice)
+            .wrapping_add(ROUND_CONSTANTS[i])
+            .wrapping_add(schedule[i]);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let majority = (a & b) ^ (a & c) ^ (b & c);
+        let temp2 = s0.wrapping_add(majority);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(temp1);
+        d = c;
+        c = b;
+        b = a;
+        a = temp1.wrapping_add(temp2);
+    }
+
+    for (word, value) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *word = word.wrapping_add(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sha256, Checksum, Manifest};
+    use crate::color::{Colors, Theme};
+    use ansi_term::Colour;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_sha256() {
+        assert_eq!(
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            sha256(&b""[..]).unwrap()
+        );
+        assert_eq!(
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            sha256(&b"abc"[..]).unwrap()
+        );
+        // the padding of a message filling the first block past the length field
+        assert_eq!(
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+            sha256(&b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"[..]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_verify_manifest() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        fs::write(tmp_dir.path().join("good.txt"), "abc").unwrap();
+        fs::write(tmp_dir.path().join("bad.txt"), "abd").unwrap();
+        fs::write(tmp_dir.path().join("other.txt"), "abc").unwrap();
+
+        let sum = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+        let manifest_path = tmp_dir.path().join("sha256sums");
+        fs::write(
+            &manifest_path,
+            format!("{}  good.txt\n{} *bad.txt\nmalformed line\n", sum, sum),
+        )
+        .unwrap();
+
+        let manifest = Manifest::from_file(&manifest_path).unwrap();
+        assert_eq!(
+            Checksum::Match,
+            manifest.verify(&tmp_dir.path().join("good.txt"))
+        );
+        assert_eq!(
+            Checksum::Mismatch,
+            manifest.verify(&tmp_dir.path().join("bad.txt"))
+        );
+        assert_eq!(
+            Checksum::Missing,
+            manifest.verify(&tmp_dir.path().join("other.txt"))
+        );
+    }
+
+    #[test]
+    fn test_render_checksum() {
+        let colors = Colors::new(Theme::NoLscolors);
+
+        assert_eq!(
+            Colour::Fixed(40).paint("✓"),
+            Checksum::Match.render(&colors)
+        );
+        assert_eq!(
+            Colour::Fixed(124).paint("✗"),
+            Checksum::Mismatch.render(&colors)
+        );
+        assert_eq!(
+            Colour::Fixed(245).paint("?"),
+            Checksum::Missing.render(&colors)
+        );
+    }
+}
@@ -1,11 +1,65 @@
 use crate::color::{ColoredString, Colors, Elem};
-use crate::flags::{DateFlag, Flags};
-use chrono::{DateTime, Duration, Local};
+use crate::flags::{DateFlag, Flags, TimeFlag, TimeZoneFlag};
+use crate::print_error;
+use ansi_term::Colour;
+use chrono::{DateTime, Duration, Local, TimeZone, Utc};
 use chrono_humanize::HumanTime;
+use std::fmt;
 use std::fs::Metadata;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::SystemTime;
 
+/// Whether the warning about the missing birth times was printed already.
+static BIRTH_TIME_WARNED: AtomicBool = AtomicBool::new(false);
+
+/// Whether the warning about sorting without the birth times was printed already.
+static BIRTH_TIME_SORT_WARNED: AtomicBool = AtomicBool::new(false);
+
+/// The displayed time stamp of a file, along with its birth time for --sort created.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Date(DateTime<Local>);
+pub struct Date(DateTime<Local>, Birth);
+
+/// The birth time of a file, or its modification time where the platform does not provide it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Birth {
+    Known(DateTime<Local>),
+    Missing(DateTime<Local>),
+}
+
+impl Birth {
+    fn from_metadata(meta: &Metadata) -> Self {
+        match meta.created() {
+            Ok(created) => Self::Known(created.into()),
+            Err(_) => Self::Missing(
+                meta.modified()
+                    .expect("failed to retrieve modified date")
+                    .into(),
+            ),
+        }
+    }
+}
+
+/// The sections of the dates for --group-by-date, in their display order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DateSection {
+    Future,
+    Today,
+    Yesterday,
+    ThisWeek,
+    Older,
+}
+
+impl DateSection {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Future => "Future",
+            Self::Today => "Today",
+            Self::Yesterday => "Yesterday",
+            Self::ThisWeek => "This week",
+            Self::Older => "Older",
+        }
+    }
+}
 
 impl<'a> From<&'a Metadata> for Date {
     fn from(meta: &'a Metadata) -> Self {
@@ -13,11 +67,60 @@ impl<'a> From<&'a Metadata> for Date {
 
         let time = modified_time.into();
 
-        Date(time)
+        Date(time, Birth::from_metadata(meta))
+    }
+}
+
+impl From<SystemTime> for Date {
+    fn from(time: SystemTime) -> Self {
+        Date(time.into(), Birth::Missing(time.into()))
     }
 }
 
 impl Date {
+    /// Get the `Date` of the `time` stamp of a file, falling back to its modification time when
+    /// the birth time is not available, with a warning printed the first time it happens.
+    pub fn from_metadata(meta: &Metadata, time: TimeFlag) -> Self {
+        let birth = Birth::from_metadata(meta);
+        let stamp = match time {
+            TimeFlag::Modified => None,
+            TimeFlag::Accessed => meta.accessed().ok().map(Into::into),
+            TimeFlag::Created => match birth {
+                Birth::Known(created) => Some(created),
+                Birth::Missing(_) => {
+                    if !BIRTH_TIME_WARNED.swap(true, Ordering::Relaxed) {
+                        print_error!("the birth time is not available, showing the modified time.");
+                    }
+                    None
+                }
+            },
+        };
+
+        match stamp {
+            Some(stamp) => Date(stamp, birth),
+            None => Date(
+                meta.modified()
+                    .expect("failed to retrieve modified date")
+                    .into(),
+                birth,
+            ),
+        }
+    }
+
+    /// Get the birth time to sort by, falling back to the modification time when it is not
+    /// available, with a warning printed the first time it happens.
+    pub fn created(&self) -> DateTime<Local> {
+        match self.1 {
+            Birth::Known(created) => created,
+            Birth::Missing(modified) => {
+                if !BIRTH_TIME_SORT_WARNED.swap(true, Ordering::Relaxed) {
+                    print_error!("the birth time is not available, sorting by the modified time.");
+                }
+                modified
+            }
+        }
+    }
+
     pub fn render(&self, colors: &Colors, flags: &Flags) -> ColoredString {
         let now = Local::now();
 
@@ -32,34 +135,115 @@ impl Date {
         colors.colorize(self.date_string(&flags), &elem)
     }
 
+    /// Render the date with a colour fading from green to grey with its age, relative to the
+    /// `(oldest, newest)` timestamps of the listing.
+    pub fn render_scaled(
+        &self,
+        colors: &Colors,
+        flags: &Flags,
+        range: (i64, i64),
+    ) -> ColoredString<'static> {
+        let (oldest, newest) = range;
+        let age = if newest > oldest {
+            (newest - self.timestamp()) as f64 / (newest - oldest) as f64
+        } else {
+            0.0
+        };
+
+        colors.colorize_with_colour(self.date_string(flags), age_colour(age))
+    }
+
+    pub fn timestamp(&self) -> i64 {
+        self.0.timestamp()
+    }
+
+    /// Get the section of the date relative to `now`, by the local calendar days, this week
+    /// being the six days before yesterday.
+    pub fn section(&self, now: &DateTime<Local>) -> DateSection {
+        if self.0 > *now {
+            return DateSection::Future;
+        }
+
+        let days = now
+            .naive_local()
+            .date()
+            .signed_duration_since(self.0.naive_local().date())
+            .num_days();
+        match days {
+            0 => DateSection::Today,
+            1 => DateSection::Yesterday,
+            2..=7 => DateSection::ThisWeek,
+            _ => DateSection::Older,
+        }
+    }
+
     pub fn date_string(&self, flags: &Flags) -> String {
         match &flags.date {
-            DateFlag::Date => self.0.format("%c").to_string(),
+            DateFlag::Date => self.format("%c", flags),
             DateFlag::Relative => format!("{}", HumanTime::from(self.0 - Local::now())),
             DateFlag::ISO => {
                 // 365.2425 * 24 * 60 * 60 = 31556952 seconds per year
                 // 15778476 seconds are 6 months
                 if self.0 > Local::now() - Duration::seconds(15_778_476) {
-                    self.0.format("%m-%d %R").to_string()
+                    self.format("%m-%d %R", flags)
                 } else {
-                    self.0.format("%F").to_string()
+                    self.format("%F", flags)
                 }
             }
-            DateFlag::Formatted(format) => self.0.format(&format).to_string(),
+            DateFlag::Formatted(format) => self.format(format, flags),
         }
     }
+
+    /// Format the date in the time zone of the flags.
+    fn format(&self, format: &str, flags: &Flags) -> String {
+        format_in(&self.0, format, flags.time_zone)
+    }
+}
+
+/// Format the `date` in the `time_zone`, the local one being the time zone of the date.
+fn format_in<Tz: TimeZone>(date: &DateTime<Tz>, format: &str, time_zone: TimeZoneFlag) -> String
+where
+    Tz::Offset: fmt::Display,
+{
+    match time_zone {
+        TimeZoneFlag::Local => date.format(format).to_string(),
+        TimeZoneFlag::Utc => date.with_timezone(&Utc).format(format).to_string(),
+    }
+}
+
+/// Get the colour of the 256 colours palette for an age between 0 (the newest) and 1 (the
+/// oldest), interpolated from Green3 to Grey.
+fn age_colour(age: f64) -> Colour {
+    const FRESH: [f64; 3] = [0.0, 215.0, 0.0];
+    const OLD: [f64; 3] = [138.0, 138.0, 138.0];
+    // the intensities of the 6x6x6 colour cube of the palette
+    const LEVELS: [f64; 6] = [0.0, 95.0, 135.0, 175.0, 215.0, 255.0];
+
+    let age = age.clamp(0.0, 1.0);
+    let level = |channel: usize| {
+        let value = FRESH[channel] + (OLD[channel] - FRESH[channel]) * age;
+        (0..LEVELS.len())
+            .min_by(|&a, &b| {
+                let distance = |l: usize| (LEVELS[l] - value).abs();
+                distance(a).total_cmp(&distance(b))
+            })
+            .unwrap_or_default() as u8
+    };
+
+    Colour::Fixed(16 + 36 * level(0) + 6 * level(1) + level(2))
 }
 
 #[cfg(test)]
 mod test {
-    use super::Date;
+    use super::{age_colour, format_in, Birth, Date, DateSection};
     use crate::color::{Colors, Theme};
-    use crate::flags::{DateFlag, Flags};
+    use crate::flags::{DateFlag, Flags, TimeZoneFlag};
     use ansi_term::Colour;
-    use chrono::{DateTime, Duration, Local};
+    use chrono::{DateTime, Duration, FixedOffset, Local, TimeZone};
     use std::io;
     use std::path::Path;
     use std::process::{Command, ExitStatus};
+    use std::time::{Duration as StdDuration, SystemTime};
     use std::{env, fs};
 
     #[cfg(unix)]
@@ -265,4 +449,118 @@ mod test {
 
         fs::remove_file(file_path).unwrap();
     }
+
+    #[test]
+    fn test_utc_time_zone() {
+        let date = Date::from(SystemTime::UNIX_EPOCH + StdDuration::from_secs(1_600_000_000));
+        let flags = Flags {
+            date: DateFlag::Formatted("%F %T %z".into()),
+            time_zone: TimeZoneFlag::Utc,
+            ..Flags::default()
+        };
+
+        assert_eq!("2020-09-13 12:26:40 +0000", date.date_string(&flags));
+    }
+
+    #[test]
+    fn test_local_time_zone_offset() {
+        // a fixed offset stands for the local time zone, whatever TZ is
+        let date = FixedOffset::east(5 * 3600 + 1800)
+            .ymd(2020, 9, 13)
+            .and_hms(17, 56, 40);
+
+        assert_eq!(
+            "2020-09-13 17:56:40 +0530",
+            format_in(&date, "%F %T %z", TimeZoneFlag::Local)
+        );
+        assert_eq!(
+            "2020-09-13 12:26:40 +0000",
+            format_in(&date, "%F %T %z", TimeZoneFlag::Utc)
+        );
+    }
+
+    #[test]
+    fn test_formatted_unix_timestamp() {
+        let mut file_path = env::temp_dir();
+        file_path.push("test_formatted_unix_timestamp.tmp");
+
+        let creation_date = Local::now() - Duration::days(3);
+        let success = cross_platform_touch(&file_path, &creation_date)
+            .unwrap()
+            .success();
+        assert!(success, "failed to exec touch");
+
+        let colors = Colors::new(Theme::NoColor);
+        let date = Date::from(&file_path.metadata().unwrap());
+
+        let flags = Flags {
+            date: DateFlag::Formatted("%s".into()),
+            ..Flags::default()
+        };
+
+        assert_eq!(
+            creation_date.timestamp().to_string(),
+            date.render(&colors, &flags).to_string()
+        );
+
+        fs::remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_age_colour_bounds() {
+        assert_eq!(Colour::Fixed(40), age_colour(0.0));
+        assert_eq!(Colour::Fixed(102), age_colour(1.0));
+    }
+
+    #[test]
+    fn test_render_scaled() {
+        // the green intensity above the red one, in the colour cube of the palette
+        let greenness = |date: &Date, range| {
+            let colors = Colors::new(Theme::Default);
+            let rendered = date.render_scaled(&colors, &Flags::default(), range);
+            match rendered.style_ref().foreground {
+                Some(Colour::Fixed(index)) => {
+                    let cube = i32::from(index) - 16;
+                    (cube / 6) % 6 - cube / 36
+                }
+                colour => panic!("unexpected colour {:?}", colour),
+            }
+        };
+
+        let now = SystemTime::now();
+        let newest = Date::from(now);
+        let middle = Date::from(now - StdDuration::from_secs(50 * 86_400));
+        let oldest = Date::from(now - StdDuration::from_secs(100 * 86_400));
+        let range = (oldest.timestamp(), newest.timestamp());
+
+        assert!(greenness(&newest, range) > greenness(&middle, range));
+        assert!(greenness(&middle, range) > greenness(&oldest, range));
+    }
+
+    #[test]
+    fn test_render_scaled_single_date() {
+        let colors = Colors::new(Theme::Default);
+        let date = Date::from(SystemTime::now());
+        let range = (date.timestamp(), date.timestamp());
+
+        assert_eq!(
+            Some(Colour::Fixed(40)),
+            date.render_scaled(&colors, &Flags::default(), range)
+                .style_ref()
+                .foreground
+        );
+    }
+
+    #[test]
+    fn test_section() {
+        let now = Local::now();
+        let section =
+            |offset: Duration| Date(now + offset, Birth::Missing(now + offset)).section(&now);
+
+        assert_eq!(DateSection::Future, section(Duration::minutes(1)));
+        assert_eq!(DateSection::Today, section(Duration::zero()));
+        assert_eq!(DateSection::Yesterday, section(Duration::days(-1)));
+        assert_eq!(DateSection::ThisWeek, section(Duration::days(-4)));
+        assert_eq!(DateSection::Older, section(Duration::days(-30)));
+    }
 }
@@ -0,0 +1,82 @@
+use crate::color::{ColoredString, Colors, Elem};
+use std::fs::Metadata;
+
+/// The id of the device holding a file, telling the mounts apart.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Device {
+    id: Option<u64>,
+}
+
+impl From<&Metadata> for Device {
+    #[cfg(unix)]
+    fn from(meta: &Metadata) -> Self {
+        use std::os::unix::fs::MetadataExt;
+
+        Self {
+            id: Some(meta.dev()),
+        }
+    }
+
+    #[cfg(windows)]
+    fn from(_: &Metadata) -> Self {
+        Self { id: None }
+    }
+}
+
+impl Device {
+    pub fn none() -> Self {
+        Self { id: None }
+    }
+
+    pub fn id(&self) -> Option<u64> {
+        self.id
+    }
+
+    pub fn render(&self, colors: &Colors) -> ColoredString<'static> {
+        match self.id {
+            Some(id) => colors.colorize(id.to_string(), &Elem::Device { valid: true }),
+            None => colors.colorize(String::from("-"), &Elem::Device { valid: false }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Device;
+    use crate::color::{Colors, Theme};
+    use std::fs::File;
+    use tempfile::tempdir;
+
+    #[test]
+    #[cfg(unix)]
+    fn test_device_shared_on_same_fs() {
+        use std::os::unix::fs::MetadataExt;
+
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let path_a = tmp_dir.path().join("a");
+        let path_b = tmp_dir.path().join("b");
+        File::create(&path_a).expect("failed to create file");
+        File::create(&path_b).expect("failed to create file");
+
+        let meta_a = path_a.metadata().unwrap();
+        let device_a = Device::from(&meta_a);
+        let device_b = Device::from(&path_b.metadata().unwrap());
+        assert_eq!(device_a, device_b);
+
+        let colors = Colors::new(Theme::NoColor);
+        assert_eq!(
+            meta_a.dev().to_string(),
+            device_a.render(&colors).to_string()
+        );
+    }
+
+    #[test]
+    fn test_device_none() {
+        assert_eq!(
+            "-",
+            Device::none()
+                .render(&Colors::new(Theme::NoColor))
+                .to_string()
+        );
+    }
+}
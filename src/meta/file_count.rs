@@ -0,0 +1,98 @@
+use crate::color::{ColoredString, Colors, Elem};
+use crate::flags::Display;
+use std::path::Path;
+
+/// The number of the entries of a directory, its `.` and `..` left out.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct FileCount {
+    count: Option<usize>,
+}
+
+impl FileCount {
+    pub fn none() -> Self {
+        Self { count: None }
+    }
+
+    /// Count the entries of the directory at `path`, [none](FileCount::none) when it can not be
+    /// read.
+    pub fn from_dir(path: &Path) -> Self {
+        Self {
+            count: path.read_dir().ok().map(Iterator::count),
+        }
+    }
+
+    /// Count the entries of the directory at `path` that the `display` lists, the hidden ones
+    /// being left out unless all of them are shown.
+    pub fn from_visible_dir(path: &Path, display: Display) -> Self {
+        let hidden_shown = matches!(display, Display::All | Display::AlmostAll);
+        Self {
+            count: path.read_dir().ok().map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .filter(|entry| {
+                        hidden_shown || !entry.file_name().to_string_lossy().starts_with('.')
+                    })
+                    .count()
+            }),
+        }
+    }
+
+    /// Render the count in parentheses after a name, [None] for the files that are not counted.
+    pub fn render_suffix(&self, colors: &Colors) -> Option<ColoredString<'static>> {
+        self.count
+            .map(|count| colors.colorize(format!(" ({})", count), &Elem::FileCount))
+    }
+
+    /// Render the count, blank for the files that are not counted, like the non-directories.
+    pub fn render(&self, colors: &Colors) -> ColoredString<'static> {
+        match self.count {
+            Some(count) => colors.colorize(count.to_string(), &Elem::FileCount),
+            None => colors.colorize(String::new(), &Elem::FileCount),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FileCount;
+    use crate::color::{Colors, Theme};
+    use crate::flags::Display;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_file_count_of_dir() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        fs::create_dir(tmp_dir.path().join("sub")).unwrap();
+        fs::write(tmp_dir.path().join("file"), "one").unwrap();
+        fs::write(tmp_dir.path().join(".hidden"), "two").unwrap();
+
+        let colors = Colors::new(Theme::NoColor);
+        let count = FileCount::from_dir(tmp_dir.path());
+        assert_eq!("3", count.render(&colors).to_string());
+        assert_eq!(
+            "",
+            FileCount::from_dir(&tmp_dir.path().join("file"))
+                .render(&colors)
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_visible_count_of_dir() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        fs::create_dir(tmp_dir.path().join("sub")).unwrap();
+        fs::write(tmp_dir.path().join("file"), "one").unwrap();
+        fs::write(tmp_dir.path().join(".hidden"), "two").unwrap();
+
+        let colors = Colors::new(Theme::NoColor);
+        let suffix = |display| {
+            FileCount::from_visible_dir(tmp_dir.path(), display)
+                .render_suffix(&colors)
+                .map(|suffix| suffix.to_string())
+        };
+        assert_eq!(Some(" (2)".to_string()), suffix(Display::VisibleOnly));
+        assert_eq!(Some(" (3)".to_string()), suffix(Display::AlmostAll));
+        assert_eq!(None, FileCount::none().render_suffix(&colors));
+    }
+}
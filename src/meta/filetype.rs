@@ -1,6 +1,6 @@
 use crate::color::{ColoredString, Colors, Elem};
 use crate::meta::Permissions;
-use std::fs::Metadata;
+use std::fs::{self, Metadata};
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 #[cfg_attr(windows, allow(dead_code))]
@@ -80,6 +80,38 @@ impl FileType {
         }
     }
 
+    /// Get the `FileType` stored in a directory entry, without the permissions nor the type of
+    /// the symlink targets.
+    pub fn from_entry_type(file_type: fs::FileType) -> Self {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::FileTypeExt;
+
+            if file_type.is_fifo() {
+                return FileType::Pipe;
+            } else if file_type.is_char_device() {
+                return FileType::CharDevice;
+            } else if file_type.is_block_device() {
+                return FileType::BlockDevice;
+            } else if file_type.is_socket() {
+                return FileType::Socket;
+            }
+        }
+
+        if file_type.is_file() {
+            FileType::File {
+                exec: false,
+                uid: false,
+            }
+        } else if file_type.is_dir() {
+            FileType::Directory { uid: false }
+        } else if file_type.is_symlink() {
+            FileType::SymLink { is_dir: false }
+        } else {
+            FileType::Special
+        }
+    }
+
     pub fn is_dirlike(self) -> bool {
         matches!(
             self,
@@ -89,6 +121,20 @@ impl FileType {
 }
 
 impl FileType {
+    /// Get the letter of the file type in the listings of `ls -l`.
+    pub fn letter(self) -> char {
+        match self {
+            FileType::File { .. } => '-',
+            FileType::Directory { .. } => 'd',
+            FileType::Pipe => 'p',
+            FileType::SymLink { .. } => 'l',
+            FileType::BlockDevice => 'b',
+            FileType::CharDevice => 'c',
+            FileType::Socket => 's',
+            FileType::Special => '?',
+        }
+    }
+
     pub fn render(self, colors: &Colors) -> ColoredString {
         match self {
             FileType::File { exec, .. } => {
@@ -114,6 +160,7 @@ mod test {
     use crate::meta::Meta;
     #[cfg(unix)]
     use crate::meta::Permissions;
+    use crate::meta::UsersCache;
     use ansi_term::Colour;
     #[cfg(unix)]
     use std::fs::File;
@@ -125,6 +172,27 @@ mod test {
     use std::process::Command;
     use tempfile::tempdir;
 
+    #[test]
+    fn test_letter() {
+        let letters: Vec<char> = vec![
+            FileType::File {
+                uid: false,
+                exec: true,
+            },
+            FileType::Directory { uid: false },
+            FileType::SymLink { is_dir: true },
+            FileType::Pipe,
+            FileType::Socket,
+            FileType::CharDevice,
+            FileType::BlockDevice,
+            FileType::Special,
+        ]
+        .into_iter()
+        .map(FileType::letter)
+        .collect();
+        assert_eq!(vec!['-', 'd', 'l', 'p', 's', 'c', 'b', '?'], letters);
+    }
+
     #[test]
     #[cfg(unix)] // Windows uses different default permissions
     fn test_file_type() {
@@ -144,7 +212,7 @@ mod test {
     #[test]
     fn test_dir_type() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
-        let meta = Meta::from_path(&tmp_dir.path().to_path_buf(), false)
+        let meta = Meta::from_path(&tmp_dir.path().to_path_buf(), false, &UsersCache::new())
             .expect("failed to get tempdir path");
         let metadata = tmp_dir.path().metadata().expect("failed to get metas");
 
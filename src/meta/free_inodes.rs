@@ -0,0 +1,76 @@
+use crate::color::{ColoredString, Colors, Elem};
+use std::path::Path;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct FreeInodes {
+    count: Option<u64>,
+}
+
+impl FreeInodes {
+    /// Get the number of inodes available on the file system holding the directory at `path`.
+    #[cfg(unix)]
+    pub fn from_dir(path: &Path) -> Self {
+        use std::ffi::CString;
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = match CString::new(path.as_os_str().as_bytes()) {
+            Ok(path) => path,
+            Err(_) => return Self::none(),
+        };
+
+        let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+        if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+            return Self::none();
+        }
+
+        #[allow(clippy::useless_conversion)]
+        Self {
+            count: Some(u64::from(stat.f_favail)),
+        }
+    }
+
+    #[cfg(windows)]
+    pub fn from_dir(_: &Path) -> Self {
+        Self::none()
+    }
+
+    pub fn none() -> Self {
+        Self { count: None }
+    }
+
+    pub fn render(&self, colors: &Colors) -> ColoredString<'static> {
+        match self.count {
+            Some(i) => colors.colorize(i.to_string(), &Elem::INode { valid: true }),
+            None => colors.colorize(String::from("-"), &Elem::INode { valid: false }),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use super::FreeInodes;
+    use crate::color::{Colors, Theme};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_free_inodes_of_dir() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let free_inodes = FreeInodes::from_dir(tmp_dir.path());
+        assert!(free_inodes.count.is_some());
+
+        let rendered = free_inodes.render(&Colors::new(Theme::NoColor)).to_string();
+        assert!(rendered.parse::<u64>().is_ok());
+    }
+
+    #[test]
+    fn test_free_inodes_none() {
+        assert_eq!(
+            "-",
+            FreeInodes::none()
+                .render(&Colors::new(Theme::NoColor))
+                .to_string()
+        );
+    }
+}
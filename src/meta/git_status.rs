@@ -0,0 +1,436 @@
+use crate::color::{ColoredString, Colors, Elem};
+use crate::flags::GitSymbols;
+use ansi_term::ANSIStrings;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+
+/// The status codes of the paths of a repository, as the two letters of `git status --porcelain`,
+/// by path.
+pub type Statuses = Arc<HashMap<PathBuf, [u8; 2]>>;
+
+/// The status of each repository, by repository root, to run `git status` only once per
+/// repository for all the scan threads. [None] when the status could not be read.
+static STATUSES: Mutex<BTreeMap<PathBuf, Option<Statuses>>> = Mutex::new(BTreeMap::new());
+
+/// The status code of an ignored path.
+const IGNORED: [u8; 2] = *b"!!";
+/// The status code of an untracked path.
+const UNTRACKED: [u8; 2] = *b"??";
+/// The status code of an unchanged path.
+const CLEAN: [u8; 2] = *b"  ";
+
+/// Get the root of the git repository holding the canonical `path`, if any. The content of the
+/// git directory itself is not part of the repository.
+pub fn repository_root(path: &Path) -> Option<PathBuf> {
+    if path.components().any(|c| c.as_os_str() == ".git") {
+        return None;
+    }
+
+    path.ancestors()
+        .find(|p| p.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+/// Get the status of the paths of the repository at `root`, the ignored ones included.
+///
+/// The status is read from the `git` command rather than through libgit2, which would add a C
+/// dependency to the build for a single block. Without a `git` command, or when it fails, the
+/// status is [None] and the block stays blank.
+pub fn statuses(root: &Path) -> Option<Statuses> {
+    let mut cache = STATUSES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    cache
+        .entry(root.to_path_buf())
+        .or_insert_with(|| read_statuses(root).map(Arc::new))
+        .clone()
+}
+
+fn read_statuses(root: &Path) -> Option<HashMap<PathBuf, [u8; 2]>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args([
+            "status",
+            "--porcelain",
+            "-z",
+            "--untracked-files=all",
+            "--ignored=matching",
+        ])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let mut statuses = HashMap::new();
+    let mut records = output.stdout.split(|&b| b == 0);
+    while let Some(record) = records.next() {
+        if record.len() < 4 {
+            continue;
+        }
+
+        let path = String::from_utf8_lossy(&record[3..]);
+        statuses.insert(root.join(path.as_ref()), [record[0], record[1]]);
+
+        // the renames and the copies are followed by their source path
+        if record[0] == b'R' || record[0] == b'C' {
+            records.next();
+        }
+    }
+
+    Some(statuses)
+}
+
+/// Check whether the `code` is the one of an ignored path.
+pub fn is_ignored(code: &[u8; 2]) -> bool {
+    *code == IGNORED
+}
+
+/// Derive the status of a directory from the `codes` of the entries under it: untracked when all
+/// of them are, otherwise the staged and modified letters they agree on, `M` when they differ.
+fn directory_code<'a>(codes: impl Iterator<Item = &'a [u8; 2]>) -> [u8; 2] {
+    let mut changed = codes.filter(|code| !is_ignored(code)).peekable();
+    if changed.peek().is_none() {
+        return CLEAN;
+    }
+
+    let mut letters: [Option<u8>; 2] = [None, None];
+    let mut untracked = true;
+    for code in changed {
+        if *code == UNTRACKED {
+            continue;
+        }
+        untracked = false;
+
+        for (letter, &found) in letters.iter_mut().zip(code.iter()) {
+            if found == b' ' || found == b'?' {
+                continue;
+            }
+            *letter = match *letter {
+                Some(known) if known != found => Some(b'M'),
+                _ => Some(found),
+            };
+        }
+    }
+
+    if untracked {
+        UNTRACKED
+    } else {
+        [letters[0].unwrap_or(b' '), letters[1].unwrap_or(b' ')]
+    }
+}
+
+/// The git repository holding a listed directory, with the status of its paths, read once for the
+/// whole listing.
+#[derive(Debug)]
+pub struct Repository {
+    root: PathBuf,
+    statuses: Statuses,
+}
+
+impl Repository {
+    /// Find the repository holding the canonical `path`, if any.
+    pub fn discover(path: &Path) -> Option<Self> {
+        let root = repository_root(path)?;
+        let statuses = statuses(&root)?;
+        Some(Self { root, statuses })
+    }
+
+    /// Check whether the canonical directory `dir` belongs to this repository, rather than to a
+    /// nested one or to none.
+    pub fn holds(&self, dir: &Path) -> bool {
+        dir.starts_with(&self.root)
+            && !dir.components().any(|c| c.as_os_str() == ".git")
+            && (dir == self.root || !dir.join(".git").exists())
+    }
+
+    /// Get the status of the paths of the repository.
+    pub fn statuses(&self) -> &Statuses {
+        &self.statuses
+    }
+
+    /// Get the status of the canonical `path` within the repository.
+    pub fn status(&self, path: &Path, is_dir: bool) -> GitStatus {
+        GitStatus {
+            code: Some(code_of(&self.statuses, path, is_dir)),
+        }
+    }
+}
+
+/// Get the status code of the canonical `path` among the `statuses` of its repository.
+fn code_of(statuses: &Statuses, path: &Path, is_dir: bool) -> [u8; 2] {
+    if let Some(code) = statuses.get(path) {
+        return *code;
+    }
+
+    if path
+        .ancestors()
+        .skip(1)
+        .any(|dir| statuses.get(dir).is_some_and(is_ignored))
+    {
+        return IGNORED;
+    }
+
+    if is_dir {
+        directory_code(
+            statuses
+                .iter()
+                .filter(|(changed, _)| changed.starts_with(path))
+                .map(|(_, code)| code),
+        )
+    } else {
+        CLEAN
+    }
+}
+
+/// The git status of a file, [None] outside of a repository.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct GitStatus {
+    code: Option<[u8; 2]>,
+}
+
+impl GitStatus {
+    /// Get the status of the file at `path` in the git repository holding it. The files within
+    /// an ignored directory are ignored too, and a directory gets the status of the entries under
+    /// it.
+    pub fn from_path(path: &Path) -> Self {
+        // the parent is resolved rather than the path, which would follow a symlink
+        let path = match (path.parent(), path.file_name()) {
+            (Some(parent), Some(name)) => {
+                let parent = if parent.as_os_str().is_empty() {
+                    Path::new(".")
+                } else {
+                    parent
+                };
+                match parent.canonicalize() {
+                    Ok(parent) => parent.join(name),
+                    Err(_) => return Self::none(),
+                }
+            }
+            _ => match path.canonicalize() {
+                Ok(path) => path,
+                Err(_) => return Self::none(),
+            },
+        };
+
+        let repository = match Repository::discover(&path) {
+            Some(repository) => repository,
+            None => return Self::none(),
+        };
+
+        let is_dir = path.symlink_metadata().is_ok_and(|meta| meta.is_dir());
+        repository.status(&path, is_dir)
+    }
+
+    pub fn none() -> Self {
+        Self { code: None }
+    }
+
+    pub fn render(&self, colors: &Colors, symbols: &GitSymbols) -> ColoredString<'static> {
+        let code = match self.code {
+            Some(code) => code,
+            None => return colors.colorize(String::from("  "), &Elem::NonFile),
+        };
+
+        match code {
+            CLEAN => colors.colorize(symbols.unmodified.clone(), &Elem::NonFile),
+            UNTRACKED => colors.colorize(symbols.untracked.clone(), &Elem::GitUntracked),
+            IGNORED => colors.colorize(symbols.ignored.clone(), &Elem::GitIgnored),
+            [staged, modified] => {
+                let letter = |letter: u8, elem: &Elem| {
+                    let symbol = match symbols.letter(letter) {
+                        Some(symbol) => symbol.to_string(),
+                        None => char::from(letter).to_string(),
+                    };
+                    colors.colorize(symbol, elem)
+                };
+                let strings: &[ColoredString] = &[
+                    letter(staged, &Elem::GitStaged),
+                    letter(modified, &Elem::GitModified),
+                ];
+                ColoredString::from(ANSIStrings(strings).to_string())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use super::GitStatus;
+    use crate::app;
+    use crate::color::{Colors, Theme};
+    use crate::config_file::Config;
+    use crate::flags::{Flags, GitSymbols};
+    use crate::meta::{Meta, UsersCache};
+    use std::fs;
+    use std::path::Path;
+    use std::process::Command;
+    use tempfile::tempdir;
+
+    fn git(repo: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(repo)
+            .args(["-c", "user.name=lsd", "-c", "user.email=lsd@example.com"])
+            .args(args)
+            .output()
+            .expect("failed to run git")
+            .status;
+        assert!(status.success());
+    }
+
+    fn render_with(path: &Path, symbols: &GitSymbols) -> String {
+        GitStatus::from_path(path)
+            .render(&Colors::new(Theme::NoColor), symbols)
+            .to_string()
+    }
+
+    fn render(path: &Path) -> String {
+        render_with(path, &GitSymbols::default())
+    }
+
+    #[test]
+    fn test_git_status() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let repo = tmp_dir.path();
+        fs::create_dir_all(repo.join("build")).unwrap();
+        fs::write(repo.join(".gitignore"), "build/\n*.log\n").unwrap();
+        fs::write(repo.join("clean.txt"), "one").unwrap();
+        fs::write(repo.join("modified.txt"), "one").unwrap();
+
+        git(repo, &["init", "--quiet"]);
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "--quiet", "--message", "init"]);
+
+        fs::write(repo.join("modified.txt"), "two").unwrap();
+        fs::write(repo.join("added.txt"), "one").unwrap();
+        git(repo, &["add", "added.txt"]);
+        fs::write(repo.join("untracked.txt"), "one").unwrap();
+        fs::write(repo.join("debug.log"), "one").unwrap();
+        fs::write(repo.join("build/out"), "one").unwrap();
+
+        assert_eq!("--", render(&repo.join("clean.txt")));
+        assert_eq!(" M", render(&repo.join("modified.txt")));
+        assert_eq!("A ", render(&repo.join("added.txt")));
+        assert_eq!("??", render(&repo.join("untracked.txt")));
+        assert_eq!("!!", render(&repo.join("debug.log")));
+        assert_eq!("!!", render(&repo.join("build")));
+        assert_eq!("!!", render(&repo.join("build/out")));
+    }
+
+    #[test]
+    fn test_git_status_directory() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let repo = tmp_dir.path();
+        fs::create_dir_all(repo.join("clean")).unwrap();
+        fs::create_dir_all(repo.join("changed")).unwrap();
+        fs::write(repo.join("clean/a"), "one").unwrap();
+        fs::write(repo.join("changed/a"), "one").unwrap();
+        fs::write(repo.join("changed/b"), "one").unwrap();
+        fs::create_dir_all(repo.join("staged")).unwrap();
+        fs::write(repo.join("staged/a"), "one").unwrap();
+        fs::write(repo.join("staged/b"), "one").unwrap();
+
+        git(repo, &["init", "--quiet"]);
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "--quiet", "--message", "init"]);
+
+        fs::create_dir_all(repo.join("newdir/deep")).unwrap();
+        fs::write(repo.join("newdir/b"), "one").unwrap();
+        fs::write(repo.join("newdir/deep/c"), "one").unwrap();
+        fs::write(repo.join("changed/a"), "two").unwrap();
+        fs::write(repo.join("changed/c"), "one").unwrap();
+        fs::write(repo.join("staged/a"), "two").unwrap();
+        git(repo, &["add", "staged/a"]);
+        fs::write(repo.join("staged/b"), "two").unwrap();
+
+        assert_eq!("--", render(&repo.join("clean")));
+        assert_eq!("??", render(&repo.join("newdir")));
+        assert_eq!("??", render(&repo.join("newdir/deep")));
+        assert_eq!(" M", render(&repo.join("changed")));
+        assert_eq!("MM", render(&repo.join("staged")));
+    }
+
+    #[test]
+    fn test_git_status_read_by_the_scan() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let outer = tmp_dir.path().join("outer");
+        let inner = outer.join("inner");
+        fs::create_dir_all(&inner).unwrap();
+        fs::write(outer.join("modified.txt"), "one").unwrap();
+        fs::write(inner.join("changed.txt"), "one").unwrap();
+
+        git(&inner, &["init", "--quiet"]);
+        git(&inner, &["add", "."]);
+        git(&inner, &["commit", "--quiet", "--message", "init"]);
+        git(&outer, &["init", "--quiet"]);
+        git(&outer, &["add", "modified.txt"]);
+        git(&outer, &["commit", "--quiet", "--message", "init"]);
+
+        fs::write(outer.join("modified.txt"), "two").unwrap();
+        fs::write(inner.join("changed.txt"), "two").unwrap();
+        fs::write(inner.join("new.txt"), "one").unwrap();
+
+        let argv = vec!["lsd", "--tree", "--blocks", "git,name"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+        let content = Meta::from_path(&outer, false, &UsersCache::new())
+            .unwrap()
+            .recurse_into(42, &flags, &UsersCache::new())
+            .unwrap()
+            .unwrap();
+
+        let colors = Colors::new(Theme::NoColor);
+        let status = |metas: &[Meta], name: &str| {
+            let meta = metas.iter().find(|m| m.name.name == name).unwrap();
+            meta.git_status
+                .render(&colors, &GitSymbols::default())
+                .to_string()
+        };
+        assert_eq!(" M", status(&content, "modified.txt"));
+
+        // the nested repository has statuses of its own
+        let nested = content.iter().find(|m| m.name.name == "inner").unwrap();
+        let nested = nested.content.as_ref().unwrap();
+        assert_eq!(" M", status(nested, "changed.txt"));
+        assert_eq!("??", status(nested, "new.txt"));
+    }
+
+    #[test]
+    fn test_git_status_outside_repository() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        fs::write(tmp_dir.path().join("file"), "one").unwrap();
+
+        assert_eq!("  ", render(&tmp_dir.path().join("file")));
+    }
+
+    #[test]
+    fn test_git_status_configured_symbols() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let repo = tmp_dir.path();
+        fs::write(repo.join("clean.txt"), "one").unwrap();
+        fs::write(repo.join("modified.txt"), "one").unwrap();
+
+        git(repo, &["init", "--quiet"]);
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "--quiet", "--message", "init"]);
+
+        fs::write(repo.join("modified.txt"), "two").unwrap();
+        fs::write(repo.join("untracked.txt"), "one").unwrap();
+
+        let symbols = GitSymbols {
+            unmodified: String::from("  "),
+            untracked: String::from("?"),
+            modified: String::from("~"),
+            ..GitSymbols::default()
+        };
+        assert_eq!("  ", render_with(&repo.join("clean.txt"), &symbols));
+        assert_eq!(" ~", render_with(&repo.join("modified.txt"), &symbols));
+        assert_eq!("?", render_with(&repo.join("untracked.txt"), &symbols));
+    }
+}
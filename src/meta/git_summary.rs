@@ -0,0 +1,150 @@
+use super::git_status::{is_ignored, repository_root, statuses, Statuses};
+use crate::color::{ColoredString, Colors, Elem};
+use std::path::Path;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct GitDirSummary {
+    changed: Option<usize>,
+}
+
+impl GitDirSummary {
+    /// Count the files changed within the directory at `path`, according to the git repository
+    /// holding it. The untracked files count as changed, the ignored ones do not.
+    pub fn from_dir(path: &Path) -> Self {
+        let dir = match path.canonicalize() {
+            Ok(dir) => dir,
+            Err(_) => return Self::none(),
+        };
+
+        match repository_root(&dir).and_then(|root| statuses(&root)) {
+            Some(statuses) => Self::from_statuses(&statuses, &dir),
+            None => Self::none(),
+        }
+    }
+
+    /// Count the files changed within the canonical directory `dir`, among the `statuses` of the
+    /// repository holding it.
+    pub fn from_statuses(statuses: &Statuses, dir: &Path) -> Self {
+        Self {
+            changed: Some(
+                statuses
+                    .iter()
+                    .filter(|(p, code)| !is_ignored(code) && p.starts_with(dir))
+                    .count(),
+            ),
+        }
+    }
+
+    pub fn none() -> Self {
+        Self { changed: None }
+    }
+
+    pub fn render(&self, colors: &Colors) -> ColoredString<'static> {
+        match self.changed {
+            Some(0) => colors.colorize(String::from("✓"), &Elem::GitStatus { clean: true }),
+            Some(n) => colors.colorize(format!("±{}", n), &Elem::GitStatus { clean: false }),
+            None => colors.colorize(String::from("-"), &Elem::NonFile),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use super::GitDirSummary;
+    use crate::app;
+    use crate::color::{Colors, Theme};
+    use crate::config_file::Config;
+    use crate::flags::Flags;
+    use crate::meta::{Meta, UsersCache};
+    use std::fs;
+    use std::path::Path;
+    use std::process::Command;
+    use tempfile::tempdir;
+
+    fn git(repo: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(repo)
+            .args(["-c", "user.name=lsd", "-c", "user.email=lsd@example.com"])
+            .args(args)
+            .output()
+            .expect("failed to run git")
+            .status;
+        assert!(status.success());
+    }
+
+    fn render(path: &Path) -> String {
+        GitDirSummary::from_dir(path)
+            .render(&Colors::new(Theme::NoColor))
+            .to_string()
+    }
+
+    #[test]
+    fn test_git_dir_summary() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let repo = tmp_dir.path();
+        fs::create_dir_all(repo.join("sub/deep")).unwrap();
+        fs::create_dir_all(repo.join("clean")).unwrap();
+        fs::write(repo.join("sub/deep/tracked.txt"), "one").unwrap();
+        fs::write(repo.join("clean/tracked.txt"), "one").unwrap();
+
+        git(repo, &["init", "--quiet"]);
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "--quiet", "--message", "init"]);
+
+        fs::write(repo.join("sub/deep/tracked.txt"), "two").unwrap();
+        fs::write(repo.join("sub/untracked.txt"), "one").unwrap();
+
+        assert_eq!("±1", render(&repo.join("sub/deep")));
+        assert_eq!("±2", render(&repo.join("sub")));
+        assert_eq!("±2", render(repo));
+        assert_eq!("✓", render(&repo.join("clean")));
+    }
+
+    #[test]
+    fn test_git_dir_summary_read_by_the_scan() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let repo = tmp_dir.path();
+        fs::create_dir_all(repo.join("sub/deep")).unwrap();
+        fs::create_dir_all(repo.join("clean")).unwrap();
+        fs::write(repo.join("sub/deep/tracked.txt"), "one").unwrap();
+        fs::write(repo.join("clean/tracked.txt"), "one").unwrap();
+
+        git(repo, &["init", "--quiet"]);
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "--quiet", "--message", "init"]);
+
+        fs::write(repo.join("sub/deep/tracked.txt"), "two").unwrap();
+        fs::write(repo.join("sub/untracked.txt"), "one").unwrap();
+
+        let argv = vec!["lsd", "--tree", "--blocks", "git_dir_summary,name"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+        let content = Meta::from_path(repo, false, &UsersCache::new())
+            .unwrap()
+            .recurse_into(42, &flags, &UsersCache::new())
+            .unwrap()
+            .unwrap();
+
+        let colors = Colors::new(Theme::NoColor);
+        let summary = |metas: &[Meta], name: &str| {
+            let meta = metas.iter().find(|m| m.name.name == name).unwrap();
+            meta.git_summary.render(&colors).to_string()
+        };
+        assert_eq!("±2", summary(&content, "sub"));
+        assert_eq!("✓", summary(&content, "clean"));
+
+        let sub = content.iter().find(|m| m.name.name == "sub").unwrap();
+        let sub = sub.content.as_ref().unwrap();
+        assert_eq!("±1", summary(sub, "deep"));
+        assert_eq!("-", summary(sub, "untracked.txt"));
+    }
+
+    #[test]
+    fn test_git_dir_summary_outside_repository() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        assert_eq!("-", render(tmp_dir.path()));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::color::{ColoredString, Colors, Elem};
+use crate::flags::Flags;
 use std::fs::Metadata;
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
@@ -23,8 +24,19 @@ impl<'a> From<&'a Metadata> for INode {
 }
 
 impl INode {
-    pub fn render(&self, colors: &Colors) -> ColoredString {
+    pub fn none() -> Self {
+        Self { index: None }
+    }
+
+    pub fn index(&self) -> Option<u64> {
+        self.index
+    }
+
+    pub fn render(&self, colors: &Colors, flags: &Flags) -> ColoredString {
         match self.index {
+            Some(i) if flags.inode_hex.0 => {
+                colors.colorize(format!("{:#x}", i), &Elem::INode { valid: true })
+            }
             Some(i) => colors.colorize(i.to_string(), &Elem::INode { valid: true }),
             None => colors.colorize(String::from("-"), &Elem::INode { valid: false }),
         }
@@ -35,6 +47,8 @@ impl INode {
 #[cfg(unix)]
 mod tests {
     use super::INode;
+    use crate::color::{Colors, Theme};
+    use crate::flags::{Flags, InodeHex};
     use std::env;
     use std::io;
     use std::path::Path;
@@ -59,4 +73,31 @@ mod tests {
         #[cfg(windows)]
         assert!(inode.index.is_none());
     }
+
+    #[test]
+    fn test_inode_render_hex() {
+        use std::os::unix::fs::MetadataExt;
+
+        let mut file_path = env::temp_dir();
+        file_path.push("inode_hex.tmp");
+
+        let success = cross_platform_touch(&file_path).unwrap().success();
+        assert!(success, "failed to exec touch");
+
+        let metadata = file_path.metadata().unwrap();
+        let inode = INode::from(&metadata);
+        let colors = Colors::new(Theme::NoColor);
+
+        let mut flags = Flags::default();
+        assert_eq!(
+            metadata.ino().to_string(),
+            inode.render(&colors, &flags).to_string()
+        );
+
+        flags.inode_hex = InodeHex(true);
+        assert_eq!(
+            format!("0x{:x}", metadata.ino()),
+            inode.render(&colors, &flags).to_string()
+        );
+    }
 }
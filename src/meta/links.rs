@@ -1,4 +1,5 @@
 use crate::color::{ColoredString, Colors, Elem};
+use crate::flags::Flags;
 use std::fs::Metadata;
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
@@ -23,8 +24,16 @@ impl<'a> From<&'a Metadata> for Links {
 }
 
 impl Links {
-    pub fn render(&self, colors: &Colors) -> ColoredString {
+    pub fn none() -> Self {
+        Self { nlink: None }
+    }
+
+    pub fn render(&self, colors: &Colors, flags: &Flags) -> ColoredString {
         match self.nlink {
+            // the single links are the common case, only the others stand out
+            Some(1) if flags.links_only_multi.0 => {
+                colors.colorize(String::from("1"), &Elem::SingleLink)
+            }
             Some(i) => colors.colorize(i.to_string(), &Elem::Links { valid: true }),
             None => colors.colorize(String::from("-"), &Elem::Links { valid: false }),
         }
@@ -35,10 +44,15 @@ impl Links {
 #[cfg(unix)]
 mod tests {
     use super::Links;
+    use crate::color::{Colors, Theme};
+    use crate::flags::{Flags, LinksOnlyMulti};
+    use ansi_term::Colour;
     use std::env;
+    use std::fs::{self, File};
     use std::io;
     use std::path::Path;
     use std::process::{Command, ExitStatus};
+    use tempfile::tempdir;
 
     fn cross_platform_touch(path: &Path) -> io::Result<ExitStatus> {
         Command::new("touch").arg(&path).status()
@@ -59,4 +73,57 @@ mod tests {
         #[cfg(windows)]
         assert!(links.nlink.is_none());
     }
+
+    #[test]
+    fn test_links_only_multi() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let single = tmp_dir.path().join("single");
+        let multi = tmp_dir.path().join("multi");
+        File::create(&single).expect("failed to create file");
+        File::create(&multi).expect("failed to create file");
+        fs::hard_link(&multi, tmp_dir.path().join("multi-link")).expect("failed to link");
+
+        let colors = Colors::new(Theme::NoLscolors);
+        let flags = Flags {
+            links_only_multi: LinksOnlyMulti(true),
+            ..Flags::default()
+        };
+
+        let single_links = Links::from(&single.metadata().unwrap());
+        assert_eq!(
+            Colour::Fixed(240).paint("1"),
+            single_links.render(&colors, &flags)
+        );
+        assert_eq!(
+            "1",
+            single_links
+                .render(&Colors::new(Theme::NoColor), &flags)
+                .to_string()
+        );
+
+        let multi_links = Links::from(&multi.metadata().unwrap());
+
+        // LS_COLORS has no indicator of the single links, which stay dimmed
+        let database = tmp_dir.path().join("dircolors");
+        fs::write(&database, "NORMAL 01;37\n").expect("failed to write the database");
+        let ls_colors = Colors::new(Theme::Default).with_ls_colors_file(&database);
+        assert_eq!(
+            Colour::Fixed(240).paint("1"),
+            single_links.render(&ls_colors, &flags)
+        );
+        assert_ne!(
+            single_links.render(&ls_colors, &flags),
+            multi_links.render(&ls_colors, &flags)
+        );
+        assert_eq!(
+            Colour::Fixed(13).paint("2"),
+            multi_links.render(&colors, &flags)
+        );
+
+        // without the flag, the single links are as prominent as the others
+        assert_eq!(
+            Colour::Fixed(13).paint("1"),
+            single_links.render(&colors, &Flags::default())
+        );
+    }
 }
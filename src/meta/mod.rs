@@ -1,35 +1,80 @@
+mod access;
+mod age_gap;
+pub mod archive;
+pub mod budget;
+pub mod checksum;
 mod date;
+mod device;
+mod file_count;
 mod filetype;
+mod free_inodes;
+mod git_status;
+mod git_summary;
 mod indicator;
 mod inode;
 mod links;
 pub mod name;
 mod owner;
+mod parallel;
 mod permissions;
+pub mod progress;
+mod real_path;
 mod size;
 mod symlink;
 
 #[cfg(windows)]
 mod windows_utils;
 
-pub use self::date::Date;
+pub use self::access::Access;
+pub use self::age_gap::AgeGap;
+pub use self::date::{Date, DateSection};
+pub use self::device::Device;
+pub use self::file_count::FileCount;
 pub use self::filetype::FileType;
+pub use self::free_inodes::FreeInodes;
+pub use self::git_status::GitStatus;
+use self::git_status::Repository;
+pub use self::git_summary::GitDirSummary;
 pub use self::indicator::Indicator;
 pub use self::inode::INode;
 pub use self::links::Links;
 pub use self::name::Name;
-pub use self::owner::Owner;
+pub use self::owner::{Owner, UsersCache};
 pub use self::permissions::Permissions;
-pub use self::size::Size;
-pub use self::symlink::SymLink;
+pub use self::real_path::RealPath;
+pub use self::size::{Size, Unit};
+pub use self::symlink::{SymLink, TargetState};
 pub use crate::icon::Icons;
 
-use crate::flags::{Display, Flags, Layout};
+use crate::flags::{Block, Display, Flags, IgnoreGlobs, Layout, TimeFlag};
 use crate::print_error;
 
-use std::fs::read_link;
+#[cfg(test)]
+use std::cell::Cell;
+use std::fs::{read_link, DirEntry};
 use std::io::{Error, ErrorKind};
 use std::path::{Component, Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+#[cfg(test)]
+thread_local! {
+    /// The number of metadata reads done by [Meta::from_path], counted for the tests.
+    static METADATA_READS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// The directories being listed, from the innermost one, to stop on the symlink loops of
+/// --follow-dir-symlinks.
+struct Ancestors<'a> {
+    /// The device and the inode of the directory.
+    id: Option<(u64, u64)>,
+    parent: Option<&'a Ancestors<'a>>,
+}
+
+impl Ancestors<'_> {
+    fn contains(&self, id: Option<(u64, u64)>) -> bool {
+        id.is_some() && (self.id == id || self.parent.is_some_and(|parent| parent.contains(id)))
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct Meta {
@@ -44,58 +89,148 @@ pub struct Meta {
     pub indicator: Indicator,
     pub inode: INode,
     pub links: Links,
+    pub free_inodes: FreeInodes,
+    pub age_gap: AgeGap,
+    pub device: Device,
+    pub mount_point: bool,
+    pub file_count: FileCount,
+    /// The number of the visible entries of the directory, for --dir-child-count.
+    pub child_count: FileCount,
+    /// The entry is read from an archive, its path not existing on the disk.
+    pub archived: bool,
+    /// Whether the content of the directory could be listed, for the glyphs of its state.
+    pub content_state: TargetState,
+    pub git_status: GitStatus,
+    pub git_summary: GitDirSummary,
     pub content: Option<Vec<Meta>>,
 }
 
 impl Meta {
+    /// Read the content of the directory down to `depth`, the owners of its entries being named
+    /// through the `users` cache of the run.
     pub fn recurse_into(
         &self,
         depth: usize,
         flags: &Flags,
+        users: &UsersCache,
     ) -> Result<Option<Vec<Meta>>, std::io::Error> {
+        self.recurse_within(depth, flags, users, &self.path, None, None)
+            .map(|(content, _)| content)
+    }
+
+    /// Read the content of the directory like [Meta::recurse_into], within the `parent`
+    /// directories being listed from the `root` one, and the git `repository` holding them if
+    /// its statuses are shown. The content comes with the state of the directory, telling
+    /// whether it could be read.
+    fn recurse_within(
+        &self,
+        depth: usize,
+        flags: &Flags,
+        users: &UsersCache,
+        root: &Path,
+        parent: Option<&Ancestors>,
+        repository: Option<&Repository>,
+    ) -> Result<(Option<Vec<Meta>>, TargetState), std::io::Error> {
         if depth == 0 {
-            return Ok(None);
+            return Ok((None, TargetState::Valid));
         }
 
         if flags.display == Display::DirectoryOnly && flags.layout != Layout::Tree {
-            return Ok(None);
+            return Ok((None, TargetState::Valid));
         }
 
         match self.file_type {
             FileType::Directory { .. } => (),
             FileType::SymLink { is_dir: true } => {
                 if flags.layout == Layout::OneLine {
-                    return Ok(None);
+                    return Ok((None, TargetState::Valid));
                 }
             }
-            _ => return Ok(None),
+            _ => return Ok((None, TargetState::Valid)),
         }
 
-        let entries = match self.path.read_dir() {
-            Ok(entries) => entries,
+        let entries: Vec<_> = match self.path.read_dir() {
+            Ok(entries) => entries.collect(),
             Err(err) => {
                 print_error!("{}: {}.", self.path.display(), err);
-                return Ok(None);
+                let state = match err.kind() {
+                    ErrorKind::PermissionDenied => TargetState::NoAccess,
+                    _ => TargetState::Valid,
+                };
+                return Ok((None, state));
+            }
+        };
+        // skip the name lookups of the owners shown as ids, or in the huge directories
+        let numeric_owners = flags.numeric.0 || Owner::numeric_for(entries.len());
+
+        let ancestors = Ancestors {
+            id: self.file_id(),
+            parent,
+        };
+
+        // the statuses are read once per repository, the nested ones being found on the way
+        let dir = if flags.blocks.0.contains(&Block::GitStatus)
+            || flags.blocks.0.contains(&Block::GitDirSummary)
+        {
+            self.path.canonicalize().ok()
+        } else {
+            None
+        };
+        let discovered;
+        let repository = match (&dir, repository) {
+            (Some(dir), Some(repository)) if repository.holds(dir) => Some(repository),
+            (Some(dir), _) => {
+                discovered = Repository::discover(dir);
+                discovered.as_ref()
             }
+            (None, _) => None,
         };
 
         let mut content: Vec<Meta> = Vec::new();
+        // the directories to scan on several threads, once all the entries are read; the budget
+        // and the progress indicator are kept by the current thread, which then scans them all
+        let parallel = !budget::is_started() && !progress::is_enabled();
+        let mut pending = Vec::new();
 
-        if Display::All == flags.display && flags.layout != Layout::Tree {
+        // keep the directories out of the time bounds when they hold entries within them
+        let within_time_filter = |meta: &Meta| {
+            !flags.time_filter.is_active()
+                || flags.time_filter.matches(meta.date.timestamp())
+                || meta
+                    .content
+                    .as_ref()
+                    .is_some_and(|content| !content.is_empty())
+        };
+
+        if Display::All == flags.display
+            && flags.layout != Layout::Tree
+            && flags.type_filter.keeps(self.file_type, false)
+        {
             let mut current_meta;
 
             current_meta = self.clone();
             current_meta.name.name = ".".to_owned();
 
-            let mut parent_meta =
-                Self::from_path(&self.path.join(Component::ParentDir), flags.dereference.0)?;
+            let mut parent_meta = Self::from_path_with_time(
+                &self.path.join(Component::ParentDir),
+                flags.dereference.0,
+                flags.time,
+                users,
+            )?;
             parent_meta.name.name = "..".to_owned();
+            parent_meta.count_entries(flags);
+            parent_meta.read_git_status(flags);
 
             content.push(current_meta);
             content.push(parent_meta);
         }
 
         for entry in entries {
+            // stop the scan once the --scan-budget is spent, keeping the partial content
+            if budget::exhausted() {
+                break;
+            }
+
             let entry = entry?;
             let path = entry.path();
 
@@ -103,7 +238,7 @@ impl Meta {
                 .file_name()
                 .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "invalid file name"))?;
 
-            if flags.ignore_globs.0.is_match(&name) {
+            if flags.ignore_globs.is_match(&path, root) {
                 continue;
             }
 
@@ -113,13 +248,55 @@ impl Meta {
                 }
             }
 
-            let mut entry_meta = match Self::from_path(&path, flags.dereference.0) {
+            let entry_meta = if flags.no_metadata.0 {
+                Self::from_dir_entry(&entry)
+            } else {
+                Self::from_path_with(
+                    &path,
+                    flags.dereference.0,
+                    numeric_owners,
+                    flags.time,
+                    users,
+                )
+            };
+            let mut entry_meta = match entry_meta {
                 Ok(res) => res,
                 Err(err) => {
                     print_error!("{}: {}.", path.display(), err);
                     continue;
                 }
             };
+            progress::tick();
+            if !entry_meta.file_type.is_dirlike() {
+                budget::spend(entry_meta.size.get_bytes());
+            }
+
+            // dereference only the links pointing to directories for --follow-dir-symlinks, but
+            // do not list again a directory holding the link
+            let mut looped = false;
+            if flags.follow_dir_symlinks.0 {
+                if let FileType::SymLink { is_dir: true } = entry_meta.file_type {
+                    match Self::from_path_with(&path, true, numeric_owners, flags.time, users) {
+                        Ok(res) => entry_meta = res,
+                        Err(err) => {
+                            print_error!("{}: {}.", path.display(), err);
+                            continue;
+                        }
+                    }
+                    if ancestors.contains(entry_meta.file_id()) {
+                        print_error!("{}: not listing already-listed directory.", path.display());
+                        entry_meta.content_state = TargetState::Loop;
+                        looped = true;
+                    }
+                }
+            }
+
+            if !flags
+                .type_filter
+                .keeps(entry_meta.file_type, flags.no_symlink.0)
+            {
+                continue;
+            }
 
             // skip files for --tree -d
             if flags.layout == Layout::Tree {
@@ -130,37 +307,131 @@ impl Meta {
                 }
             }
 
-            match entry_meta.recurse_into(depth - 1, &flags) {
-                Ok(content) => entry_meta.content = content,
-                Err(err) => {
-                    print_error!("{}: {}.", path.display(), err);
+            // mark the directories living on another filesystem than their parent
+            if flags.mark_mounts.0 && entry_meta.file_type.is_dirlike() {
+                entry_meta.mount_point = entry_meta.device != self.device;
+            }
+
+            entry_meta.count_entries(flags);
+            if let (Some(dir), Some(repository)) = (&dir, repository) {
+                let path = dir.join(name);
+                let is_dir = matches!(entry_meta.file_type, FileType::Directory { .. });
+                if flags.blocks.0.contains(&Block::GitStatus) {
+                    entry_meta.git_status = repository.status(&path, is_dir);
+                }
+                // a nested repository counts the changes of its own
+                if is_dir && flags.blocks.0.contains(&Block::GitDirSummary) {
+                    entry_meta.git_summary = if repository.holds(&path) {
+                        GitDirSummary::from_statuses(repository.statuses(), &path)
+                    } else {
+                        GitDirSummary::from_dir(&path)
+                    };
+                }
+            }
+
+            // show the bundles as single entries, without their content
+            if !looped && !flags.bundle_extensions.is_bundle(&entry_meta.name) {
+                if parallel && depth > 1 && entry_meta.file_type.is_dirlike() {
+                    pending.push(content.len());
+                    content.push(entry_meta);
                     continue;
                 }
-            };
+
+                match entry_meta.recurse_within(
+                    depth - 1,
+                    &flags,
+                    users,
+                    root,
+                    Some(&ancestors),
+                    repository,
+                ) {
+                    Ok((content, state)) => {
+                        entry_meta.content = content;
+                        entry_meta.content_state = state;
+                    }
+                    Err(err) => {
+                        print_error!("{}: {}.", path.display(), err);
+                        continue;
+                    }
+                };
+            }
+
+            if !within_time_filter(&entry_meta) {
+                continue;
+            }
 
             content.push(entry_meta);
         }
 
-        Ok(Some(content))
+        if !pending.is_empty() {
+            let dirs: Vec<&Meta> = pending.iter().map(|&index| &content[index]).collect();
+            let scanned = parallel::map(&dirs, |dir| {
+                dir.recurse_within(depth - 1, flags, users, root, Some(&ancestors), repository)
+            });
+            let mut dropped = Vec::new();
+            for (index, scanned) in pending.into_iter().zip(scanned) {
+                let dir = &mut content[index];
+                let kept = match scanned {
+                    Ok((scanned, state)) => {
+                        dir.content = scanned;
+                        dir.content_state = state;
+                        within_time_filter(dir)
+                    }
+                    Err(err) => {
+                        print_error!("{}: {}.", dir.path.display(), err);
+                        false
+                    }
+                };
+                if !kept {
+                    dropped.push(index);
+                }
+            }
+
+            let mut index = 0;
+            content.retain(|_| {
+                let kept = dropped.binary_search(&index).is_err();
+                index += 1;
+                kept
+            });
+        }
+
+        Ok((Some(content), TargetState::Valid))
+    }
+
+    /// The device and the inode of the file, identifying it whatever the path leading to it.
+    fn file_id(&self) -> Option<(u64, u64)> {
+        self.device.id().zip(self.inode.index())
+    }
+
+    /// Replace the size of a directory by the total size of its content, without the files
+    /// matching the `ignore_globs` nor the targets of the symlinks.
+    pub fn calculate_total_size(&mut self, ignore_globs: &IgnoreGlobs) {
+        let root = self.path.clone();
+        self.calculate_total_size_within(ignore_globs, &root);
     }
 
-    pub fn calculate_total_size(&mut self) {
+    /// Total the size like [Meta::calculate_total_size], within the directory listed from `root`.
+    fn calculate_total_size_within(&mut self, ignore_globs: &IgnoreGlobs, root: &Path) {
         if let FileType::Directory { .. } = self.file_type {
             if let Some(metas) = &mut self.content {
                 let mut size_accumulated = self.size.get_bytes();
                 for x in &mut metas.iter_mut() {
-                    x.calculate_total_size();
+                    x.calculate_total_size_within(ignore_globs, root);
                     size_accumulated += x.size.get_bytes();
                 }
                 self.size = Size::new(size_accumulated);
             } else {
                 // possibility that 'depth' limited the recursion in 'recurse_into'
-                self.size = Size::new(Meta::calculate_total_file_size(&self.path));
+                self.size = Size::new(Meta::calculate_total_file_size(
+                    &self.path,
+                    ignore_globs,
+                    root,
+                ));
             }
         }
     }
 
-    fn calculate_total_file_size(path: &PathBuf) -> u64 {
+    fn calculate_total_file_size(path: &PathBuf, ignore_globs: &IgnoreGlobs, root: &Path) -> u64 {
         let metadata = if read_link(&path).is_ok() {
             // If the file is a link, retrieve the metadata without following
             // the link.
@@ -177,6 +448,7 @@ impl Meta {
         };
         let file_type = metadata.file_type();
         if file_type.is_file() {
+            budget::spend(metadata.len());
             metadata.len()
         } else if file_type.is_dir() {
             let mut size = metadata.len();
@@ -189,6 +461,10 @@ impl Meta {
                 }
             };
             for entry in entries {
+                if budget::exhausted() {
+                    break;
+                }
+
                 let path = match entry {
                     Ok(entry) => entry.path(),
                     Err(err) => {
@@ -196,7 +472,12 @@ impl Meta {
                         continue;
                     }
                 };
-                size += Meta::calculate_total_file_size(&path);
+                // the ignored files are left out, as they are below the recursion depth
+                if ignore_globs.is_match(&path, root) {
+                    continue;
+                }
+                progress::tick();
+                size += Meta::calculate_total_file_size(&path, ignore_globs, root);
             }
             size
         } else {
@@ -204,7 +485,70 @@ impl Meta {
         }
     }
 
-    pub fn from_path(path: &Path, dereference: bool) -> Result<Self, std::io::Error> {
+    /// Get the `Meta` of `path`, its owner being named through the `users` cache of the run.
+    pub fn from_path(
+        path: &Path,
+        dereference: bool,
+        users: &UsersCache,
+    ) -> Result<Self, std::io::Error> {
+        Self::from_path_with(path, dereference, false, TimeFlag::Modified, users)
+    }
+
+    /// Get the `Meta` of `path`, its date being the `time` stamp of the file.
+    pub fn from_path_with_time(
+        path: &Path,
+        dereference: bool,
+        time: TimeFlag,
+        users: &UsersCache,
+    ) -> Result<Self, std::io::Error> {
+        Self::from_path_with(path, dereference, false, time, users)
+    }
+
+    /// Count the entries of the directory for the file_count block and for --dir-child-count,
+    /// and the free inodes of its file system for the free_inodes block, when they are shown.
+    pub fn count_entries(&mut self, flags: &Flags) {
+        if let FileType::Directory { .. } = self.file_type {
+            if flags.blocks.0.contains(&Block::FreeInodes) {
+                self.free_inodes = FreeInodes::from_dir(&self.path);
+            }
+            if flags.blocks.0.contains(&Block::FileCount) {
+                self.file_count = FileCount::from_dir(&self.path);
+            }
+            if flags.dir_child_count.0 && flags.layout == Layout::Grid {
+                self.child_count = FileCount::from_visible_dir(&self.path, flags.display);
+            }
+        }
+    }
+
+    /// Get the git status of a listed file for the git block, and the count of the changes under
+    /// a listed directory for the git_dir_summary block, when they are shown. The content of the
+    /// directories gets them while being read, by [Meta::recurse_into].
+    pub fn read_git_status(&mut self, flags: &Flags) {
+        if self.archived {
+            return;
+        }
+        if flags.blocks.0.contains(&Block::GitStatus) {
+            self.git_status = GitStatus::from_path(&self.path);
+        }
+        if let FileType::Directory { .. } = self.file_type {
+            if flags.blocks.0.contains(&Block::GitDirSummary) {
+                self.git_summary = GitDirSummary::from_dir(&self.path);
+            }
+        }
+    }
+
+    /// Get the `Meta` of `path`, with the numeric owner ids only if `numeric_owners` is set.
+    #[cfg_attr(windows, allow(unused_variables))]
+    fn from_path_with(
+        path: &Path,
+        dereference: bool,
+        numeric_owners: bool,
+        time: TimeFlag,
+        users: &UsersCache,
+    ) -> Result<Self, std::io::Error> {
+        #[cfg(test)]
+        METADATA_READS.with(|reads| reads.set(reads.get() + 1));
+
         // If the file is a link then retrieve link metadata instead with target metadata (if present).
         let (metadata, symlink_meta) = if read_link(path).is_ok() && !dereference {
             (path.symlink_metadata()?, path.metadata().ok())
@@ -213,7 +557,7 @@ impl Meta {
         };
 
         #[cfg(unix)]
-        let owner = Owner::from(&metadata);
+        let owner = Owner::from(&metadata, numeric_owners, users);
         #[cfg(unix)]
         let permissions = Permissions::from(&metadata);
 
@@ -228,10 +572,20 @@ impl Meta {
         Ok(Self {
             inode,
             links,
+            free_inodes: FreeInodes::none(),
+            age_gap: AgeGap::from(&metadata),
+            device: Device::from(&metadata),
+            mount_point: false,
+            file_count: FileCount::none(),
+            child_count: FileCount::none(),
+            archived: false,
+            content_state: TargetState::Valid,
+            git_status: GitStatus::none(),
+            git_summary: GitDirSummary::none(),
             path: path.to_path_buf(),
             symlink: SymLink::from(path),
             size: Size::from(&metadata),
-            date: Date::from(&metadata),
+            date: Date::from_metadata(&metadata, time),
             indicator: Indicator::from(file_type),
             owner,
             permissions,
@@ -240,4 +594,355 @@ impl Meta {
             content: None,
         })
     }
+
+    /// Get the `Meta` of a directory `entry` from its path and the file type the directory holds
+    /// for it, without reading its metadata. The other fields are left empty.
+    fn from_dir_entry(entry: &DirEntry) -> Result<Self, std::io::Error> {
+        let path = entry.path();
+        let file_type = FileType::from_entry_type(entry.file_type()?);
+
+        Ok(Self {
+            inode: INode::none(),
+            links: Links::none(),
+            free_inodes: FreeInodes::none(),
+            age_gap: AgeGap::none(),
+            device: Device::none(),
+            mount_point: false,
+            file_count: FileCount::none(),
+            child_count: FileCount::none(),
+            archived: false,
+            content_state: TargetState::Valid,
+            git_status: GitStatus::none(),
+            git_summary: GitDirSummary::none(),
+            name: Name::new(&path, file_type),
+            symlink: SymLink::new(None, false),
+            size: Size::new(0),
+            date: Date::from(UNIX_EPOCH),
+            indicator: Indicator::from(file_type),
+            owner: Owner::new(String::from("-"), String::from("-")),
+            permissions: Permissions::from_mode(0),
+            file_type,
+            path,
+            content: None,
+        })
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use super::{FileType, FreeInodes, Meta, TargetState, METADATA_READS};
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Flags;
+    use crate::meta::UsersCache;
+    use std::fs::{self, File};
+    use std::os::unix::fs::symlink;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::tempdir;
+
+    /// The number of the owner names looked up by [counted_lookup].
+    static LOOKUPS: AtomicUsize = AtomicUsize::new(0);
+
+    fn counted_lookup(id: u32) -> Option<String> {
+        LOOKUPS.fetch_add(1, Ordering::Relaxed);
+        Some(id.to_string())
+    }
+
+    #[test]
+    fn test_recurse_into_shares_the_owner_names() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        for dir in &["a", "b", "c", "d"] {
+            for sub in &["x", "y"] {
+                let path = tmp_dir.path().join(dir).join(sub);
+                fs::create_dir_all(&path).expect("failed to create dir");
+                File::create(path.join("file")).expect("failed to create file");
+            }
+        }
+
+        let argv = vec!["lsd", "--long", "--recursive"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+        let users = UsersCache::with_lookups(counted_lookup, counted_lookup);
+
+        // the directories scanned on several threads look the owner up once for all of them
+        let content = Meta::from_path(tmp_dir.path(), false, &users)
+            .unwrap()
+            .recurse_into(42, &flags, &users)
+            .unwrap()
+            .unwrap();
+        assert_eq!(4, content.len());
+        assert_eq!(2, LOOKUPS.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_count_entries_free_inodes_only_when_shown() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let configure = |argv: Vec<&str>| {
+            let matches = app::build().get_matches_from_safe(argv).unwrap();
+            Flags::configure_from(&matches, &Config::with_none()).unwrap()
+        };
+
+        let mut meta = Meta::from_path(tmp_dir.path(), false, &UsersCache::new()).unwrap();
+        assert_eq!(FreeInodes::none(), meta.free_inodes);
+
+        meta.count_entries(&configure(vec!["lsd", "--long"]));
+        assert_eq!(FreeInodes::none(), meta.free_inodes);
+
+        meta.count_entries(&configure(vec![
+            "lsd",
+            "--long",
+            "--blocks",
+            "free_inodes,name",
+        ]));
+        assert_ne!(FreeInodes::none(), meta.free_inodes);
+    }
+
+    #[test]
+    fn test_recurse_into_follow_dir_symlinks() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let root = tmp_dir.path().join("root");
+        fs::create_dir(&root).expect("failed to create dir");
+
+        let dir_path = tmp_dir.path().join("target.d");
+        fs::create_dir(&dir_path).expect("failed to create dir");
+        File::create(dir_path.join("inner")).expect("failed to create file");
+        let file_path = tmp_dir.path().join("target.txt");
+        File::create(&file_path).expect("failed to create file");
+
+        symlink(&dir_path, root.join("link.d")).expect("failed to create symlink");
+        symlink(&file_path, root.join("link.txt")).expect("failed to create symlink");
+
+        let argv = vec!["lsd", "--long", "--recursive", "--follow-dir-symlinks"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let content = Meta::from_path(&root, false, &UsersCache::new())
+            .unwrap()
+            .recurse_into(42, &flags, &UsersCache::new())
+            .unwrap()
+            .unwrap();
+
+        let dir_link = content.iter().find(|m| m.name.name == "link.d").unwrap();
+        assert!(matches!(dir_link.file_type, FileType::Directory { .. }));
+        let inner = dir_link
+            .content
+            .as_ref()
+            .expect("link.d should be recursed into");
+        assert_eq!(1, inner.len());
+        assert_eq!("inner", inner[0].name.name);
+
+        let file_link = content.iter().find(|m| m.name.name == "link.txt").unwrap();
+        assert_eq!(FileType::SymLink { is_dir: false }, file_link.file_type);
+        assert!(file_link.content.is_none());
+    }
+
+    #[test]
+    fn test_recurse_into_follow_dir_symlinks_loop() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let root = tmp_dir.path().join("root");
+        fs::create_dir_all(root.join("sub")).expect("failed to create dir");
+        symlink(".", root.join("loop")).expect("failed to create symlink");
+        symlink("..", root.join("sub/up")).expect("failed to create symlink");
+
+        let argv = vec!["lsd", "--recursive", "--follow-dir-symlinks"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        // the links back to the listed directories are kept, without their content
+        let content = Meta::from_path(&root, false, &UsersCache::new())
+            .unwrap()
+            .recurse_into(42, &flags, &UsersCache::new())
+            .unwrap()
+            .unwrap();
+        assert_eq!(2, content.len());
+
+        let looped = content.iter().find(|m| m.name.name == "loop").unwrap();
+        assert!(matches!(looped.file_type, FileType::Directory { .. }));
+        assert!(looped.content.is_none());
+
+        let sub = content.iter().find(|m| m.name.name == "sub").unwrap();
+        let inner = sub.content.as_ref().expect("sub should be recursed into");
+        assert_eq!(1, inner.len());
+        assert_eq!("up", inner[0].name.name);
+        assert!(inner[0].content.is_none());
+    }
+
+    #[test]
+    fn test_recurse_into_follow_dir_symlinks_tree_loop() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let root = tmp_dir.path().join("root");
+        fs::create_dir_all(root.join("sub")).expect("failed to create dir");
+        symlink("..", root.join("sub/up")).expect("failed to create symlink");
+
+        let argv = vec!["lsd", "--tree", "--follow-dir-symlinks"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let content = Meta::from_path(&root, false, &UsersCache::new())
+            .unwrap()
+            .recurse_into(42, &flags, &UsersCache::new())
+            .unwrap()
+            .unwrap();
+        assert_eq!(1, content.len());
+
+        let inner = content[0]
+            .content
+            .as_ref()
+            .expect("sub should be recursed into");
+        assert_eq!(1, inner.len());
+        assert!(matches!(inner[0].file_type, FileType::Directory { .. }));
+        assert_eq!(TargetState::Loop, inner[0].content_state);
+        assert!(inner[0].content.is_none());
+    }
+
+    #[test]
+    fn test_recurse_into_without_follow_dir_symlinks() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let root = tmp_dir.path().join("root");
+        fs::create_dir(&root).expect("failed to create dir");
+
+        let dir_path = tmp_dir.path().join("target.d");
+        fs::create_dir(&dir_path).expect("failed to create dir");
+        File::create(dir_path.join("inner")).expect("failed to create file");
+        symlink(&dir_path, root.join("link.d")).expect("failed to create symlink");
+
+        let argv = vec!["lsd", "--long", "--recursive"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let content = Meta::from_path(&root, false, &UsersCache::new())
+            .unwrap()
+            .recurse_into(42, &flags, &UsersCache::new())
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(FileType::SymLink { is_dir: true }, content[0].file_type);
+        assert!(content[0].content.is_none());
+    }
+
+    #[test]
+    fn test_recurse_into_bundle_extensions() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let root = tmp_dir.path().join("Applications");
+        fs::create_dir_all(root.join("Safari.app/Contents")).expect("failed to create dir");
+        fs::create_dir_all(root.join("Utilities/Terminal.app")).expect("failed to create dir");
+
+        let argv = vec!["lsd", "--tree", "--bundle-extension", "app"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let content = Meta::from_path(&root, false, &UsersCache::new())
+            .unwrap()
+            .recurse_into(42, &flags, &UsersCache::new())
+            .unwrap()
+            .unwrap();
+
+        let bundle = content
+            .iter()
+            .find(|m| m.name.name == "Safari.app")
+            .unwrap();
+        assert!(bundle.content.is_none());
+
+        let dir = content.iter().find(|m| m.name.name == "Utilities").unwrap();
+        let inner = dir
+            .content
+            .as_ref()
+            .expect("Utilities should be recursed into");
+        assert_eq!(1, inner.len());
+        assert!(inner[0].content.is_none());
+    }
+
+    #[test]
+    fn test_recurse_into_no_metadata() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        fs::create_dir(tmp_dir.path().join("sub")).expect("failed to create dir");
+        File::create(tmp_dir.path().join("sub/inner")).expect("failed to create file");
+        File::create(tmp_dir.path().join("file")).expect("failed to create file");
+        symlink("file", tmp_dir.path().join("link")).expect("failed to create symlink");
+
+        let argv = vec!["lsd", "--tree", "--no-metadata"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let root = Meta::from_path(tmp_dir.path(), false, &UsersCache::new()).unwrap();
+        let reads = METADATA_READS.with(|reads| reads.get());
+        let mut content = root
+            .recurse_into(42, &flags, &UsersCache::new())
+            .unwrap()
+            .unwrap();
+        assert_eq!(reads, METADATA_READS.with(|reads| reads.get()));
+
+        content.sort_by(|a, b| a.name.name.cmp(&b.name.name));
+        let names: Vec<_> = content.iter().map(|m| m.name.name.as_str()).collect();
+        assert_eq!(vec!["file", "link", "sub"], names);
+        assert!(matches!(content[0].file_type, FileType::File { .. }));
+        assert!(matches!(content[1].file_type, FileType::SymLink { .. }));
+        assert!(matches!(content[2].file_type, FileType::Directory { .. }));
+        assert_eq!("inner", content[2].content.as_ref().unwrap()[0].name.name);
+    }
+
+    #[test]
+    fn test_calculate_total_size_beyond_depth() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let sub = tmp_dir.path().join("sub");
+        fs::create_dir(&sub).expect("failed to create dir");
+        fs::write(sub.join("kept"), [0; 100]).unwrap();
+        fs::write(sub.join("skipped.log"), [0; 1000]).unwrap();
+        fs::write(tmp_dir.path().join("target"), [0; 5000]).unwrap();
+        symlink(tmp_dir.path().join("target"), sub.join("link")).unwrap();
+
+        let argv = vec!["lsd", "--total-size", "--ignore-glob", "*.log"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let mut meta = Meta::from_path(&sub, false, &UsersCache::new()).unwrap();
+        meta.calculate_total_size(&flags.ignore_globs);
+
+        // neither the ignored file nor the target of the symlink are counted
+        let expected = sub.metadata().unwrap().len() + 100;
+        assert_eq!(expected, meta.size.get_bytes());
+    }
+
+    #[test]
+    fn test_recurse_into_time_filter() {
+        use std::time::{Duration, SystemTime};
+
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let now = SystemTime::now();
+        let touch = |name: &str, hours_ago: u64| {
+            let file = File::create(tmp_dir.path().join(name)).expect("failed to create file");
+            file.set_modified(now - Duration::from_secs(hours_ago * 3600))
+                .expect("failed to set the modification time");
+        };
+        touch("fresh", 0);
+        touch("day_old", 24);
+        touch("week_old", 24 * 7);
+
+        let names = |argv: Vec<&str>| {
+            let matches = app::build().get_matches_from_safe(argv).unwrap();
+            let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+            let mut names: Vec<_> = Meta::from_path(tmp_dir.path(), false, &UsersCache::new())
+                .unwrap()
+                .recurse_into(1, &flags, &UsersCache::new())
+                .unwrap()
+                .unwrap()
+                .into_iter()
+                .map(|meta| meta.name.name)
+                .collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(vec!["fresh"], names(vec!["lsd", "--newer-than", "2h"]));
+        assert_eq!(
+            vec!["day_old", "fresh"],
+            names(vec!["lsd", "--newer-than", "2d"])
+        );
+        assert_eq!(
+            vec!["day_old"],
+            names(vec!["lsd", "--newer-than", "2d", "--older-than", "2h"])
+        );
+        assert_eq!(vec!["day_old", "fresh", "week_old"], names(vec!["lsd"]));
+    }
 }
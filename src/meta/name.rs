@@ -1,9 +1,11 @@
 use crate::color::{ColoredString, Colors, Elem};
+use crate::flags::{Flags, HyperlinkOption};
 use crate::icon::Icons;
 use crate::meta::filetype::FileType;
+use ansi_term::ANSIStrings;
 use std::cmp::{Ordering, PartialOrd};
 use std::ffi::OsStr;
-use std::path::{Component, Path, PathBuf};
+use std::path::{Component, Path, PathBuf, MAIN_SEPARATOR};
 
 #[derive(Debug)]
 pub enum DisplayOption<'a> {
@@ -101,23 +103,67 @@ impl Name {
         colors: &Colors,
         icons: &Icons,
         display_option: &DisplayOption,
-    ) -> ColoredString {
-        let content = match display_option {
-            DisplayOption::FileName => {
-                format!("{}{}", icons.get(self), self.escape(self.file_name()))
+        flags: &Flags,
+    ) -> ColoredString<'static> {
+        let rendered = self.render_unlinked(colors, icons, display_option, flags);
+        if flags.hyperlink != HyperlinkOption::Always {
+            return rendered;
+        }
+
+        // the OSC 8 sequences opening then closing the link around the name
+        ColoredString::from(format!(
+            "\u{1b}]8;;{}\u{1b}\\{}\u{1b}]8;;\u{1b}\\",
+            self.file_uri(),
+            rendered
+        ))
+    }
+
+    fn render_unlinked(
+        &self,
+        colors: &Colors,
+        icons: &Icons,
+        display_option: &DisplayOption,
+        flags: &Flags,
+    ) -> ColoredString<'static> {
+        let mut icon = icons.get(self);
+        if flags.type_prefix.0 {
+            icon = format!("{} {}", self.file_type.letter(), icon);
+        }
+        // the relative directory, rendered dimmed between the icon and the file name
+        let mut dir_prefix = None;
+        let mut content = match display_option {
+            DisplayOption::FileName => self.escape(self.file_name()),
+            DisplayOption::Relative { base_path } => {
+                let relative_path = self.relative_path(base_path);
+                match relative_path.parent() {
+                    Some(parent)
+                        if flags.show_relative_prefix.0 && !parent.as_os_str().is_empty() =>
+                    {
+                        dir_prefix = Some(format!(
+                            "{}{}",
+                            self.escape(&parent.to_string_lossy()),
+                            MAIN_SEPARATOR
+                        ));
+                        self.escape(self.file_name())
+                    }
+                    _ => self.escape(&relative_path.to_string_lossy()),
+                }
             }
-            DisplayOption::Relative { base_path } => format!(
-                "{}{}",
-                icons.get(self),
-                self.escape(&self.relative_path(base_path).to_string_lossy())
-            ),
-            DisplayOption::None => format!(
-                "{}{}",
-                icons.get(self),
-                self.escape(&self.path.to_string_lossy())
-            ),
+            DisplayOption::None => self.escape(&self.path.to_string_lossy()),
         };
 
+        // split the extension off the stem, to render it dimmed
+        let mut extension = None;
+        if flags.emphasize_extension.0 && !matches!(self.file_type, FileType::Directory { .. }) {
+            if let Some(ext) = &self.extension {
+                let suffix = format!(".{}", self.escape(ext));
+                if content.ends_with(&suffix) && content.len() > suffix.len() {
+                    content.truncate(content.len() - suffix.len());
+                    extension = Some(suffix);
+                }
+            }
+        }
+
         let elem = match self.file_type {
             FileType::CharDevice => Elem::CharDevice,
             FileType::Directory { uid } => Elem::Dir { uid },
@@ -129,7 +175,77 @@ impl Name {
             },
         };
 
-        colors.colorize_using_path(content, &self.path, &elem)
+        let dim_icon = flags.dim_hidden.0 && self.is_hidden();
+        if dir_prefix.is_none() && extension.is_none() && !dim_icon {
+            return colors.colorize_using_path(icon + &content, &self.path, &elem);
+        }
+
+        let mut strings = Vec::new();
+        if dim_icon {
+            strings.push(colors.colorize_dimmed_using_path(icon, &self.path, &elem));
+        } else if !icon.is_empty() {
+            strings.push(colors.colorize_using_path(icon, &self.path, &elem));
+        }
+        if let Some(dir_prefix) = dir_prefix {
+            strings.push(colors.colorize_dimmed_using_path(dir_prefix, &self.path, &elem));
+        }
+        strings.push(colors.colorize_using_path(content, &self.path, &elem));
+        if let Some(extension) = extension {
+            strings.push(colors.colorize_dimmed_using_path(extension, &self.path, &elem));
+        }
+
+        ColoredString::from(ANSIStrings(&strings).to_string())
+    }
+
+    /// The `file://` URI of the file, its path made absolute with the bytes out of the unreserved
+    /// set percent-encoded.
+    fn file_uri(&self) -> String {
+        let path = match std::env::current_dir() {
+            Ok(dir) if self.path.is_relative() => dir.join(&self.path),
+            _ => self.path.clone(),
+        };
+
+        #[cfg(unix)]
+        let bytes = {
+            use std::os::unix::ffi::OsStrExt;
+            path.as_os_str().as_bytes().to_vec()
+        };
+        #[cfg(not(unix))]
+        let bytes = {
+            let path = path.to_string_lossy().replace('\\', "/");
+            // the drive letter of an absolute Windows path is preceded by the empty host
+            format!("/{}", path.trim_start_matches('/')).into_bytes()
+        };
+
+        let mut uri = String::from("file://");
+        for byte in bytes {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                    uri.push(byte as char)
+                }
+                _ => uri.push_str(&format!("%{:02X}", byte)),
+            }
+        }
+        uri
+    }
+
+    /// Check if the file is hidden, by its leading dot or by its hidden attribute on Windows.
+    pub fn is_hidden(&self) -> bool {
+        if self.file_name().starts_with('.') {
+            return true;
+        }
+
+        #[cfg(windows)]
+        {
+            use std::os::windows::fs::MetadataExt;
+            const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+
+            if let Ok(metadata) = self.path.symlink_metadata() {
+                return metadata.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0;
+            }
+        }
+
+        false
     }
 
     pub fn extension(&self) -> Option<&str> {
@@ -166,12 +282,14 @@ mod test {
     use super::DisplayOption;
     use super::Name;
     use crate::color::{self, Colors};
+    use crate::flags::{DimHidden, EmphasizeExtension, Flags, HyperlinkOption, TypePrefix};
     use crate::icon::{self, Icons};
     use crate::meta::FileType;
     use crate::meta::Meta;
     #[cfg(unix)]
     use crate::meta::Permissions;
-    use ansi_term::Colour;
+    use crate::meta::UsersCache;
+    use ansi_term::{ANSIStrings, Colour};
     use std::cmp::Ordering;
     use std::fs::{self, File};
     #[cfg(unix)]
@@ -198,10 +316,36 @@ mod test {
 
         assert_eq!(
             Colour::Fixed(184).paint(" file.txt"),
-            name.render(&colors, &icons, &DisplayOption::FileName)
+            name.render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
         );
     }
 
+    #[test]
+    fn test_print_name_type_prefix() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let icons = Icons::new(icon::Theme::NoIcon, " ".to_string());
+        let colors = Colors::new(color::Theme::NoColor);
+        let flags = Flags {
+            type_prefix: TypePrefix(true),
+            ..Flags::default()
+        };
+
+        let file_path = tmp_dir.path().join("file.txt");
+        File::create(&file_path).expect("failed to create file");
+        let dir_path = tmp_dir.path().join("directory");
+        fs::create_dir(&dir_path).expect("failed to create the dir");
+
+        for (path, expected) in &[(file_path, "- file.txt"), (dir_path, "d directory")] {
+            let meta = Meta::from_path(path, false, &UsersCache::new()).unwrap();
+            assert_eq!(
+                *expected,
+                meta.name
+                    .render(&colors, &icons, &DisplayOption::FileName, &flags)
+                    .to_string()
+            );
+        }
+    }
+
     #[test]
     fn test_print_dir_name() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
@@ -210,13 +354,14 @@ mod test {
         // Chreate the directory
         let dir_path = tmp_dir.path().join("directory");
         fs::create_dir(&dir_path).expect("failed to create the dir");
-        let meta = Meta::from_path(&dir_path, false).unwrap();
+        let meta = Meta::from_path(&dir_path, false, &UsersCache::new()).unwrap();
 
         let colors = Colors::new(color::Theme::NoLscolors);
 
         assert_eq!(
             Colour::Fixed(33).paint(" directory"),
-            meta.name.render(&colors, &icons, &DisplayOption::FileName)
+            meta.name
+                .render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
         );
     }
 
@@ -244,7 +389,7 @@ mod test {
 
         assert_eq!(
             Colour::Fixed(44).paint(" target.tmp"),
-            name.render(&colors, &icons, &DisplayOption::FileName)
+            name.render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
         );
     }
 
@@ -272,7 +417,7 @@ mod test {
 
         assert_eq!(
             Colour::Fixed(44).paint(" target.d"),
-            name.render(&colors, &icons, &DisplayOption::FileName)
+            name.render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
         );
     }
 
@@ -298,7 +443,7 @@ mod test {
 
         assert_eq!(
             Colour::Fixed(184).paint(" pipe.tmp"),
-            name.render(&colors, &icons, &DisplayOption::FileName)
+            name.render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
         );
     }
 
@@ -310,14 +455,14 @@ mod test {
         // Create the file;
         let file_path = tmp_dir.path().join("file.txt");
         File::create(&file_path).expect("failed to create file");
-        let meta = Meta::from_path(&file_path, false).unwrap();
+        let meta = Meta::from_path(&file_path, false, &UsersCache::new()).unwrap();
 
         let colors = Colors::new(color::Theme::NoColor);
 
         assert_eq!(
             "file.txt",
             meta.name
-                .render(&colors, &icons, &DisplayOption::FileName)
+                .render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
                 .to_string()
                 .as_str()
         );
@@ -533,7 +678,7 @@ mod test {
 
         assert_eq!(
             Colour::Fixed(184).paint(" file\\ttab.txt"),
-            name.render(&colors, &icons, &DisplayOption::FileName)
+            name.render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
         );
 
         let file_path = tmp_dir.path().join("file\nnewline.txt");
@@ -546,7 +691,136 @@ mod test {
 
         assert_eq!(
             Colour::Fixed(184).paint(" file\\nnewline.txt"),
-            name.render(&colors, &icons, &DisplayOption::FileName)
+            name.render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
+        );
+    }
+
+    #[test]
+    fn test_render_emphasized_extension() {
+        let colors = Colors::new(color::Theme::NoLscolors);
+        let icons = Icons::new(icon::Theme::NoIcon, " ".to_string());
+        let flags = Flags {
+            emphasize_extension: EmphasizeExtension(true),
+            ..Flags::default()
+        };
+        let file_type = FileType::File {
+            uid: false,
+            exec: false,
+        };
+
+        let name = Name::new(Path::new("main.rs"), file_type);
+        let expected = ANSIStrings(&[
+            Colour::Fixed(184).paint("main"),
+            Colour::Fixed(184).dimmed().paint(".rs"),
+        ])
+        .to_string();
+        assert_eq!(
+            expected,
+            name.render(&colors, &icons, &DisplayOption::FileName, &flags)
+                .to_string()
+        );
+
+        // the names without a stem or an extension are rendered whole
+        for file_name in &[".bashrc", "Makefile"] {
+            let name = Name::new(Path::new(file_name), file_type);
+            assert_eq!(
+                Colour::Fixed(184).paint(*file_name).to_string(),
+                name.render(&colors, &icons, &DisplayOption::FileName, &flags)
+                    .to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_render_emphasized_extension_without_color() {
+        let colors = Colors::new(color::Theme::NoColor);
+        let icons = Icons::new(icon::Theme::NoIcon, " ".to_string());
+        let flags = Flags {
+            emphasize_extension: EmphasizeExtension(true),
+            ..Flags::default()
+        };
+
+        let name = Name::new(
+            Path::new("main.rs"),
+            FileType::File {
+                uid: false,
+                exec: false,
+            },
+        );
+        assert_eq!(
+            "main.rs",
+            name.render(&colors, &icons, &DisplayOption::FileName, &flags)
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_render_dim_hidden() {
+        let colors = Colors::new(color::Theme::NoLscolors);
+        let icons = Icons::new(icon::Theme::Fancy, " ".to_string());
+        let flags = Flags {
+            dim_hidden: DimHidden(true),
+            ..Flags::default()
+        };
+        let file_type = FileType::File {
+            uid: false,
+            exec: false,
+        };
+
+        let name = Name::new(Path::new(".hidden"), file_type);
+        let expected = ANSIStrings(&[
+            Colour::Fixed(184).dimmed().paint(icons.get(&name)),
+            Colour::Fixed(184).paint(".hidden"),
+        ])
+        .to_string();
+        assert_eq!(
+            expected,
+            name.render(&colors, &icons, &DisplayOption::FileName, &flags)
+                .to_string()
+        );
+
+        let name = Name::new(Path::new("visible"), file_type);
+        let rendered = name
+            .render(&colors, &icons, &DisplayOption::FileName, &flags)
+            .to_string();
+        assert!(!rendered.contains("\x1b[2;"), "{:?}", rendered);
+        assert_eq!(
+            Colour::Fixed(184)
+                .paint(icons.get(&name) + "visible")
+                .to_string(),
+            rendered
+        );
+    }
+
+    #[test]
+    fn test_render_hyperlink() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("a file.txt");
+        File::create(&file_path).expect("failed to create file");
+        let meta = Meta::from_path(&file_path, false, &UsersCache::new()).unwrap();
+
+        let colors = Colors::new(color::Theme::NoColor);
+        let icons = Icons::new(icon::Theme::NoIcon, " ".to_string());
+        let flags = Flags {
+            hyperlink: HyperlinkOption::Always,
+            ..Flags::default()
+        };
+
+        let uri = meta.name.file_uri();
+        assert!(uri.starts_with("file:///"), "{}", uri);
+        assert!(uri.ends_with("/a%20file.txt"), "{}", uri);
+        assert_eq!(
+            format!("\u{1b}]8;;{}\u{1b}\\a file.txt\u{1b}]8;;\u{1b}\\", uri),
+            meta.name
+                .render(&colors, &icons, &DisplayOption::FileName, &flags)
+                .to_string()
+        );
+
+        assert_eq!(
+            "a file.txt",
+            meta.name
+                .render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
+                .to_string()
         );
     }
 }
@@ -1,46 +1,449 @@
 use crate::color::{ColoredString, Colors, Elem};
+use crate::flags::Flags;
+use crate::print_error;
+use ansi_term::Colour;
+#[cfg(unix)]
+use std::collections::BTreeMap;
 #[cfg(unix)]
 use std::fs::Metadata;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+#[cfg(unix)]
+use std::sync::{mpsc, Mutex};
+#[cfg(unix)]
+use std::thread;
+use std::time::Duration;
+
+/// The milliseconds to wait for a user or group name, `u64::MAX` when there is no limit.
+static LOOKUP_TIMEOUT_MS: AtomicU64 = AtomicU64::new(u64::MAX);
+
+/// The number of entries of a directory beyond which its owners are not looked up by name.
+pub const NUMERIC_THRESHOLD: usize = 10_000;
+
+/// Whether the note about the numeric owners was printed already.
+static NUMERIC_NOTED: AtomicBool = AtomicBool::new(false);
+
+/// The user and group names resolved during a run, shared by its scan threads so that each id
+/// is looked up at most once.
+pub struct UsersCache {
+    #[cfg(unix)]
+    users: Mutex<BTreeMap<u32, String>>,
+    #[cfg(unix)]
+    groups: Mutex<BTreeMap<u32, String>>,
+    /// The lookups of the names of the ids, replaced by the tests to count them.
+    #[cfg(unix)]
+    user_lookup: fn(u32) -> Option<String>,
+    #[cfg(unix)]
+    group_lookup: fn(u32) -> Option<String>,
+}
+
+impl UsersCache {
+    pub fn new() -> Self {
+        Self {
+            #[cfg(unix)]
+            users: Mutex::new(BTreeMap::new()),
+            #[cfg(unix)]
+            groups: Mutex::new(BTreeMap::new()),
+            #[cfg(unix)]
+            user_lookup: user_name,
+            #[cfg(unix)]
+            group_lookup: group_name,
+        }
+    }
+
+    /// Get an empty cache resolving the names with the given lookups.
+    #[cfg(all(test, unix))]
+    pub(crate) fn with_lookups(
+        user_lookup: fn(u32) -> Option<String>,
+        group_lookup: fn(u32) -> Option<String>,
+    ) -> Self {
+        Self {
+            user_lookup,
+            group_lookup,
+            ..Self::new()
+        }
+    }
+
+    /// Get the name of the user `uid`, looking it up the first time it is requested.
+    #[cfg(unix)]
+    fn user(&self, uid: u32, timeout: Option<Duration>) -> String {
+        let lookup = self.user_lookup;
+        cached_name(&self.users, uid, || {
+            name_or_id(move || lookup(uid), uid, timeout)
+        })
+    }
+
+    /// Get the name of the group `gid`, looking it up the first time it is requested.
+    #[cfg(unix)]
+    fn group(&self, gid: u32, timeout: Option<Duration>) -> String {
+        let lookup = self.group_lookup;
+        cached_name(&self.groups, gid, || {
+            name_or_id(move || lookup(gid), gid, timeout)
+        })
+    }
+}
+
+impl Default for UsersCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(unix)]
+fn user_name(uid: u32) -> Option<String> {
+    users::get_user_by_uid(uid).map(|user| user.name().to_string_lossy().to_string())
+}
+
+#[cfg(unix)]
+fn group_name(gid: u32) -> Option<String> {
+    users::get_group_by_gid(gid).map(|group| group.name().to_string_lossy().to_string())
+}
 
 #[derive(Clone, Debug)]
 pub struct Owner {
     user: String,
     group: String,
+    /// The numeric ids of the user and of the group, [None] when they are not known.
+    ids: Option<(u32, u32)>,
 }
 
 impl Owner {
-    #[cfg_attr(unix, allow(dead_code))]
     pub fn new(user: String, group: String) -> Self {
-        Self { user, group }
+        Self {
+            user,
+            group,
+            ids: None,
+        }
+    }
+
+    /// Set the numeric ids of the user and of the group, displayed with --numeric.
+    pub fn with_ids(self, uid: u32, gid: u32) -> Self {
+        Self {
+            ids: Some((uid, gid)),
+            ..self
+        }
+    }
+
+    /// Set how long to wait for the user and group names of the owners built afterwards.
+    pub fn set_lookup_timeout(timeout: Option<Duration>) {
+        let millis = timeout.map_or(u64::MAX, |t| t.as_millis() as u64);
+        LOOKUP_TIMEOUT_MS.store(millis, Ordering::Relaxed);
+    }
+
+    /// Decide whether the owners of a directory holding `entry_count` entries are numeric only,
+    /// noting it once the first time it happens.
+    pub fn numeric_for(entry_count: usize) -> bool {
+        let numeric = entry_count > NUMERIC_THRESHOLD;
+        if numeric && !NUMERIC_NOTED.swap(true, Ordering::Relaxed) {
+            print_error!(
+                "listing directories of more than {} entries, their owners are shown as ids.",
+                NUMERIC_THRESHOLD
+            );
+        }
+
+        numeric
+    }
+
+    #[cfg(unix)]
+    fn lookup_timeout() -> Option<Duration> {
+        match LOOKUP_TIMEOUT_MS.load(Ordering::Relaxed) {
+            u64::MAX => None,
+            millis => Some(Duration::from_millis(millis)),
+        }
     }
 }
 
+/// Run the `lookup` of the name of `id`, falling back to the numeric `id` when there is no such
+/// name or when the lookup does not answer within `timeout`.
+///
+/// The lookup is run on its own thread so that a hanging name service can be abandoned.
 #[cfg(unix)]
-impl<'a> From<&'a Metadata> for Owner {
-    fn from(meta: &Metadata) -> Self {
+fn name_or_id<F>(lookup: F, id: u32, timeout: Option<Duration>) -> String
+where
+    F: FnOnce() -> Option<String> + Send + 'static,
+{
+    let name = match timeout {
+        Some(timeout) => {
+            let (sender, receiver) = mpsc::channel();
+            thread::spawn(move || {
+                // the receiver is gone when the lookup timed out, there is nobody to tell then
+                let _ = sender.send(lookup());
+            });
+
+            receiver.recv_timeout(timeout).ok().flatten()
+        }
+        None => lookup(),
+    };
+
+    name.unwrap_or_else(|| id.to_string())
+}
+
+/// Get the name of `id` from the `cache`, calling `resolve` only the first time it is requested.
+///
+/// The cache stays locked during the lookup, so that the threads asking for the same id wait for
+/// its name rather than looking it up again.
+#[cfg(unix)]
+fn cached_name<F>(cache: &Mutex<BTreeMap<u32, String>>, id: u32, resolve: F) -> String
+where
+    F: FnOnce() -> String,
+{
+    let mut names = cache
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    names.entry(id).or_insert_with(resolve).clone()
+}
+
+#[cfg(unix)]
+impl Owner {
+    /// Get the owner of the file of `meta`, its names resolved through the `users` cache of the
+    /// run, with the numeric ids only when `numeric` is set so that the name service is not
+    /// queried at all.
+    pub fn from(meta: &Metadata, numeric: bool, users: &UsersCache) -> Self {
         use std::os::unix::fs::MetadataExt;
-        use users::{get_group_by_gid, get_user_by_uid};
 
-        let user = match get_user_by_uid(meta.uid()) {
-            Some(res) => res.name().to_string_lossy().to_string(),
-            None => meta.uid().to_string(),
-        };
+        let (uid, gid) = (meta.uid(), meta.gid());
+        if numeric {
+            return Self::new(uid.to_string(), gid.to_string()).with_ids(uid, gid);
+        }
 
-        let group = match get_group_by_gid(meta.gid()) {
-            Some(res) => res.name().to_string_lossy().to_string(),
-            None => meta.gid().to_string(),
-        };
+        let timeout = Self::lookup_timeout();
+        let user = users.user(uid, timeout);
+        let group = users.group(gid, timeout);
 
-        Self { user, group }
+        Self::new(user, group).with_ids(uid, gid)
     }
 }
 
+/// The colors of the users with --color-by-owner, distinct on the dark terminals.
+const OWNER_COLOURS: [u8; 12] = [33, 40, 44, 75, 141, 168, 172, 178, 203, 214, 220, 229];
+
+/// Get the color of the `user` with --color-by-owner, the same for every run as it is picked by
+/// the FNV-1a hash of the name.
+fn owner_colour(user: &str) -> Colour {
+    let hash = user.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    Colour::Fixed(OWNER_COLOURS[(hash % OWNER_COLOURS.len() as u64) as usize])
+}
+
 impl Owner {
-    pub fn render_user(&self, colors: &Colors) -> ColoredString {
-        colors.colorize(self.user.clone(), &Elem::User)
+    pub fn render_user(&self, colors: &Colors, flags: &Flags) -> ColoredString {
+        if flags.anonymize.0 {
+            return colors.colorize(String::from("user"), &Elem::User);
+        }
+
+        let user = match (flags.numeric.0, self.ids) {
+            (true, Some((uid, _))) => uid.to_string(),
+            _ => self.user.clone(),
+        };
+        if flags.color_by_owner.0 {
+            colors.colorize_with_colour(user, owner_colour(&self.user))
+        } else {
+            colors.colorize(user, &Elem::User)
+        }
     }
 
-    pub fn render_group(&self, colors: &Colors) -> ColoredString {
-        colors.colorize(self.group.clone(), &Elem::Group)
+    pub fn render_group(&self, colors: &Colors, flags: &Flags) -> ColoredString {
+        if flags.anonymize.0 {
+            colors.colorize(String::from("group"), &Elem::Group)
+        } else if let (true, Some((_, gid))) = (flags.numeric.0, self.ids) {
+            colors.colorize(gid.to_string(), &Elem::Group)
+        } else {
+            colors.colorize(self.group.clone(), &Elem::Group)
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod test {
+    use super::{cached_name, name_or_id, Owner, UsersCache};
+    use crate::color::{Colors, Theme};
+    use crate::flags::{Anonymize, ColorByOwner, Flags, Numeric};
+    use std::collections::BTreeMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_name_without_timeout() {
+        let name = name_or_id(|| Some("root".to_string()), 0, None);
+        assert_eq!("root", name);
+    }
+
+    #[test]
+    fn test_name_within_timeout() {
+        let name = name_or_id(
+            || Some("root".to_string()),
+            0,
+            Some(Duration::from_secs(10)),
+        );
+        assert_eq!("root", name);
+    }
+
+    #[test]
+    fn test_unknown_name_falls_back_to_id() {
+        let name = name_or_id(|| None, 1000, None);
+        assert_eq!("1000", name);
+    }
+
+    #[test]
+    fn test_timeout_expired_falls_back_to_id() {
+        let slow_lookup = || {
+            thread::sleep(Duration::from_millis(500));
+            Some("root".to_string())
+        };
+
+        let name = name_or_id(slow_lookup, 1000, Some(Duration::from_millis(10)));
+        assert_eq!("1000", name);
+    }
+
+    #[test]
+    fn test_repeated_ids_hit_the_cache() {
+        let cache = Mutex::new(BTreeMap::new());
+        let calls = AtomicUsize::new(0);
+        let resolve = |name: &str| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            name.to_string()
+        };
+
+        assert_eq!("root", cached_name(&cache, 0, || resolve("root")));
+        assert_eq!("user", cached_name(&cache, 1000, || resolve("user")));
+        assert_eq!("root", cached_name(&cache, 0, || resolve("root")));
+        assert_eq!("user", cached_name(&cache, 1000, || resolve("user")));
+        assert_eq!(2, calls.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_threads_share_the_cache() {
+        let cache = Mutex::new(BTreeMap::new());
+        let calls = AtomicUsize::new(0);
+
+        thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    cached_name(&cache, 0, || {
+                        calls.fetch_add(1, Ordering::Relaxed);
+                        "root".to_string()
+                    })
+                });
+            }
+        });
+        assert_eq!(1, calls.load(Ordering::Relaxed));
+    }
+
+    /// The number of the names looked up by [counted_lookup].
+    static LOOKUPS: AtomicUsize = AtomicUsize::new(0);
+
+    fn counted_lookup(id: u32) -> Option<String> {
+        LOOKUPS.fetch_add(1, Ordering::Relaxed);
+        Some(format!("name-{}", id))
+    }
+
+    #[test]
+    fn test_owner_from_looks_up_each_id_once() {
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let meta = tmp_dir.path().metadata().unwrap();
+        let users = UsersCache::with_lookups(counted_lookup, counted_lookup);
+
+        let owner = Owner::from(&meta, false, &users);
+        assert_eq!(2, LOOKUPS.load(Ordering::Relaxed));
+        let again = Owner::from(&meta, false, &users);
+        assert_eq!(2, LOOKUPS.load(Ordering::Relaxed));
+
+        assert_eq!(owner.user, again.user);
+        assert_eq!(owner.group, again.group);
+        assert!(owner.user.starts_with("name-"));
+
+        // the numeric owners do not go through the cache
+        Owner::from(&meta, true, &users);
+        assert_eq!(2, LOOKUPS.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_numeric_beyond_threshold() {
+        use super::NUMERIC_THRESHOLD;
+        use std::os::unix::fs::MetadataExt;
+
+        assert!(!Owner::numeric_for(NUMERIC_THRESHOLD));
+        assert!(Owner::numeric_for(NUMERIC_THRESHOLD + 1));
+
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let meta = tmp_dir.path().metadata().unwrap();
+        let owner = Owner::from(
+            &meta,
+            Owner::numeric_for(NUMERIC_THRESHOLD + 1),
+            &UsersCache::new(),
+        );
+        let colors = Colors::new(Theme::NoColor);
+        let flags = Flags::default();
+
+        assert_eq!(
+            meta.uid().to_string(),
+            owner.render_user(&colors, &flags).to_string()
+        );
+        assert_eq!(
+            meta.gid().to_string(),
+            owner.render_group(&colors, &flags).to_string()
+        );
+    }
+
+    #[test]
+    fn test_render_owner() {
+        let owner = Owner::new("alice".to_string(), "staff".to_string());
+        let colors = Colors::new(Theme::NoColor);
+        let flags = Flags::default();
+
+        assert_eq!("alice", owner.render_user(&colors, &flags).to_string());
+        assert_eq!("staff", owner.render_group(&colors, &flags).to_string());
+    }
+
+    #[test]
+    fn test_render_owner_anonymized() {
+        let owner = Owner::new("alice".to_string(), "staff".to_string());
+        let colors = Colors::new(Theme::NoColor);
+        let flags = Flags {
+            anonymize: Anonymize(true),
+            ..Flags::default()
+        };
+
+        assert_eq!("user", owner.render_user(&colors, &flags).to_string());
+        assert_eq!("group", owner.render_group(&colors, &flags).to_string());
+    }
+
+    #[test]
+    fn test_render_owner_numeric() {
+        let owner = Owner::new("alice".to_string(), "staff".to_string()).with_ids(1000, 20);
+        let colors = Colors::new(Theme::NoColor);
+        let flags = Flags {
+            numeric: Numeric(true),
+            ..Flags::default()
+        };
+
+        assert_eq!("1000", owner.render_user(&colors, &flags).to_string());
+        assert_eq!("20", owner.render_group(&colors, &flags).to_string());
+
+        // the names are kept when the ids are not known
+        let owner = Owner::new("alice".to_string(), "staff".to_string());
+        assert_eq!("alice", owner.render_user(&colors, &flags).to_string());
+    }
+
+    #[test]
+    fn test_render_user_color_by_owner() {
+        let colors = Colors::new(Theme::NoLscolors);
+        let flags = Flags {
+            color_by_owner: ColorByOwner(true),
+            ..Flags::default()
+        };
+        let render = |user: &str| {
+            Owner::new(user.to_string(), "staff".to_string())
+                .render_user(&colors, &flags)
+                .style_ref()
+                .foreground
+        };
+
+        assert_eq!(render("alice"), render("alice"));
+        assert_ne!(render("alice"), render("bob"));
+        assert_ne!(render("root"), render("nobody"));
     }
 }
@@ -0,0 +1,133 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// The most threads scanning at once, bounding the directories read at the same time.
+const MAX_WORKERS: usize = 8;
+
+/// The number of extra threads running, shared by the nested scans.
+static WORKERS: AtomicUsize = AtomicUsize::new(0);
+
+/// The number of threads the scans can use, the calling one included.
+fn worker_limit() -> usize {
+    thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(MAX_WORKERS)
+}
+
+/// Reserve up to `wanted` extra threads in the count of the `workers` running, so that at most
+/// `limit` threads run, returning the number of the ones reserved.
+fn reserve(workers: &AtomicUsize, wanted: usize, limit: usize) -> usize {
+    let limit = limit.saturating_sub(1);
+    let mut running = workers.load(Ordering::Relaxed);
+    loop {
+        let reserved = wanted.min(limit.saturating_sub(running));
+        if reserved == 0 {
+            return 0;
+        }
+
+        match workers.compare_exchange_weak(
+            running,
+            running + reserved,
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+        ) {
+            Ok(_) => return reserved,
+            Err(current) => running = current,
+        }
+    }
+}
+
+/// Run the `job` on each of the `items`, on the calling thread and on the threads left within
+/// the limit, returning the results in the order of the items.
+pub fn map<T, R, F>(items: &[T], job: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    map_within(items, job, &WORKERS, worker_limit())
+}
+
+/// Run the `job` like [map], the extra threads counted in `workers`, on at most `limit` threads.
+fn map_within<T, R, F>(items: &[T], job: F, workers: &AtomicUsize, limit: usize) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let extra = reserve(workers, items.len().saturating_sub(1), limit);
+    if extra == 0 {
+        return items.iter().map(job).collect();
+    }
+
+    let queue = Mutex::new(items.iter().enumerate());
+    let work = || {
+        let mut done = Vec::new();
+        loop {
+            // the lock is released before the job runs
+            let next = queue.lock().unwrap().next();
+            match next {
+                Some((index, item)) => done.push((index, job(item))),
+                None => return done,
+            }
+        }
+    };
+
+    let mut results: Vec<Option<R>> = items.iter().map(|_| None).collect();
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..extra).map(|_| scope.spawn(work)).collect();
+        let mut done = work();
+        for worker in workers {
+            done.extend(worker.join().expect("a scan thread panicked"));
+        }
+        for (index, result) in done {
+            results[index] = Some(result);
+        }
+    });
+    workers.fetch_sub(extra, Ordering::Relaxed);
+
+    results.into_iter().map(Option::unwrap).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::map_within;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_map_keeps_the_order() {
+        let items: Vec<usize> = (0..100).collect();
+        let workers = AtomicUsize::new(0);
+        let squares = map_within(&items, |n| n * n, &workers, 4);
+        assert_eq!(items.iter().map(|n| n * n).collect::<Vec<_>>(), squares);
+        assert_eq!(0, workers.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_map_runs_on_several_threads() {
+        let items: Vec<usize> = (0..100).collect();
+        let workers = AtomicUsize::new(0);
+        let job = |_: &usize| {
+            thread::sleep(Duration::from_millis(1));
+            thread::current().id()
+        };
+        let threads = map_within(&items, job, &workers, 4);
+        assert!(threads.iter().any(|id| *id != thread::current().id()));
+    }
+
+    #[test]
+    fn test_map_nested_within_the_limit() {
+        let items: Vec<usize> = (0..10).collect();
+        let workers = AtomicUsize::new(0);
+        let job = |n: &usize| {
+            assert!(workers.load(Ordering::Relaxed) <= 3);
+            let products = map_within(&items, |m| n * m, &workers, 4);
+            products.into_iter().sum::<usize>()
+        };
+        let sums = map_within(&items, job, &workers, 4);
+        assert_eq!(items.iter().map(|n| n * 45).collect::<Vec<_>>(), sums);
+    }
+}
@@ -55,6 +55,29 @@ impl<'a> From<&'a Metadata> for Permissions {
 }
 
 impl Permissions {
+    /// Get the permissions from the bits of a unix mode, like the one stored in a tar header.
+    pub fn from_mode(bits: u32) -> Self {
+        let has_bit = |bit| bits & bit == bit;
+
+        Self {
+            user_read: has_bit(0o400),
+            user_write: has_bit(0o200),
+            user_execute: has_bit(0o100),
+
+            group_read: has_bit(0o040),
+            group_write: has_bit(0o020),
+            group_execute: has_bit(0o010),
+
+            other_read: has_bit(0o004),
+            other_write: has_bit(0o002),
+            other_execute: has_bit(0o001),
+
+            sticky: has_bit(0o1000),
+            setgid: has_bit(0o2000),
+            setuid: has_bit(0o4000),
+        }
+    }
+
     pub fn render(&self, colors: &Colors) -> ColoredString {
         let bit = |bit, chr: &'static str, elem: &Elem| {
             if bit {
@@ -98,6 +121,75 @@ impl Permissions {
         ColoredString::from(res)
     }
 
+    /// Render the permissions with one background per triad, showing the access level of the
+    /// user, the group and the others.
+    pub fn render_heatmap(&self, colors: &Colors) -> ColoredString<'static> {
+        let triad = |read: bool, write: bool, execute: bool, special: bool, mark: char| {
+            let elem = match (read, write, execute) {
+                (true, true, true) => Elem::TriadFull,
+                (false, false, false) => Elem::TriadNone,
+                _ => Elem::TriadPartial,
+            };
+            let execute = match (execute, special) {
+                (false, false) => '-',
+                (true, false) => 'x',
+                (false, true) => mark.to_ascii_uppercase(),
+                (true, true) => mark,
+            };
+            let chars = format!(
+                "{}{}{}",
+                if read { 'r' } else { '-' },
+                if write { 'w' } else { '-' },
+                execute
+            );
+            colors.colorize_background(chars, &elem)
+        };
+
+        let strings: &[ColoredString] = &[
+            triad(
+                self.user_read,
+                self.user_write,
+                self.user_execute,
+                self.setuid,
+                's',
+            ),
+            triad(
+                self.group_read,
+                self.group_write,
+                self.group_execute,
+                self.setgid,
+                's',
+            ),
+            triad(
+                self.other_read,
+                self.other_write,
+                self.other_execute,
+                self.sticky,
+                't',
+            ),
+        ];
+
+        let res = ANSIStrings(strings).to_string();
+        ColoredString::from(res)
+    }
+
+    /// Render the mode as four octal digits, the special bits always in the first one, like
+    /// `0755` or `4755` for a setuid file.
+    pub fn render_octal(&self, colors: &Colors) -> ColoredString<'static> {
+        let digit = |high: bool, middle: bool, low: bool| {
+            (u8::from(high) << 2) | (u8::from(middle) << 1) | u8::from(low)
+        };
+
+        let octal = format!(
+            "{}{}{}{}",
+            digit(self.setuid, self.setgid, self.sticky),
+            digit(self.user_read, self.user_write, self.user_execute),
+            digit(self.group_read, self.group_write, self.group_execute),
+            digit(self.other_read, self.other_write, self.other_execute),
+        );
+        colors.colorize(octal, &Elem::Octal)
+    }
+
     pub fn is_executable(&self) -> bool {
         self.user_execute || self.group_execute || self.other_execute
     }
@@ -127,3 +219,99 @@ mod modes {
     pub const SETGID: Mode = libc::S_ISGID as Mode;
     pub const SETUID: Mode = libc::S_ISUID as Mode;
 }
+
+#[cfg(test)]
+mod test {
+    use super::Permissions;
+    use crate::color::{Colors, Theme};
+    use ansi_term::{ANSIStrings, Colour};
+
+    fn render_octal(mode: u32) -> String {
+        Permissions::from_mode(mode)
+            .render_octal(&Colors::new(Theme::NoColor))
+            .to_string()
+    }
+
+    #[test]
+    fn test_render_octal_plain() {
+        assert_eq!("0644", render_octal(0o644));
+        assert_eq!("0000", render_octal(0o000));
+    }
+
+    #[test]
+    fn test_render_octal_setuid() {
+        assert_eq!("4755", render_octal(0o4755));
+        assert_eq!("6750", render_octal(0o6750));
+    }
+
+    #[test]
+    fn test_render_octal_sticky() {
+        assert_eq!("1777", render_octal(0o1777));
+    }
+
+    #[test]
+    fn test_render_heatmap() {
+        let permissions = Permissions::from_mode(0o750);
+
+        let expected = ANSIStrings(&[
+            Colour::Black.on(Colour::Fixed(40)).paint("rwx"),
+            Colour::Black.on(Colour::Fixed(184)).paint("r-x"),
+            Colour::Black.on(Colour::Fixed(124)).paint("---"),
+        ])
+        .to_string();
+        let rendered = permissions
+            .render_heatmap(&Colors::new(Theme::NoLscolors))
+            .to_string();
+        assert_eq!(expected, rendered);
+        for background in &["48;5;40", "48;5;184", "48;5;124"] {
+            assert!(rendered.contains(background), "{:?}", rendered);
+        }
+    }
+
+    #[test]
+    fn test_render_heatmap_without_color() {
+        let permissions = Permissions::from_mode(0o4755);
+        assert_eq!(
+            "rwsr-xr-x",
+            permissions
+                .render_heatmap(&Colors::new(Theme::NoColor))
+                .to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_render_setuid_without_exec() {
+        use std::fs::{self, File};
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let path = tmp_dir.path().join("setuid");
+        File::create(&path).expect("failed to create file");
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o4644)).unwrap();
+
+        let permissions = Permissions::from(&fs::metadata(&path).unwrap());
+        assert_eq!(
+            "rwSr--r--",
+            permissions.render(&Colors::new(Theme::NoColor)).to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_render_sticky_without_exec() {
+        use std::fs;
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let path = tmp_dir.path().join("sticky");
+        fs::create_dir(&path).expect("failed to create dir");
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o1776)).unwrap();
+
+        let permissions = Permissions::from(&fs::metadata(&path).unwrap());
+        assert_eq!(
+            "rwxrwxrwT",
+            permissions.render(&Colors::new(Theme::NoColor)).to_string()
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use std::cell::RefCell;
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+/// The minimum delay between two updates of the indicator.
+const INTERVAL: Duration = Duration::from_millis(100);
+
+thread_local! {
+    /// The indicator of the running scan, [None] when `--progress` is not enabled.
+    static PROGRESS: RefCell<Option<Progress>> = const { RefCell::new(None) };
+}
+
+/// A count of the scanned files, periodically printed on a single line.
+pub struct Progress {
+    count: usize,
+    interval: Duration,
+    last_update: Option<Instant>,
+    out: Box<dyn Write>,
+}
+
+impl Progress {
+    pub fn new(out: Box<dyn Write>, interval: Duration) -> Self {
+        Self {
+            count: 0,
+            interval,
+            last_update: None,
+            out,
+        }
+    }
+
+    /// Count one more scanned file, updating the indicator once the interval has elapsed.
+    pub fn tick(&mut self) {
+        self.count += 1;
+
+        if self
+            .last_update
+            .is_none_or(|last| last.elapsed() >= self.interval)
+        {
+            let _ = write!(self.out, "\rScanning: {} files", self.count);
+            let _ = self.out.flush();
+            self.last_update = Some(Instant::now());
+        }
+    }
+
+    /// Erase the indicator, so that it does not remain in front of the listing.
+    pub fn clear(&mut self) {
+        if self.last_update.is_some() {
+            let _ = write!(self.out, "\r\x1b[K");
+            let _ = self.out.flush();
+        }
+    }
+}
+
+/// Print the indicator of the following scans to stderr, if it is a tty.
+pub fn enable() {
+    if io::stderr().is_terminal() {
+        start(Progress::new(Box::new(io::stderr()), INTERVAL));
+    }
+}
+
+fn start(progress: Progress) {
+    PROGRESS.with(|cell| *cell.borrow_mut() = Some(progress));
+}
+
+/// Whether the indicator is enabled, counting the scans of the current thread.
+pub fn is_enabled() -> bool {
+    PROGRESS.with(|cell| cell.borrow().is_some())
+}
+
+/// Count one more scanned file in the enabled indicator.
+pub fn tick() {
+    PROGRESS.with(|cell| {
+        if let Some(progress) = cell.borrow_mut().as_mut() {
+            progress.tick();
+        }
+    });
+}
+
+/// Erase and disable the indicator, once the scans are done.
+pub fn finish() {
+    PROGRESS.with(|cell| {
+        if let Some(mut progress) = cell.borrow_mut().take() {
+            progress.clear();
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{finish, start, Progress};
+    use crate::flags::Flags;
+    use crate::meta::Meta;
+    use crate::meta::UsersCache;
+    use std::cell::RefCell;
+    use std::fs;
+    use std::io::{self, Write};
+    use std::rc::Rc;
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    #[derive(Clone, Default)]
+    struct Output(Rc<RefCell<Vec<u8>>>);
+
+    impl Output {
+        fn text(&self) -> String {
+            String::from_utf8(self.0.borrow().clone()).unwrap()
+        }
+    }
+
+    impl Write for Output {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_progress_tick() {
+        let output = Output::default();
+        let mut progress = Progress::new(Box::new(output.clone()), Duration::ZERO);
+
+        progress.tick();
+        progress.tick();
+        progress.clear();
+
+        assert_eq!(
+            "\rScanning: 1 files\rScanning: 2 files\r\x1b[K",
+            output.text()
+        );
+    }
+
+    #[test]
+    fn test_progress_interval() {
+        let output = Output::default();
+        let mut progress = Progress::new(Box::new(output.clone()), Duration::from_secs(3600));
+
+        progress.tick();
+        progress.tick();
+        progress.tick();
+
+        assert_eq!("\rScanning: 1 files", output.text());
+    }
+
+    #[test]
+    fn test_progress_recurse_into() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        fs::create_dir(tmp_dir.path().join("sub")).unwrap();
+        fs::write(tmp_dir.path().join("one"), "").unwrap();
+        fs::write(tmp_dir.path().join("sub/two"), "").unwrap();
+
+        let output = Output::default();
+        start(Progress::new(Box::new(output.clone()), Duration::ZERO));
+
+        Meta::from_path(tmp_dir.path(), false, &UsersCache::new())
+            .unwrap()
+            .recurse_into(42, &Flags::default(), &UsersCache::new())
+            .unwrap();
+        finish();
+
+        let text = output.text();
+        assert!(text.ends_with("\rScanning: 3 files\r\x1b[K"), "{:?}", text);
+    }
+}
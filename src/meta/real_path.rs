@@ -0,0 +1,66 @@
+use crate::color::{ColoredString, Colors, Elem};
+use std::path::{Path, PathBuf};
+
+/// The canonical absolute path of a file, its symlinks resolved.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RealPath {
+    path: Option<PathBuf>,
+}
+
+impl RealPath {
+    /// Get the canonical path of `path`, [None] when it can not be resolved, like the path of a
+    /// broken symlink.
+    pub fn from_path(path: &Path) -> Self {
+        Self {
+            path: path.canonicalize().ok(),
+        }
+    }
+
+    pub fn none() -> Self {
+        Self { path: None }
+    }
+
+    pub fn render(&self, colors: &Colors) -> ColoredString<'static> {
+        match &self.path {
+            Some(path) => colors.colorize(path.to_string_lossy().into_owned(), &Elem::RealPath),
+            None => colors.colorize(String::from("-"), &Elem::RealPath),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RealPath;
+    use crate::color::{Colors, Theme};
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn render(real_path: &RealPath) -> String {
+        real_path.render(&Colors::new(Theme::NoColor)).to_string()
+    }
+
+    #[test]
+    fn test_real_path_of_relative_path() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let dir = tmp_dir.path().canonicalize().unwrap();
+        fs::create_dir(dir.join("sub")).unwrap();
+        fs::write(dir.join("file"), "one").unwrap();
+
+        let real_path = RealPath::from_path(&dir.join("sub/../file"));
+        assert_eq!(dir.join("file").to_string_lossy(), render(&real_path));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_real_path_of_symlink() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let dir = tmp_dir.path().canonicalize().unwrap();
+        fs::write(dir.join("target"), "one").unwrap();
+        std::os::unix::fs::symlink("target", dir.join("link")).unwrap();
+        std::os::unix::fs::symlink("missing", dir.join("broken")).unwrap();
+
+        let real_path = RealPath::from_path(&dir.join("link"));
+        assert_eq!(dir.join("target").to_string_lossy(), render(&real_path));
+        assert_eq!("-", render(&RealPath::from_path(&dir.join("broken"))));
+    }
+}
@@ -1,9 +1,13 @@
 use crate::color::{ColoredString, Colors, Elem};
 use crate::flags::{Flags, SizeFlag};
+use crate::meta::Name;
 use ansi_term::ANSIStrings;
 use std::fs::Metadata;
 use std::iter::repeat;
 
+/// The number of cells of the bar of the `size_bar` block.
+pub const BAR_WIDTH: usize = 10;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Unit {
     None,
@@ -17,48 +21,122 @@ pub enum Unit {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Size {
     bytes: u64,
+    /// The disk space allocated to the file, in bytes.
+    allocated: u64,
+    sparse: bool,
+    /// The unit to display the size in whatever its magnitude, [None] to choose it from it.
+    unit: Option<Unit>,
 }
 
 impl<'a> From<&'a Metadata> for Size {
     fn from(meta: &Metadata) -> Self {
         let len = meta.len();
-        Self { bytes: len }
+        Self {
+            bytes: len,
+            allocated: allocated(meta),
+            sparse: is_sparse(meta),
+            unit: None,
+        }
     }
 }
 
+/// Whether the file uses less disk space than its size, the 512 bytes blocks being counted.
+#[cfg(unix)]
+fn is_sparse(meta: &Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    meta.is_file() && meta.blocks() * 512 < meta.len()
+}
+
+#[cfg(windows)]
+fn is_sparse(_: &Metadata) -> bool {
+    false
+}
+
+/// The disk space allocated to the file, its 512 bytes blocks being counted.
+#[cfg(unix)]
+fn allocated(meta: &Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+
+    meta.blocks() * 512
+}
+
+#[cfg(windows)]
+fn allocated(meta: &Metadata) -> u64 {
+    meta.len()
+}
+
 impl Size {
     pub fn new(bytes: u64) -> Self {
-        Self { bytes }
+        Self {
+            bytes,
+            allocated: bytes,
+            sparse: false,
+            unit: None,
+        }
+    }
+
+    /// Get the size of the file `name` to display, in the unit configured for its extension.
+    pub fn of_name(&self, name: &Name, flags: &Flags) -> Self {
+        Self {
+            unit: flags.size_units.unit_of(name),
+            ..self.clone()
+        }
+    }
+
+    pub fn is_sparse(&self) -> bool {
+        self.sparse
     }
 
     pub fn get_bytes(&self) -> u64 {
         self.bytes
     }
 
+    pub fn get_allocated(&self) -> u64 {
+        self.allocated
+    }
+
     fn format_size(&self, number: f64) -> String {
         format!("{0:.1$}", number, if number < 10.0 { 1 } else { 0 })
     }
 
     pub fn get_unit(&self, flags: &Flags) -> Unit {
-        if self.bytes < 1024 || flags.size == SizeFlag::Bytes {
+        if flags.size == SizeFlag::Bytes {
+            return Unit::Byte;
+        }
+        if let Some(unit) = &self.unit {
+            return unit.clone();
+        }
+
+        let base = Self::base(flags);
+        if self.bytes < base {
             Unit::Byte
-        } else if self.bytes < 1024 * 1024 {
+        } else if self.bytes < base.pow(2) {
             Unit::Kilo
-        } else if self.bytes < 1024 * 1024 * 1024 {
+        } else if self.bytes < base.pow(3) {
             Unit::Mega
-        } else if self.bytes < 1024 * 1024 * 1024 * 1024 {
+        } else if self.bytes < base.pow(4) {
             Unit::Giga
         } else {
             Unit::Tera
         }
     }
 
+    /// The number of bytes of a kilo, 1000 for the SI units and 1024 otherwise.
+    fn base(flags: &Flags) -> u64 {
+        if flags.si_units.0 {
+            1000
+        } else {
+            1024
+        }
+    }
+
     pub fn render(
         &self,
         colors: &Colors,
         flags: &Flags,
         val_alignment: Option<usize>,
-    ) -> ColoredString {
+    ) -> ColoredString<'static> {
         let val_content = self.render_value(colors, flags);
         let unit_content = self.render_unit(colors, flags);
 
@@ -71,7 +149,8 @@ impl Size {
         };
 
         let mut strings: Vec<ColoredString> = vec![ColoredString::from(left_pad), val_content];
-        if flags.size != SizeFlag::Short {
+        // the exact numbers of bytes have no unit to separate
+        if flags.size == SizeFlag::Default {
             strings.push(ColoredString::from(" "));
         }
         strings.push(unit_content);
@@ -80,10 +159,12 @@ impl Size {
         ColoredString::from(res)
     }
 
-    fn paint(&self, colors: &Colors, flags: &Flags, content: String) -> ColoredString {
+    fn paint(&self, colors: &Colors, flags: &Flags, content: String) -> ColoredString<'static> {
         let unit = self.get_unit(flags);
 
-        if unit == Unit::None {
+        if flags.highlight_sparse.0 && self.is_sparse() {
+            colors.colorize(content, &Elem::SparseFile)
+        } else if unit == Unit::None {
             colors.colorize(content, &Elem::NonFile)
         } else if unit == Unit::Byte || unit == Unit::Kilo {
             colors.colorize(content, &Elem::FileSmall)
@@ -94,7 +175,7 @@ impl Size {
         }
     }
 
-    pub fn render_value(&self, colors: &Colors, flags: &Flags) -> ColoredString {
+    pub fn render_value(&self, colors: &Colors, flags: &Flags) -> ColoredString<'static> {
         let content = self.value_string(flags);
 
         self.paint(colors, flags, content)
@@ -103,32 +184,70 @@ impl Size {
     pub fn value_string(&self, flags: &Flags) -> String {
         let unit = self.get_unit(flags);
 
+        let base = Self::base(flags) as f64;
+        let scaled = |power: i32| {
+            self.format_size(((self.bytes as f64) / base.powi(power) * 10.0).round() / 10.0)
+        };
+
         match unit {
             Unit::None => "".to_string(),
             Unit::Byte => self.bytes.to_string(),
-            Unit::Kilo => self.format_size(((self.bytes as f64) / 1024.0 * 10.0).round() / 10.0),
-            Unit::Mega => {
-                self.format_size(((self.bytes as f64) / (1024.0 * 1024.0) * 10.0).round() / 10.0)
-            }
-            Unit::Giga => self.format_size(
-                ((self.bytes as f64) / (1024.0 * 1024.0 * 1024.0) * 10.0).round() / 10.0,
-            ),
-            Unit::Tera => self.format_size(
-                ((self.bytes as f64) / (1024.0 * 1024.0 * 1024.0 * 1024.0) * 10.0).round() / 10.0,
-            ),
+            Unit::Kilo => scaled(1),
+            Unit::Mega => scaled(2),
+            Unit::Giga => scaled(3),
+            Unit::Tera => scaled(4),
+        }
+    }
+
+    /// Get the size as the `total` of a listing, like `ls -l`: the number of kilo blocks by
+    /// default, the rounded size with its unit for the short sizes, and the exact number of bytes.
+    pub fn total_string(&self, flags: &Flags) -> String {
+        match flags.size {
+            SizeFlag::Default => self.bytes.div_ceil(Self::base(flags)).to_string(),
+            SizeFlag::Short => self.value_string(flags) + &self.unit_string(flags),
+            SizeFlag::Bytes => self.bytes.to_string(),
         }
     }
 
-    pub fn render_unit(&self, colors: &Colors, flags: &Flags) -> ColoredString {
+    /// Render the size as a rounded percentage of `total`.
+    pub fn render_percent(&self, colors: &Colors, flags: &Flags, total: u64) -> ColoredString<'_> {
+        let percent = (self.bytes * 100 + total / 2)
+            .checked_div(total)
+            .unwrap_or(0);
+
+        self.paint(colors, flags, format!("{:>4}", format!("{}%", percent)))
+    }
+
+    /// Render the size as a bar of [BAR_WIDTH] cells, filled in proportion to `max`.
+    pub fn render_bar(&self, colors: &Colors, flags: &Flags, max: u64) -> ColoredString<'_> {
+        let width = BAR_WIDTH as u64;
+        let filled = (self.bytes * width + max / 2)
+            .checked_div(max)
+            .unwrap_or(0)
+            .min(width) as usize;
+
+        let bar = "█".repeat(filled) + &"░".repeat(BAR_WIDTH - filled);
+        self.paint(colors, flags, bar)
+    }
+
+    pub fn render_unit(&self, colors: &Colors, flags: &Flags) -> ColoredString<'static> {
         let content = self.unit_string(flags);
 
-        self.paint(colors, flags, content)
+        // the unit is dimmed relative to the value, except for the "-" of non files
+        if self.get_unit(flags) == Unit::None {
+            self.paint(colors, flags, content)
+        } else {
+            colors.colorize(content, &Elem::SizeUnit)
+        }
     }
 
     pub fn unit_string(&self, flags: &Flags) -> String {
         let unit = self.get_unit(flags);
 
         match flags.size {
+            // the SI kilo is a lowercase k, like for `ls --si`
+            SizeFlag::Default if flags.si_units.0 && unit == Unit::Kilo => String::from("kB"),
+            SizeFlag::Short if flags.si_units.0 && unit == Unit::Kilo => String::from("k"),
             SizeFlag::Default => match unit {
                 Unit::None => String::from("-"),
                 Unit::Byte => String::from("B"),
@@ -152,9 +271,11 @@ impl Size {
 
 #[cfg(test)]
 mod test {
-    use super::Size;
-    use crate::color::{Colors, Theme};
-    use crate::flags::{Flags, SizeFlag};
+    use super::{Size, Unit};
+    use crate::color::{Colors, Elem, Theme};
+    use crate::flags::{Flags, SiUnits, SizeFlag, SizeUnits};
+    use crate::meta::{FileType, Name};
+    use std::path::Path;
 
     #[test]
     fn render_byte() {
@@ -170,6 +291,46 @@ mod test {
         assert_eq!(size.unit_string(&flags).as_str(), "");
     }
 
+    #[test]
+    fn render_si_kilobyte() {
+        let size = Size::new(1500);
+        let mut flags = Flags {
+            si_units: SiUnits(true),
+            ..Flags::default()
+        };
+
+        assert_eq!(size.value_string(&flags).as_str(), "1.5");
+        assert_eq!(size.unit_string(&flags).as_str(), "kB");
+        flags.size = SizeFlag::Short;
+        assert_eq!(size.unit_string(&flags).as_str(), "k");
+    }
+
+    #[test]
+    fn render_total() {
+        let size = Size::new(49152);
+        let mut flags = Flags::default();
+
+        assert_eq!(size.total_string(&flags).as_str(), "48");
+        flags.size = SizeFlag::Short;
+        assert_eq!(size.total_string(&flags).as_str(), "48K");
+        flags.size = SizeFlag::Bytes;
+        assert_eq!(size.total_string(&flags).as_str(), "49152");
+    }
+
+    #[test]
+    fn render_si_megabyte() {
+        let size = Size::new(2_500_000);
+        let flags = Flags {
+            si_units: SiUnits(true),
+            ..Flags::default()
+        };
+
+        assert_eq!(Unit::Byte, Size::new(999).get_unit(&flags));
+        assert_eq!(Unit::Kilo, Size::new(1000).get_unit(&flags));
+        assert_eq!(size.value_string(&flags).as_str(), "2.5");
+        assert_eq!(size.unit_string(&flags).as_str(), "MB");
+    }
+
     #[test]
     fn render_10_minus_kilobyte() {
         let size = Size::new(4 * 1024); // 4 kilobytes
@@ -330,4 +491,114 @@ mod test {
         assert_eq!(size.render(&colors, &flags, Some(2)).to_string(), "42K");
         assert_eq!(size.render(&colors, &flags, Some(3)).to_string(), " 42K");
     }
+
+    #[test]
+    fn render_unit_with_distinct_color() {
+        let size = Size::new(42 * 1024); // 42 kilobytes
+        let flags = Flags::default();
+        let colors = Colors::new(Theme::NoLscolors);
+
+        let value = colors.colorize(String::from("42"), &Elem::FileSmall);
+        let unit = colors.colorize(String::from("KB"), &Elem::SizeUnit);
+        assert_ne!(value.style_ref(), unit.style_ref());
+
+        assert_eq!(size.render_value(&colors, &flags), value);
+        assert_eq!(size.render_unit(&colors, &flags), unit);
+        assert_eq!(
+            size.render(&colors, &flags, None).to_string(),
+            format!("{} {}", value, unit)
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn render_sparse_file() {
+        use crate::flags::HighlightSparse;
+        use std::fs::File;
+        use std::io::Write;
+
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+
+        let sparse_path = tmp_dir.path().join("disk.img");
+        let sparse_file = File::create(&sparse_path).expect("failed to create file");
+        sparse_file
+            .set_len(16 * 1024 * 1024)
+            .expect("failed to set file length");
+        let sparse = Size::from(&sparse_path.metadata().unwrap());
+
+        let dense_path = tmp_dir.path().join("dense");
+        let mut dense_file = File::create(&dense_path).expect("failed to create file");
+        dense_file
+            .write_all(&[1; 8192])
+            .expect("failed to write file");
+        dense_file.sync_all().expect("failed to sync file");
+        let dense = Size::from(&dense_path.metadata().unwrap());
+
+        assert!(sparse.is_sparse());
+        assert!(!dense.is_sparse());
+
+        let colors = Colors::new(Theme::NoLscolors);
+        let mut flags = Flags {
+            highlight_sparse: HighlightSparse(true),
+            ..Flags::default()
+        };
+        assert_eq!(
+            colors.colorize(String::from("16"), &Elem::SparseFile),
+            sparse.render_value(&colors, &flags)
+        );
+        assert_eq!(
+            colors.colorize(String::from("8.0"), &Elem::FileSmall),
+            dense.render_value(&colors, &flags)
+        );
+
+        // the sparse files are not highlighted without the flag
+        flags.highlight_sparse = HighlightSparse(false);
+        assert_eq!(
+            colors.colorize(String::from("16"), &Elem::FileMedium),
+            sparse.render_value(&colors, &flags)
+        );
+    }
+
+    #[test]
+    fn render_bar() {
+        let colors = Colors::new(Theme::NoColor);
+        let flags = Flags::default();
+
+        assert_eq!(
+            "██████████",
+            Size::new(800).render_bar(&colors, &flags, 800).to_string()
+        );
+        assert_eq!(
+            "███░░░░░░░",
+            Size::new(240).render_bar(&colors, &flags, 800).to_string()
+        );
+        assert_eq!(
+            "░░░░░░░░░░",
+            Size::new(0).render_bar(&colors, &flags, 0).to_string()
+        );
+    }
+
+    #[test]
+    fn render_size_unit_of_extension() {
+        let mut flags = Flags {
+            size_units: SizeUnits(vec![("mp4".to_string(), Unit::Mega)]),
+            ..Flags::default()
+        };
+        let file = FileType::File {
+            uid: false,
+            exec: false,
+        };
+        let size = Size::new(420 * 1024); // 420 kilobytes
+
+        let clip = size.of_name(&Name::new(Path::new("clip.mp4"), file), &flags);
+        assert_eq!(clip.value_string(&flags).as_str(), "0.4");
+        assert_eq!(clip.unit_string(&flags).as_str(), "MB");
+
+        let notes = size.of_name(&Name::new(Path::new("notes.txt"), file), &flags);
+        assert_eq!(notes.value_string(&flags).as_str(), "420");
+        assert_eq!(notes.unit_string(&flags).as_str(), "KB");
+
+        flags.size = SizeFlag::Bytes;
+        assert_eq!(clip.value_string(&flags).as_str(), "430080");
+    }
 }
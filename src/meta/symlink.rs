@@ -1,13 +1,48 @@
 use crate::color::{ColoredString, Colors, Elem};
-use crate::flags::Flags;
+use crate::flags::{Flags, Glyphs, TimeFlag};
+use crate::icon::Icons;
+use crate::meta::name::DisplayOption;
+use crate::meta::{Meta, Name, UsersCache};
 use ansi_term::{ANSIString, ANSIStrings};
 use std::fs::read_link;
-use std::path::Path;
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
 
 #[derive(Clone, Debug)]
 pub struct SymLink {
     target: Option<String>,
-    valid: bool,
+    state: TargetState,
+}
+
+/// Whether the target of a symlink, or the content of a directory, can be reached, and why not.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TargetState {
+    Valid,
+    Broken,
+    Loop,
+    NoAccess,
+}
+
+impl TargetState {
+    /// Get the configured glyph marking the state, empty for the reachable targets.
+    pub fn glyph(self, glyphs: &Glyphs) -> &str {
+        match self {
+            Self::Valid => "",
+            Self::Broken => &glyphs.broken,
+            Self::Loop => &glyphs.cycle,
+            Self::NoAccess => &glyphs.no_access,
+        }
+    }
+
+    fn of(target: &Path) -> Self {
+        match target.metadata() {
+            Ok(_) => Self::Valid,
+            Err(err) if err.kind() == ErrorKind::PermissionDenied => Self::NoAccess,
+            #[cfg(unix)]
+            Err(err) if err.raw_os_error() == Some(libc::ELOOP) => Self::Loop,
+            Err(_) => Self::Broken,
+        }
+    }
 }
 
 impl<'a> From<&'a Path> for SymLink {
@@ -15,7 +50,7 @@ impl<'a> From<&'a Path> for SymLink {
         if let Ok(target) = read_link(path) {
             if target.is_absolute() || path.parent() == None {
                 return Self {
-                    valid: target.exists(),
+                    state: TargetState::of(&target),
                     target: Some(
                         target
                             .to_str()
@@ -32,18 +67,27 @@ impl<'a> From<&'a Path> for SymLink {
                         .expect("failed to convert symlink to str")
                         .to_string(),
                 ),
-                valid: path.parent().unwrap().join(target).exists(),
+                state: TargetState::of(&path.parent().unwrap().join(target)),
             };
         }
 
         Self {
             target: None,
-            valid: false,
+            state: TargetState::Broken,
         }
     }
 }
 
 impl SymLink {
+    pub fn new(target: Option<String>, valid: bool) -> Self {
+        let state = if valid {
+            TargetState::Valid
+        } else {
+            TargetState::Broken
+        };
+        Self { target, state }
+    }
+
     pub fn symlink_string(&self) -> Option<String> {
         if let Some(ref target) = self.target {
             Some(target.to_string())
@@ -52,12 +96,47 @@ impl SymLink {
         }
     }
 
+    /// Resolve an absolute target against the `root` directory, returning the rebased target and
+    /// its state. Relative targets are left untouched, returning [None], like the targets whose
+    /// `..` components lead out of the `root`.
+    fn rebase(&self, root: &Path) -> Option<(String, TargetState)> {
+        let target = Path::new(self.target.as_ref()?);
+        if !target.is_absolute() {
+            return None;
+        }
+
+        // the `..` are resolved lexically, without following the symlinks under the root
+        let mut relative = PathBuf::new();
+        for component in target.components() {
+            match component {
+                Component::ParentDir if !relative.pop() => return None,
+                Component::Normal(name) => relative.push(name),
+                _ => (),
+            }
+        }
+        let rebased = root.join(relative);
+
+        let state = TargetState::of(&rebased);
+        Some((rebased.to_string_lossy().to_string(), state))
+    }
+
     pub fn render(&self, colors: &Colors, flag: &Flags) -> ColoredString {
         if let Some(target_string) = self.symlink_string() {
-            let elem = if self.valid {
-                &Elem::SymLink
-            } else {
-                &Elem::BrokenSymLink
+            let rebased = flag
+                .symlink_root
+                .0
+                .as_ref()
+                .and_then(|root| self.rebase(root));
+            let (target_string, state) = rebased.unwrap_or((target_string, self.state));
+
+            // the unreachable targets are marked by the glyph of their state, when configured
+            let elem = match state {
+                TargetState::Valid => &Elem::SymLink,
+                _ => &Elem::MissingTarget,
+            };
+            let target_string = match state.glyph(&flag.glyphs) {
+                "" => target_string,
+                glyph => format!("{} {}", target_string, glyph),
             };
 
             let strings: &[ColoredString] = &[
@@ -71,21 +150,63 @@ impl SymLink {
             ANSIString::from("")
         }
     }
+
+    /// Render the target of the `link` like a listed entry, with the color and the icon of its
+    /// type. The unreachable or rebased targets are rendered like by [render](Self::render).
+    pub fn render_styled(
+        &self,
+        link: &Path,
+        colors: &Colors,
+        icons: &Icons,
+        flag: &Flags,
+    ) -> ColoredString<'_> {
+        let target = match &self.target {
+            Some(target) if self.state == TargetState::Valid && flag.symlink_root.0.is_none() => {
+                target
+            }
+            _ => return self.render(colors, flag),
+        };
+
+        // the name is built from the resolved path, for its hyperlink to lead to the target
+        let (resolved, display_option) = match link.parent() {
+            Some(parent) if Path::new(target).is_relative() => (
+                parent.join(target),
+                DisplayOption::Relative { base_path: parent },
+            ),
+            _ => (PathBuf::from(target), DisplayOption::None),
+        };
+        // only the file type is needed, the numeric owners skipping the name lookups
+        let users = UsersCache::new();
+        let file_type =
+            match Meta::from_path_with(&resolved, true, true, TimeFlag::Modified, &users) {
+                Ok(meta) => meta.file_type,
+                Err(_) => return self.render(colors, flag),
+            };
+
+        let name = Name::new(&resolved, file_type);
+        let strings: &[ColoredString] = &[
+            ColoredString::from(format!(" {} ", flag.symlink_arrow)),
+            name.render(colors, icons, &display_option, flag),
+        ];
+
+        ColoredString::from(ANSIStrings(strings).to_string())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::SymLink;
+    use super::{SymLink, TargetState};
     use crate::app;
-    use crate::color::{Colors, Theme};
+    use crate::color::{Colors, Elem, Theme};
     use crate::config_file::Config;
     use crate::flags::Flags;
+    use crate::meta::UsersCache;
 
     #[test]
     fn test_symlink_render_default_valid_target_nocolor() {
         let link = SymLink {
             target: Some("/target".to_string()),
-            valid: true,
+            state: TargetState::Valid,
         };
         let argv = vec!["lsd"];
         let matches = app::build().get_matches_from_safe(argv).unwrap();
@@ -103,7 +224,7 @@ mod tests {
     fn test_symlink_render_default_invalid_target_nocolor() {
         let link = SymLink {
             target: Some("/target".to_string()),
-            valid: false,
+            state: TargetState::Broken,
         };
         let argv = vec!["lsd"];
         let matches = app::build().get_matches_from_safe(argv).unwrap();
@@ -116,4 +237,207 @@ mod tests {
             .to_string()
         );
     }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_symlink_render_rebased_absolute_target() {
+        let root = tempfile::tempdir().expect("failed to create temp dir");
+        std::fs::create_dir_all(root.path().join("usr/lib")).expect("failed to create dir");
+        std::fs::File::create(root.path().join("usr/lib/real")).expect("failed to create file");
+        let root_str = root.path().to_str().unwrap();
+
+        let argv = vec!["lsd", "--symlink-root", root_str];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+        let colors = Colors::new(Theme::NoLscolors);
+        let rebased = |target: &str| root.path().join(target).to_string_lossy().to_string();
+
+        // the target does not exist on the real root, but does under the symlink root
+        let link = SymLink {
+            target: Some("/usr/lib/real".to_string()),
+            state: TargetState::Broken,
+        };
+        assert_eq!(
+            format!(
+                " ⇒ {}",
+                colors.colorize(rebased("usr/lib/real"), &Elem::SymLink)
+            ),
+            link.render(&colors, &flags).to_string()
+        );
+
+        let link = SymLink {
+            target: Some("/usr/lib/missing".to_string()),
+            state: TargetState::Valid,
+        };
+        assert_eq!(
+            format!(
+                " ⇒ {}",
+                colors.colorize(rebased("usr/lib/missing"), &Elem::MissingTarget)
+            ),
+            link.render(&colors, &flags).to_string()
+        );
+
+        // the `..` are resolved within the root, the targets leaving it are left untouched
+        let link = SymLink {
+            target: Some("/usr/share/../lib/real".to_string()),
+            state: TargetState::Broken,
+        };
+        assert_eq!(
+            format!(
+                " ⇒ {}",
+                colors.colorize(rebased("usr/lib/real"), &Elem::SymLink)
+            ),
+            link.render(&colors, &flags).to_string()
+        );
+
+        let link = SymLink {
+            target: Some("/usr/../../etc/passwd".to_string()),
+            state: TargetState::Valid,
+        };
+        assert_eq!(
+            format!(
+                " ⇒ {}",
+                colors.colorize("/usr/../../etc/passwd".to_string(), &Elem::SymLink)
+            ),
+            link.render(&colors, &flags).to_string()
+        );
+
+        // relative targets are left untouched
+        let link = SymLink {
+            target: Some("lib/real".to_string()),
+            state: TargetState::Valid,
+        };
+        assert_eq!(
+            format!(
+                " ⇒ {}",
+                colors.colorize("lib/real".to_string(), &Elem::SymLink)
+            ),
+            link.render(&colors, &flags).to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_symlink_render_missing_target() {
+        use crate::icon::{self, Icons};
+        use crate::meta::name::DisplayOption;
+        use crate::meta::Meta;
+        use ansi_term::Colour;
+
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let link_path = tmp_dir.path().join("dangling");
+        std::os::unix::fs::symlink("missing", &link_path).expect("failed to create symlink");
+
+        let meta = Meta::from_path(&link_path, false, &UsersCache::new()).unwrap();
+        let colors = Colors::new(Theme::NoLscolors);
+        let flags = Flags::default();
+
+        let name = meta
+            .name
+            .render(
+                &colors,
+                &Icons::new(icon::Theme::NoIcon, " ".to_string()),
+                &DisplayOption::FileName,
+                &flags,
+            )
+            .to_string();
+        let target = meta.symlink.render(&colors, &flags).to_string();
+
+        assert_eq!(Colour::Fixed(44).paint("dangling").to_string(), name);
+        assert_eq!(
+            format!(
+                " {} {}",
+                flags.symlink_arrow,
+                Colour::Fixed(203).paint("missing")
+            ),
+            target
+        );
+    }
+
+    #[test]
+    fn test_symlink_render_configured_glyphs() {
+        let mut config = Config::with_none();
+        config.glyphs = Some(crate::config_file::Glyphs {
+            no_access: Some("N".into()),
+            broken: Some("B".into()),
+            cycle: Some("L".into()),
+        });
+        let matches = app::build().get_matches_from_safe(vec!["lsd"]).unwrap();
+        let flags = Flags::configure_from(&matches, &config).unwrap();
+
+        for (state, expected) in &[
+            (TargetState::Valid, " ⇒ /target"),
+            (TargetState::Broken, " ⇒ /target B"),
+            (TargetState::Loop, " ⇒ /target L"),
+            (TargetState::NoAccess, " ⇒ /target N"),
+        ] {
+            let link = SymLink {
+                target: Some("/target".to_string()),
+                state: *state,
+            };
+            assert_eq!(
+                *expected,
+                link.render(&Colors::new(Theme::NoColor), &flags)
+                    .to_string()
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_symlink_loop_state() {
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let link_path = tmp_dir.path().join("loop");
+        std::os::unix::fs::symlink("loop", &link_path).expect("failed to create symlink");
+
+        let link = SymLink::from(link_path.as_path());
+        assert_eq!(TargetState::Loop, link.state);
+        assert_eq!(
+            " ⇒ loop",
+            link.render(&Colors::new(Theme::NoColor), &Flags::default())
+                .to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_symlink_render_styled_target() {
+        use crate::icon::{self, Icons};
+        use crate::meta::Meta;
+        use ansi_term::Colour;
+
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        std::fs::create_dir(tmp_dir.path().join("target.d")).expect("failed to create dir");
+        std::fs::File::create(tmp_dir.path().join("main.rs")).expect("failed to create file");
+        let colors = Colors::new(Theme::NoLscolors);
+        let icons = Icons::new(icon::Theme::Fancy, " ".to_string());
+        let flags = Flags::default();
+
+        for (target, expected) in &[
+            ("main.rs", Colour::Fixed(184).paint("\u{e7a8} main.rs")),
+            ("target.d", Colour::Fixed(33).paint("\u{f115} target.d")),
+        ] {
+            let link_path = tmp_dir.path().join(format!("{}.link", target));
+            std::os::unix::fs::symlink(target, &link_path).expect("failed to create symlink");
+            let meta = Meta::from_path(&link_path, false, &UsersCache::new()).unwrap();
+
+            assert_eq!(
+                format!(" {} {}", flags.symlink_arrow, expected),
+                meta.symlink
+                    .render_styled(&link_path, &colors, &icons, &flags)
+                    .to_string()
+            );
+        }
+
+        // the missing targets keep their own color
+        let link_path = tmp_dir.path().join("dangling");
+        std::os::unix::fs::symlink("missing", &link_path).expect("failed to create symlink");
+        let meta = Meta::from_path(&link_path, false, &UsersCache::new()).unwrap();
+        assert_eq!(
+            meta.symlink.render(&colors, &flags).to_string(),
+            meta.symlink
+                .render_styled(&link_path, &colors, &icons, &flags)
+                .to_string()
+        );
+    }
 }
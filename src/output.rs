@@ -0,0 +1,655 @@
+use crate::color::{self, Colors};
+use crate::display;
+use crate::flags::{Block, Flags, HyperlinkOption};
+use crate::icon::{self, Icons};
+use crate::meta::{FileType, Meta};
+
+/// Print the listing as CSV, with a header row naming the path column and the blocks, then one
+/// row per entry. The content of the directories is flattened after them, the path column
+/// telling the entries apart.
+pub fn csv(metas: &[Meta], flags: &Flags) -> String {
+    // the fields are plain text, without the escape sequences of the hyperlinks
+    let flags = &Flags {
+        hyperlink: HyperlinkOption::Never,
+        ..flags.clone()
+    };
+    let colors = Colors::new(color::Theme::NoColor);
+    let icons = Icons::new(icon::Theme::NoIcon, String::new());
+    let line_ending = flags.line_ending.as_str();
+
+    let mut output = String::new();
+    let header = std::iter::once("path").chain(flags.blocks.0.iter().map(|block| block.as_str()));
+    push_record(&mut output, header, line_ending);
+    push_rows(&mut output, metas, flags, &colors, &icons, 0);
+
+    output
+}
+
+fn push_rows(
+    output: &mut String,
+    metas: &[Meta],
+    flags: &Flags,
+    colors: &Colors,
+    icons: &Icons,
+    depth: usize,
+) {
+    let line_ending = flags.line_ending.as_str();
+    let rows = display::plain_blocks(metas, flags, colors, icons, depth);
+
+    for (meta, row) in metas.iter().zip(rows) {
+        // the directories given by the user are replaced by their content, as in the grid
+        if depth > 0 || meta.content.is_none() {
+            let path = meta.path.to_string_lossy();
+            let fields = std::iter::once(path.as_ref()).chain(row.iter().map(String::as_str));
+            push_record(output, fields, line_ending);
+        }
+
+        if let Some(content) = &meta.content {
+            push_rows(output, content, flags, colors, icons, depth + 1);
+        }
+    }
+}
+
+fn push_record<'a, I>(output: &mut String, fields: I, line_ending: &str)
+where
+    I: Iterator<Item = &'a str>,
+{
+    let fields: Vec<String> = fields.map(escape).collect();
+    *output += &fields.join(",");
+    *output += line_ending;
+}
+
+/// Print the listing as a JSON array of one object per entry, the content of the directories
+/// nested in their `content` array.
+pub fn json(metas: &[Meta], flags: &Flags) -> String {
+    let colors = Colors::new(color::Theme::NoColor);
+
+    let mut output = String::new();
+    push_array(&mut output, metas, flags, &colors);
+    output += flags.line_ending.as_str();
+
+    output
+}
+
+fn push_array(output: &mut String, metas: &[Meta], flags: &Flags, colors: &Colors) {
+    output.push('[');
+    for (idx, meta) in metas.iter().enumerate() {
+        if idx > 0 {
+            output.push(',');
+        }
+        push_object(output, meta, flags, colors);
+    }
+    output.push(']');
+}
+
+fn push_object(output: &mut String, meta: &Meta, flags: &Flags, colors: &Colors) {
+    let fields = [
+        ("name", escape_json(&meta.name.name)),
+        ("path", escape_json(&meta.path.to_string_lossy())),
+        ("size", meta.size.get_bytes().to_string()),
+        (
+            "permissions",
+            escape_json(&meta.permissions.render(colors).to_string()),
+        ),
+        (
+            "owner",
+            escape_json(&meta.owner.render_user(colors, flags).to_string()),
+        ),
+        (
+            "group",
+            escape_json(&meta.owner.render_group(colors, flags).to_string()),
+        ),
+        ("file_type", escape_json(file_type_name(meta.file_type))),
+        ("modified", meta.date.timestamp().to_string()),
+    ];
+
+    output.push('{');
+    for (name, value) in &fields {
+        *output += &format!("\"{}\":{},", name, value);
+    }
+    output.pop();
+    if let Some(content) = &meta.content {
+        *output += ",\"content\":";
+        push_array(output, content, flags, colors);
+    }
+    output.push('}');
+}
+
+/// Print the listing as one literal name per line, without colors, icons, escapes nor alignment.
+/// The directories given by the user are replaced by their content, and the entries of the
+/// recursion are named by their path from the listed directory.
+pub fn raw(metas: &[Meta], flags: &Flags) -> String {
+    let mut output = String::new();
+    push_names(&mut output, metas, flags, None);
+    output
+}
+
+fn push_names(output: &mut String, metas: &[Meta], flags: &Flags, parent: Option<&str>) {
+    for meta in metas {
+        let name = match parent {
+            Some(_) if flags.full_path.0 => meta.path.to_string_lossy().into_owned(),
+            Some(parent) => format!("{}{}", parent, meta.name.name),
+            None => meta.path.to_string_lossy().into_owned(),
+        };
+
+        match &meta.content {
+            Some(content) => {
+                if parent.is_some() {
+                    *output += &name;
+                    *output += flags.line_ending.as_str();
+                }
+                let prefix = match parent {
+                    Some(_) => format!("{}{}", name, std::path::MAIN_SEPARATOR),
+                    None => String::new(),
+                };
+                push_names(output, content, flags, Some(&prefix));
+            }
+            None => {
+                *output += &name;
+                *output += flags.line_ending.as_str();
+            }
+        }
+    }
+}
+
+/// Print the listing as an HTML table, with a header row naming the path column and the blocks,
+/// then one row per entry like [csv]. The colors are translated to inline CSS styles and the
+/// icons are wrapped in their own spans.
+pub fn html(metas: &[Meta], flags: &Flags, colors: &Colors, icons: &Icons) -> String {
+    // the cells are styled by the spans, without the escape sequences of the hyperlinks
+    let flags = &Flags {
+        hyperlink: HyperlinkOption::Never,
+        ..flags.clone()
+    };
+    let line_ending = flags.line_ending.as_str();
+
+    let mut output = String::from("<table class=\"lsd\">");
+    output += line_ending;
+    output += "<tr><th>path</th>";
+    for block in &flags.blocks.0 {
+        output += &format!("<th>{}</th>", block.as_str());
+    }
+    output += "</tr>";
+    output += line_ending;
+    push_html_rows(&mut output, metas, flags, colors, icons, 0);
+    output += "</table>";
+    output += line_ending;
+
+    output
+}
+
+fn push_html_rows(
+    output: &mut String,
+    metas: &[Meta],
+    flags: &Flags,
+    colors: &Colors,
+    icons: &Icons,
+    depth: usize,
+) {
+    let line_ending = flags.line_ending.as_str();
+    // the names are rendered without their icons, which get their own spans
+    let no_icons = Icons::new(icon::Theme::NoIcon, String::new());
+    let rows = display::plain_blocks(metas, flags, colors, &no_icons, depth);
+    let name_column = flags
+        .blocks
+        .0
+        .iter()
+        .position(|block| *block == Block::Name);
+
+    for (meta, row) in metas.iter().zip(rows) {
+        // the directories given by the user are replaced by their content, as in the grid
+        if depth > 0 || meta.content.is_none() {
+            *output += "<tr><td>";
+            *output += &escape_html(&meta.path.to_string_lossy());
+            *output += "</td>";
+            let icon = icons.get(&meta.name);
+            for (column, cell) in row.iter().enumerate() {
+                *output += "<td>";
+                if Some(column) == name_column && !icon.trim().is_empty() {
+                    *output += &format!(
+                        "<span class=\"icon\">{}</span> ",
+                        escape_html(icon.trim_end())
+                    );
+                }
+                *output += &ansi_to_html(cell);
+                *output += "</td>";
+            }
+            *output += "</tr>";
+            *output += line_ending;
+        }
+
+        if let Some(content) = &meta.content {
+            push_html_rows(output, content, flags, colors, icons, depth + 1);
+        }
+    }
+}
+
+/// The style of the text set by the SGR escape sequences, for [ansi_to_html].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct HtmlStyle {
+    foreground: Option<String>,
+    background: Option<String>,
+    bold: bool,
+    dimmed: bool,
+    italic: bool,
+    underline: bool,
+    strikethrough: bool,
+}
+
+impl HtmlStyle {
+    /// Apply the `codes` of an SGR escape sequence, the unsupported ones being ignored.
+    fn apply(&mut self, codes: &[u8]) {
+        let mut codes = codes.iter().copied();
+        while let Some(code) = codes.next() {
+            match code {
+                0 => *self = Self::default(),
+                1 => self.bold = true,
+                2 => self.dimmed = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                9 => self.strikethrough = true,
+                22 => {
+                    self.bold = false;
+                    self.dimmed = false;
+                }
+                23 => self.italic = false,
+                24 => self.underline = false,
+                29 => self.strikethrough = false,
+                30..=37 => self.foreground = Some(css_colour(code - 30)),
+                38 => self.foreground = extended_colour(&mut codes),
+                39 => self.foreground = None,
+                40..=47 => self.background = Some(css_colour(code - 40)),
+                48 => self.background = extended_colour(&mut codes),
+                49 => self.background = None,
+                90..=97 => self.foreground = Some(css_colour(code - 90 + 8)),
+                100..=107 => self.background = Some(css_colour(code - 100 + 8)),
+                _ => {}
+            }
+        }
+    }
+
+    /// Get the inline CSS declarations of the style, empty for the plain text.
+    fn css(&self) -> String {
+        let mut declarations = Vec::new();
+        if let Some(colour) = &self.foreground {
+            declarations.push(format!("color:{}", colour));
+        }
+        if let Some(colour) = &self.background {
+            declarations.push(format!("background-color:{}", colour));
+        }
+        if self.bold {
+            declarations.push(String::from("font-weight:bold"));
+        }
+        if self.dimmed {
+            declarations.push(String::from("opacity:0.5"));
+        }
+        if self.italic {
+            declarations.push(String::from("font-style:italic"));
+        }
+        match (self.underline, self.strikethrough) {
+            (true, true) => {
+                declarations.push(String::from("text-decoration:underline line-through"))
+            }
+            (true, false) => declarations.push(String::from("text-decoration:underline")),
+            (false, true) => declarations.push(String::from("text-decoration:line-through")),
+            (false, false) => {}
+        }
+        declarations.join(";")
+    }
+}
+
+/// Get the CSS colour of the `5;n` or `2;r;g;b` codes following a 38 or a 48 SGR code.
+fn extended_colour<I: Iterator<Item = u8>>(codes: &mut I) -> Option<String> {
+    match codes.next()? {
+        5 => Some(css_colour(codes.next()?)),
+        2 => Some(format!(
+            "#{:02x}{:02x}{:02x}",
+            codes.next()?,
+            codes.next()?,
+            codes.next()?
+        )),
+        _ => None,
+    }
+}
+
+/// Get the CSS colour of the `index` of the 256 colors palette of xterm.
+fn css_colour(index: u8) -> String {
+    const BASIC: [&str; 16] = [
+        "#000000", "#800000", "#008000", "#808000", "#000080", "#800080", "#008080", "#c0c0c0",
+        "#808080", "#ff0000", "#00ff00", "#ffff00", "#0000ff", "#ff00ff", "#00ffff", "#ffffff",
+    ];
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    match index {
+        0..=15 => BASIC[index as usize].to_string(),
+        16..=231 => {
+            let cube = index - 16;
+            format!(
+                "#{:02x}{:02x}{:02x}",
+                LEVELS[(cube / 36) as usize],
+                LEVELS[(cube / 6 % 6) as usize],
+                LEVELS[(cube % 6) as usize]
+            )
+        }
+        _ => {
+            let grey = 8 + (index - 232) * 10;
+            format!("#{:02x}{:02x}{:02x}", grey, grey, grey)
+        }
+    }
+}
+
+/// Translate the SGR escape sequences of the `styled` text into spans of inline CSS styles, its
+/// text being escaped.
+fn ansi_to_html(styled: &str) -> String {
+    let mut html = String::new();
+    let mut style = HtmlStyle::default();
+    let mut text = String::new();
+
+    let flush = |html: &mut String, text: &mut String, style: &HtmlStyle| {
+        if text.is_empty() {
+            return;
+        }
+        let css = style.css();
+        if css.is_empty() {
+            *html += &escape_html(text);
+        } else {
+            *html += &format!("<span style=\"{}\">{}</span>", css, escape_html(text));
+        }
+        text.clear();
+    };
+
+    let mut rest = styled;
+    while let Some(start) = rest.find("\u{1b}[") {
+        text += &rest[..start];
+        let sequence = &rest[start + 2..];
+        let terminator = sequence
+            .char_indices()
+            .find(|(_, c)| !c.is_ascii_digit() && *c != ';');
+        match terminator {
+            Some((end, 'm')) => {
+                flush(&mut html, &mut text, &style);
+                let codes: Vec<u8> = sequence[..end]
+                    .split(';')
+                    .map(|code| code.parse().unwrap_or(0))
+                    .collect();
+                style.apply(&codes);
+                rest = &sequence[end + 1..];
+            }
+            // the other sequences are left out
+            Some((end, c)) => rest = &sequence[end + c.len_utf8()..],
+            None => rest = "",
+        }
+    }
+    text += rest;
+    flush(&mut html, &mut text, &style);
+
+    html
+}
+
+/// Escape the characters of the `text` having a meaning in HTML.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped += "&amp;",
+            '<' => escaped += "&lt;",
+            '>' => escaped += "&gt;",
+            '"' => escaped += "&quot;",
+            '\'' => escaped += "&#39;",
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn file_type_name(file_type: FileType) -> &'static str {
+    match file_type {
+        FileType::BlockDevice => "block-device",
+        FileType::CharDevice => "char-device",
+        FileType::Directory { .. } => "directory",
+        FileType::File { .. } => "file",
+        FileType::SymLink { .. } => "symlink",
+        FileType::Pipe => "pipe",
+        FileType::Socket => "socket",
+        FileType::Special => "special",
+    }
+}
+
+/// Quote the `value` as a JSON string, escaping its quotes, backslashes and control characters.
+fn escape_json(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped += "\\\"",
+            '\\' => escaped += "\\\\",
+            '\n' => escaped += "\\n",
+            '\r' => escaped += "\\r",
+            '\t' => escaped += "\\t",
+            c if (c as u32) < 0x20 => escaped += &format!("\\u{:04x}", c as u32),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Quote the `field` holding a comma, a quote or a line break, doubling its quotes.
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ansi_to_html, css_colour, csv, escape, escape_json, html, json, raw};
+    use crate::app;
+    use crate::color::{self, Colors};
+    use crate::config_file::Config;
+    use crate::flags::Flags;
+    use crate::icon::{self, Icons};
+    use crate::meta::Meta;
+    use crate::meta::UsersCache;
+    use ansi_term::Colour;
+    use std::fs;
+    use tempfile::tempdir;
+
+    /// Split the CSV `input` into its records of unquoted fields.
+    fn parse(input: &str) -> Vec<Vec<String>> {
+        let mut records = Vec::new();
+        let mut record = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+
+        let mut chars = input.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (c, quoted) {
+                ('"', true) if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                ('"', _) => quoted = !quoted,
+                (',', false) => record.push(std::mem::take(&mut field)),
+                ('\n', false) => {
+                    record.push(std::mem::take(&mut field));
+                    records.push(std::mem::take(&mut record));
+                }
+                _ => field.push(c),
+            }
+        }
+
+        records
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!("plain", escape("plain"));
+        assert_eq!("\"a,b\"", escape("a,b"));
+        assert_eq!("\"say \"\"hi\"\"\"", escape("say \"hi\""));
+        assert_eq!("\"two\nlines\"", escape("two\nlines"));
+    }
+
+    #[test]
+    fn test_csv() {
+        let argv = vec![
+            "lsd",
+            "--format",
+            "csv",
+            "--blocks",
+            "name,size",
+            "--size",
+            "bytes",
+        ];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("a,\"b\".txt");
+        fs::write(&file_path, "abc").unwrap();
+
+        let mut meta = Meta::from_path(tmp_dir.path(), false, &UsersCache::new()).unwrap();
+        meta.content = meta.recurse_into(1, &flags, &UsersCache::new()).unwrap();
+
+        let records = parse(&csv(&[meta], &flags));
+        assert_eq!(
+            vec![
+                vec!["path", "name", "size"],
+                vec![file_path.to_str().unwrap(), "a,\"b\".txt", "3"],
+            ],
+            records
+        );
+    }
+
+    #[test]
+    fn test_escape_json() {
+        assert_eq!("\"plain\"", escape_json("plain"));
+        assert_eq!("\"say \\\"hi\\\"\"", escape_json("say \"hi\""));
+        assert_eq!("\"C:\\\\dir\"", escape_json("C:\\dir"));
+        assert_eq!("\"two\\nlines\\u0001\"", escape_json("two\nlines\u{1}"));
+    }
+
+    #[test]
+    fn test_json() {
+        let argv = vec!["lsd", "--json", "--tree"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        fs::create_dir(tmp_dir.path().join("sub")).unwrap();
+        let file_path = tmp_dir.path().join("sub").join("a \"b\".txt");
+        fs::write(&file_path, "abc").unwrap();
+
+        let mut meta = Meta::from_path(tmp_dir.path(), false, &UsersCache::new()).unwrap();
+        meta.content = meta.recurse_into(42, &flags, &UsersCache::new()).unwrap();
+
+        // JSON is a subset of YAML, so the output parses as a YAML sequence
+        let output = json(&[meta], &flags);
+        let value: serde_yaml::Value = serde_yaml::from_str(&output).unwrap();
+
+        let sub = &value[0]["content"][0];
+        assert_eq!("sub", sub["name"].as_str().unwrap());
+        assert_eq!("directory", sub["file_type"].as_str().unwrap());
+
+        let file = &sub["content"][0];
+        assert_eq!("a \"b\".txt", file["name"].as_str().unwrap());
+        assert_eq!(file_path.to_str().unwrap(), file["path"].as_str().unwrap());
+        assert_eq!("file", file["file_type"].as_str().unwrap());
+        assert_eq!(3, file["size"].as_u64().unwrap());
+        assert!(file["modified"].as_i64().unwrap() > 0);
+        assert!(file["content"].is_null());
+        #[cfg(unix)]
+        assert_eq!(9, file["permissions"].as_str().unwrap().len());
+    }
+
+    #[test]
+    fn test_raw() {
+        let argv = vec!["lsd", "--raw", "--recursive"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        fs::create_dir(tmp_dir.path().join("sub")).unwrap();
+        fs::write(tmp_dir.path().join("sub").join("tab\there"), "abc").unwrap();
+        fs::write(tmp_dir.path().join("\u{1b}[31mred"), "abc").unwrap();
+
+        let mut meta = Meta::from_path(tmp_dir.path(), false, &UsersCache::new()).unwrap();
+        meta.content = meta.recurse_into(42, &flags, &UsersCache::new()).unwrap();
+        let content = meta.content.as_mut().unwrap();
+        content.sort_by(|a, b| a.name.name.cmp(&b.name.name));
+
+        assert_eq!(
+            format!(
+                "\u{1b}[31mred\nsub\nsub{}tab\there\n",
+                std::path::MAIN_SEPARATOR
+            ),
+            raw(&[meta], &flags)
+        );
+    }
+
+    #[test]
+    fn test_css_colour() {
+        assert_eq!("#800000", css_colour(1));
+        assert_eq!("#0087ff", css_colour(33));
+        assert_eq!("#080808", css_colour(232));
+    }
+
+    #[test]
+    fn test_ansi_to_html() {
+        assert_eq!("a &lt;b&gt; &amp; c", ansi_to_html("a <b> & c"));
+        assert_eq!(
+            "<span style=\"color:#0087ff;font-weight:bold\">dir</span>/",
+            ansi_to_html(&format!("{}/", Colour::Fixed(33).bold().paint("dir")))
+        );
+        assert_eq!(
+            "<span style=\"color:#c0c0c0;background-color:#0a141e\">x</span>",
+            ansi_to_html(
+                &Colour::White
+                    .on(Colour::RGB(10, 20, 30))
+                    .paint("x")
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_html() {
+        let argv = vec![
+            "lsd",
+            "--format",
+            "html",
+            "--blocks",
+            "name,size",
+            "--bytes",
+        ];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        fs::create_dir(tmp_dir.path().join("sub")).unwrap();
+        fs::write(tmp_dir.path().join("a<b>.txt"), "abc").unwrap();
+
+        let mut meta = Meta::from_path(tmp_dir.path(), false, &UsersCache::new()).unwrap();
+        meta.content = meta.recurse_into(1, &flags, &UsersCache::new()).unwrap();
+        let content = meta.content.as_mut().unwrap();
+        content.sort_by(|a, b| a.name.name.cmp(&b.name.name));
+
+        let colors = Colors::new(color::Theme::NoColor);
+        let icons = Icons::new(icon::Theme::NoIcon, String::new());
+        let output = html(&[meta.clone()], &flags, &colors, &icons);
+        let rows: Vec<&str> = output.lines().collect();
+        assert_eq!("<table class=\"lsd\">", rows[0]);
+        assert_eq!("<tr><th>path</th><th>name</th><th>size</th></tr>", rows[1]);
+        assert!(rows[2].contains("<td>a&lt;b&gt;.txt</td><td>3</td></tr>"));
+        assert!(rows[3].contains("<td>sub</td>"));
+        assert_eq!("</table>", rows[4]);
+
+        let colors = Colors::new(color::Theme::NoLscolors);
+        let icons = Icons::new(icon::Theme::Fancy, String::from(" "));
+        let output = html(&[meta], &flags, &colors, &icons);
+        assert!(output.contains("<span style=\"color:#0087ff\">sub</span>"));
+        assert!(output.contains("<span class=\"icon\">"));
+    }
+}
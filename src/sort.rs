@@ -1,7 +1,11 @@
-use crate::flags::{DirGrouping, Flags, SortColumn, SortOrder};
+use crate::flags::{DirGrouping, Flags, SortColumn, SortDotfiles, SortOrder};
 use crate::meta::Meta;
 use human_sort::compare;
 use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::thread;
 
 pub type SortFn = fn(&Meta, &Meta) -> Ordering;
 
@@ -16,10 +20,22 @@ pub fn assemble_sorters(flags: &Flags) -> Vec<(SortOrder, SortFn)> {
         }
         DirGrouping::None => {}
     };
+    match flags.sorting.dotfiles {
+        SortDotfiles::First => {
+            sorters.push((SortOrder::Default, with_dotfiles_first));
+        }
+        SortDotfiles::Last => {
+            sorters.push((SortOrder::Reverse, with_dotfiles_first));
+        }
+        SortDotfiles::None | SortDotfiles::Mixed => {}
+    };
     let other_sort = match flags.sorting.column {
+        SortColumn::Name if flags.sorting.dotfiles == SortDotfiles::Mixed => by_name_without_dot,
         SortColumn::Name => by_name,
         SortColumn::Size => by_size,
         SortColumn::Time => by_date,
+        SortColumn::Created => by_created,
+        SortColumn::Version if flags.sorting.version_ignore_case => by_version_ignore_case,
         SortColumn::Version => by_version,
         SortColumn::Extension => by_extension,
     };
@@ -42,10 +58,87 @@ pub fn by_meta(sorters: &[(SortOrder, SortFn)], a: &Meta, b: &Meta) -> Ordering
     Ordering::Equal
 }
 
+/// Reorder the metas with an external command, which receives the entry names on its stdin, one
+/// per line, and is expected to write the very same names back, sorted, on its stdout.
+///
+/// # Errors
+///
+/// This errors if the command can not be run, if it fails, or if its output is not a
+/// permutation of the given names. The metas are left untouched in this case.
+pub fn by_command(command: &str, metas: &mut Vec<Meta>) -> Result<(), String> {
+    if metas.len() < 2 {
+        return Ok(());
+    }
+
+    let names: Vec<&str> = metas.iter().map(|meta| meta.name.name.as_str()).collect();
+    if names.iter().any(|name| name.contains('\n')) {
+        return Err("can not pass names containing a newline".to_string());
+    }
+    let input = names.join("\n") + "\n";
+
+    #[cfg(unix)]
+    let mut child = Command::new("sh");
+    #[cfg(unix)]
+    child.arg("-c");
+    #[cfg(windows)]
+    let mut child = Command::new("cmd");
+    #[cfg(windows)]
+    child.arg("/C");
+
+    let mut child = child
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|err| err.to_string())?;
+
+    // Write from another thread so a command streaming its output can not dead lock us.
+    let mut stdin = child
+        .stdin
+        .take()
+        .expect("failed to open the command stdin");
+    let writer = thread::spawn(move || stdin.write_all(input.as_bytes()));
+
+    let output = child.wait_with_output().map_err(|err| err.to_string())?;
+    if let Ok(Err(err)) = writer.join() {
+        return Err(err.to_string());
+    }
+    if !output.status.success() {
+        return Err(format!("the command exited with {}", output.status));
+    }
+
+    let mut indexes: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (index, name) in names.iter().enumerate().rev() {
+        indexes.entry(name).or_default().push(index);
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut order = Vec::with_capacity(metas.len());
+    for line in stdout.lines() {
+        match indexes.get_mut(line).and_then(Vec::pop) {
+            Some(index) => order.push(index),
+            None => return Err(format!("unexpected entry in the command output: {}", line)),
+        }
+    }
+    if order.len() != metas.len() {
+        return Err("the command output is missing entries".to_string());
+    }
+
+    let mut sorted: Vec<Option<Meta>> = metas.drain(..).map(Some).collect();
+    metas.extend(order.into_iter().filter_map(|index| sorted[index].take()));
+
+    Ok(())
+}
+
 fn with_dirs_first(a: &Meta, b: &Meta) -> Ordering {
     b.file_type.is_dirlike().cmp(&a.file_type.is_dirlike())
 }
 
+fn with_dotfiles_first(a: &Meta, b: &Meta) -> Ordering {
+    let is_dotfile = |meta: &Meta| meta.name.name.starts_with('.');
+    is_dotfile(b).cmp(&is_dotfile(a))
+}
+
 fn by_size(a: &Meta, b: &Meta) -> Ordering {
     b.size.get_bytes().cmp(&a.size.get_bytes())
 }
@@ -54,24 +147,50 @@ fn by_name(a: &Meta, b: &Meta) -> Ordering {
     a.name.cmp(&b.name)
 }
 
+fn by_name_without_dot(a: &Meta, b: &Meta) -> Ordering {
+    let key = |meta: &Meta| {
+        let name = &meta.name.name;
+        name.strip_prefix('.').unwrap_or(name).to_lowercase()
+    };
+    key(a).cmp(&key(b)).then_with(|| by_name(a, b))
+}
+
 fn by_date(a: &Meta, b: &Meta) -> Ordering {
     b.date.cmp(&a.date).then(a.name.cmp(&b.name))
 }
 
+fn by_created(a: &Meta, b: &Meta) -> Ordering {
+    b.date
+        .created()
+        .cmp(&a.date.created())
+        .then(a.name.cmp(&b.name))
+}
+
 fn by_version(a: &Meta, b: &Meta) -> Ordering {
     compare(&a.name.name, &b.name.name)
 }
 
+fn by_version_ignore_case(a: &Meta, b: &Meta) -> Ordering {
+    compare(&a.name.name.to_lowercase(), &b.name.name.to_lowercase()).then_with(|| by_version(a, b))
+}
+
 fn by_extension(a: &Meta, b: &Meta) -> Ordering {
-    a.name.extension().cmp(&b.name.extension())
+    a.name
+        .extension()
+        .cmp(&b.name.extension())
+        .then(a.name.cmp(&b.name))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::app;
+    use crate::config_file::{Config, Sorting};
     use crate::flags::Flags;
+    use crate::meta::UsersCache;
     use std::fs::{create_dir, File};
     use std::process::Command;
+    use std::time::{Duration, UNIX_EPOCH};
     use tempfile::tempdir;
 
     #[test]
@@ -81,12 +200,14 @@ mod tests {
         // Create the file;
         let path_a = tmp_dir.path().join("zzz");
         File::create(&path_a).expect("failed to create file");
-        let meta_a = Meta::from_path(&path_a, false).expect("failed to get meta");
+        let meta_a =
+            Meta::from_path(&path_a, false, &UsersCache::new()).expect("failed to get meta");
 
         // Create a dir;
         let path_z = tmp_dir.path().join("aaa");
         create_dir(&path_z).expect("failed to create dir");
-        let meta_z = Meta::from_path(&path_z, false).expect("failed to get meta");
+        let meta_z =
+            Meta::from_path(&path_z, false, &UsersCache::new()).expect("failed to get meta");
 
         let mut flags = Flags::default();
         flags.sorting.dir_grouping = DirGrouping::First;
@@ -102,6 +223,38 @@ mod tests {
         assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Greater);
     }
 
+    #[test]
+    fn test_sort_assemble_sorters_by_created() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let path_a = tmp_dir.path().join("aaa");
+        File::create(&path_a).expect("failed to create file");
+        thread::sleep(Duration::from_millis(20));
+
+        // the newer file is modified before the older one
+        let path_z = tmp_dir.path().join("zzz");
+        File::create(&path_z)
+            .expect("failed to create file")
+            .set_modified(UNIX_EPOCH)
+            .expect("failed to change file timestamp");
+
+        let meta_a =
+            Meta::from_path(&path_a, false, &UsersCache::new()).expect("failed to get meta");
+        let meta_z =
+            Meta::from_path(&path_z, false, &UsersCache::new()).expect("failed to get meta");
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::Created;
+        let sorter = assemble_sorters(&flags);
+
+        // without the birth times, the files are sorted by their modified time
+        let expected = match path_a.metadata().unwrap().created() {
+            Ok(_) => Ordering::Greater,
+            Err(_) => Ordering::Less,
+        };
+        assert_eq!(by_meta(&sorter, &meta_a, &meta_z), expected);
+    }
+
     #[test]
     fn test_sort_assemble_sorters_by_name_with_files_first() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
@@ -109,12 +262,14 @@ mod tests {
         // Create the file;
         let path_a = tmp_dir.path().join("zzz");
         File::create(&path_a).expect("failed to create file");
-        let meta_a = Meta::from_path(&path_a, false).expect("failed to get meta");
+        let meta_a =
+            Meta::from_path(&path_a, false, &UsersCache::new()).expect("failed to get meta");
 
         // Create a dir;
         let path_z = tmp_dir.path().join("aaa");
         create_dir(&path_z).expect("failed to create dir");
-        let meta_z = Meta::from_path(&path_z, false).expect("failed to get meta");
+        let meta_z =
+            Meta::from_path(&path_z, false, &UsersCache::new()).expect("failed to get meta");
 
         let mut flags = Flags::default();
         flags.sorting.dir_grouping = DirGrouping::Last;
@@ -135,12 +290,14 @@ mod tests {
         // Create the file;
         let path_a = tmp_dir.path().join("aaa");
         File::create(&path_a).expect("failed to create file");
-        let meta_a = Meta::from_path(&path_a, false).expect("failed to get meta");
+        let meta_a =
+            Meta::from_path(&path_a, false, &UsersCache::new()).expect("failed to get meta");
 
         // Create a dir;
         let path_z = tmp_dir.path().join("zzz");
         create_dir(&path_z).expect("failed to create dir");
-        let meta_z = Meta::from_path(&path_z, false).expect("failed to get meta");
+        let meta_z =
+            Meta::from_path(&path_z, false, &UsersCache::new()).expect("failed to get meta");
 
         let mut flags = Flags::default();
         flags.sorting.dir_grouping = DirGrouping::None;
@@ -163,12 +320,14 @@ mod tests {
         // Create the file;
         let path_a = tmp_dir.path().join("zzz");
         File::create(&path_a).expect("failed to create file");
-        let meta_a = Meta::from_path(&path_a, false).expect("failed to get meta");
+        let meta_a =
+            Meta::from_path(&path_a, false, &UsersCache::new()).expect("failed to get meta");
 
         // Create a dir;
         let path_z = tmp_dir.path().join("aaa");
         create_dir(&path_z).expect("failed to create dir");
-        let meta_z = Meta::from_path(&path_z, false).expect("failed to get meta");
+        let meta_z =
+            Meta::from_path(&path_z, false, &UsersCache::new()).expect("failed to get meta");
 
         let mut flags = Flags::default();
         flags.sorting.dir_grouping = DirGrouping::None;
@@ -191,7 +350,8 @@ mod tests {
         // Create the file;
         let path_a = tmp_dir.path().join("aaa");
         File::create(&path_a).expect("failed to create file");
-        let meta_a = Meta::from_path(&path_a, false).expect("failed to get meta");
+        let meta_a =
+            Meta::from_path(&path_a, false, &UsersCache::new()).expect("failed to get meta");
 
         // Create the file;
         let path_z = tmp_dir.path().join("zzz");
@@ -217,7 +377,8 @@ mod tests {
             .success();
 
         assert_eq!(true, success, "failed to change file timestamp");
-        let meta_z = Meta::from_path(&path_z, false).expect("failed to get meta");
+        let meta_z =
+            Meta::from_path(&path_z, false, &UsersCache::new()).expect("failed to get meta");
 
         let mut flags = Flags::default();
         flags.sorting.column = SortColumn::Time;
@@ -239,29 +400,33 @@ mod tests {
         // Create the file with rs extension;
         let path_a = tmp_dir.path().join("aaa.rs");
         File::create(&path_a).expect("failed to create file");
-        let meta_a = Meta::from_path(&path_a, false).expect("failed to get meta");
+        let meta_a =
+            Meta::from_path(&path_a, false, &UsersCache::new()).expect("failed to get meta");
 
         // Create the file with rs extension;
         let path_z = tmp_dir.path().join("zzz.rs");
         File::create(&path_z).expect("failed to create file");
-        let meta_z = Meta::from_path(&path_z, false).expect("failed to get meta");
+        let meta_z =
+            Meta::from_path(&path_z, false, &UsersCache::new()).expect("failed to get meta");
 
         // Create the file with js extension;
         let path_j = tmp_dir.path().join("zzz.js");
         File::create(&path_j).expect("failed to create file");
-        let meta_j = Meta::from_path(&path_j, false).expect("failed to get meta");
+        let meta_j =
+            Meta::from_path(&path_j, false, &UsersCache::new()).expect("failed to get meta");
 
         // Create the file with txt extension;
         let path_t = tmp_dir.path().join("zzz.txt");
         File::create(&path_t).expect("failed to create file");
-        let meta_t = Meta::from_path(&path_t, false).expect("failed to get meta");
+        let meta_t =
+            Meta::from_path(&path_t, false, &UsersCache::new()).expect("failed to get meta");
 
         let mut flags = Flags::default();
         flags.sorting.column = SortColumn::Extension;
 
-        // Sort by extension
+        // Sort by extension, then by name
         let sorter = assemble_sorters(&flags);
-        assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Equal);
+        assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Less);
 
         let sorter = assemble_sorters(&flags);
         assert_eq!(by_meta(&sorter, &meta_a, &meta_j), Ordering::Greater);
@@ -276,15 +441,18 @@ mod tests {
 
         let path_a = tmp_dir.path().join("2");
         File::create(&path_a).expect("failed to create file");
-        let meta_a = Meta::from_path(&path_a, false).expect("failed to get meta");
+        let meta_a =
+            Meta::from_path(&path_a, false, &UsersCache::new()).expect("failed to get meta");
 
         let path_b = tmp_dir.path().join("11");
         File::create(&path_b).expect("failed to create file");
-        let meta_b = Meta::from_path(&path_b, false).expect("failed to get meta");
+        let meta_b =
+            Meta::from_path(&path_b, false, &UsersCache::new()).expect("failed to get meta");
 
         let path_c = tmp_dir.path().join("12");
         File::create(&path_c).expect("failed to create file");
-        let meta_c = Meta::from_path(&path_c, false).expect("failed to get meta");
+        let meta_c =
+            Meta::from_path(&path_c, false, &UsersCache::new()).expect("failed to get meta");
 
         let mut flags = Flags::default();
         flags.sorting.column = SortColumn::Version;
@@ -295,4 +463,148 @@ mod tests {
         let sorter = assemble_sorters(&flags);
         assert_eq!(by_meta(&sorter, &meta_b, &meta_c), Ordering::Less);
     }
+
+    #[test]
+    fn test_sort_assemble_sorters_by_version_ignore_case() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let mut metas = vec![];
+        for name in &["img10.png", "IMG3.png", "img2.png", "img1.png"] {
+            let path = tmp_dir.path().join(name);
+            File::create(&path).expect("failed to create file");
+            metas.push(
+                Meta::from_path(&path, false, &UsersCache::new()).expect("failed to get meta"),
+            );
+        }
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::Version;
+        let names = |metas: &mut Vec<Meta>, flags: &Flags| {
+            let sorter = assemble_sorters(flags);
+            metas.sort_by(|a, b| by_meta(&sorter, a, b));
+            metas
+                .iter()
+                .map(|m| m.name.name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            vec!["IMG3.png", "img1.png", "img2.png", "img10.png"],
+            names(&mut metas, &flags)
+        );
+
+        flags.sorting.version_ignore_case = true;
+        assert_eq!(
+            vec!["img1.png", "img2.png", "IMG3.png", "img10.png"],
+            names(&mut metas, &flags)
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_sort_by_command() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let mut metas = vec![];
+        for name in &["bbb", "aaa", "ccc"] {
+            let path = tmp_dir.path().join(name);
+            File::create(&path).expect("failed to create file");
+            metas.push(
+                Meta::from_path(&path, false, &UsersCache::new()).expect("failed to get meta"),
+            );
+        }
+
+        by_command("sort -r", &mut metas).expect("failed to sort with command");
+
+        let names: Vec<&str> = metas.iter().map(|m| m.name.name.as_str()).collect();
+        assert_eq!(vec!["ccc", "bbb", "aaa"], names);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_sort_by_command_invalid_output() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let mut metas = vec![];
+        for name in &["bbb", "aaa"] {
+            let path = tmp_dir.path().join(name);
+            File::create(&path).expect("failed to create file");
+            metas.push(
+                Meta::from_path(&path, false, &UsersCache::new()).expect("failed to get meta"),
+            );
+        }
+
+        // missing entries
+        assert!(by_command("head -n 1", &mut metas).is_err());
+        // unknown entries
+        assert!(by_command("echo zzz; cat", &mut metas).is_err());
+        // failing command
+        assert!(by_command("sort; false", &mut metas).is_err());
+
+        // the order is kept on error
+        let names: Vec<&str> = metas.iter().map(|m| m.name.name.as_str()).collect();
+        assert_eq!(vec!["bbb", "aaa"], names);
+    }
+
+    #[test]
+    fn test_sort_interleave_dirs() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        File::create(tmp_dir.path().join("aaa")).expect("failed to create file");
+        create_dir(tmp_dir.path().join("bbb")).expect("failed to create dir");
+        File::create(tmp_dir.path().join("ccc")).expect("failed to create file");
+        create_dir(tmp_dir.path().join("ddd")).expect("failed to create dir");
+
+        let mut config = Config::with_none();
+        config.sorting = Some(Sorting {
+            column: None,
+            reverse: None,
+            dir_grouping: Some(DirGrouping::First),
+            interleave_dirs: Some(true),
+            version_ignore_case: None,
+            dotfiles: None,
+        });
+        let matches = app::build()
+            .get_matches_from_safe(vec!["lsd", "--group-dirs", "first"])
+            .unwrap();
+        let flags = Flags::configure_from(&matches, &config).unwrap();
+
+        let mut metas = Meta::from_path(tmp_dir.path(), false, &UsersCache::new())
+            .unwrap()
+            .recurse_into(1, &flags, &UsersCache::new())
+            .unwrap()
+            .unwrap();
+        let sorters = assemble_sorters(&flags);
+        metas.sort_unstable_by(|a, b| by_meta(&sorters, a, b));
+
+        let names: Vec<&str> = metas.iter().map(|meta| meta.name.name.as_str()).collect();
+        assert_eq!(vec!["aaa", "bbb", "ccc", "ddd"], names);
+    }
+
+    #[test]
+    fn test_sort_dotfiles() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let metas: Vec<Meta> = [".a", "b", ".c", "d"]
+            .iter()
+            .map(|name| {
+                let path = tmp_dir.path().join(name);
+                File::create(&path).expect("failed to create file");
+                Meta::from_path(&path, false, &UsersCache::new()).expect("failed to get meta")
+            })
+            .collect();
+
+        for (dotfiles, expected) in &[
+            (SortDotfiles::First, [".a", ".c", "b", "d"]),
+            (SortDotfiles::Last, ["b", "d", ".a", ".c"]),
+            (SortDotfiles::Mixed, [".a", "b", ".c", "d"]),
+        ] {
+            let mut flags = Flags::default();
+            flags.sorting.dotfiles = *dotfiles;
+            let sorters = assemble_sorters(&flags);
+
+            let mut metas = metas.clone();
+            metas.sort_unstable_by(|a, b| by_meta(&sorters, a, b));
+            let names: Vec<&str> = metas.iter().map(|meta| meta.name.name.as_str()).collect();
+            assert_eq!(expected.to_vec(), names, "{:?}", dotfiles);
+        }
+    }
 }
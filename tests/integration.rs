@@ -234,6 +234,675 @@ fn test_nosymlink_on_non_long() {
         .stdout(predicate::str::contains(link_icon).not());
 }
 
+#[test]
+fn test_oneline_prints_names_only() {
+    let dir = tempdir();
+    dir.child("one").write_str("some content").unwrap();
+    dir.child("two.d").create_dir_all().unwrap();
+
+    cmd()
+        .arg("-1")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("one\ntwo.d\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_oneline_nosymlink() {
+    let dir = tempdir();
+    dir.child("target").touch().unwrap();
+    fs::symlink("target", dir.path().join("link")).unwrap();
+
+    cmd()
+        .arg("-1")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("link\ntarget\n"));
+}
+
+#[test]
+fn test_date_unix_timestamp() {
+    let dir = tempdir();
+    let file = dir.child("file");
+    file.touch().unwrap();
+    let modified = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_600_000_000);
+    std::fs::File::options()
+        .write(true)
+        .open(file.path())
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+
+    cmd()
+        .arg("-l")
+        .arg("--ignore-config")
+        .arg("--date")
+        .arg("+%s")
+        .arg(file.path())
+        .assert()
+        .stdout(predicate::str::contains(" 1600000000 "));
+}
+
+#[test]
+fn test_time_zone() {
+    let dir = tempdir();
+    let file = dir.child("file");
+    file.touch().unwrap();
+    let modified = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_600_000_000);
+    std::fs::File::options()
+        .write(true)
+        .open(file.path())
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+
+    // the POSIX zone three hours east of UTC, without daylight saving time
+    for (zone, expected) in &[("local", "15:26 file\n"), ("utc", "12:26 file\n")] {
+        cmd()
+            .env("TZ", "XXX-3")
+            .arg("-l")
+            .arg("--ignore-config")
+            .arg("--blocks")
+            .arg("date,name")
+            .arg("--date")
+            .arg("+%H:%M")
+            .arg("--time-zone")
+            .arg(zone)
+            .arg("file")
+            .current_dir(dir.path())
+            .assert()
+            .stdout(predicate::eq(*expected));
+    }
+}
+
+#[test]
+fn test_time_accessed() {
+    let dir = tempdir();
+    let stamp = |secs| std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs);
+    for (name, accessed, modified) in &[
+        ("one", 1_600_000_000, 1_500_000_000),
+        ("two", 1_500_000_000, 1_600_000_000),
+    ] {
+        let file = dir.child(name);
+        file.touch().unwrap();
+        let times = std::fs::FileTimes::new()
+            .set_accessed(stamp(*accessed))
+            .set_modified(stamp(*modified));
+        std::fs::File::options()
+            .write(true)
+            .open(file.path())
+            .unwrap()
+            .set_times(times)
+            .unwrap();
+    }
+
+    cmd()
+        .arg("-lt")
+        .arg("--ignore-config")
+        .arg("--blocks")
+        .arg("date,name")
+        .arg("--date")
+        .arg("+%s")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("1600000000 two\n1500000000 one\n"));
+
+    cmd()
+        .arg("-lt")
+        .arg("--ignore-config")
+        .arg("--blocks")
+        .arg("date,name")
+        .arg("--date")
+        .arg("+%s")
+        .arg("--time")
+        .arg("accessed")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("1600000000 one\n1500000000 two\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_dedup_hard_links() {
+    let dir = tempdir();
+    dir.child("file").touch().unwrap();
+    std::fs::hard_link(dir.path().join("file"), dir.path().join("link")).unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("-1")
+        .arg("file")
+        .arg("link")
+        .current_dir(dir.path())
+        .assert()
+        .stdout(predicate::eq("file\nlink\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("-1")
+        .arg("--dedup")
+        .arg("file")
+        .arg("link")
+        .current_dir(dir.path())
+        .assert()
+        .stdout(predicate::eq("file\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_numeric_owner() {
+    use std::os::unix::fs::MetadataExt;
+
+    let dir = tempdir();
+    dir.child("file").touch().unwrap();
+    let meta = dir.path().join("file").metadata().unwrap();
+
+    cmd()
+        .arg("-ln")
+        .arg("--ignore-config")
+        .arg("--blocks")
+        .arg("user,group,name")
+        .arg(dir.path())
+        .assert()
+        .stdout(format!("{} {} file\n", meta.uid(), meta.gid()));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_collapse_repeats() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir();
+    for (name, mode) in &[("a", 0o644), ("b", 0o644), ("c", 0o600)] {
+        dir.child(name).touch().unwrap();
+        std::fs::set_permissions(dir.path().join(name), PermissionsExt::from_mode(*mode)).unwrap();
+    }
+    let user = users::get_current_username().unwrap();
+    let user = user.to_str().unwrap();
+    let blank = " ".repeat(user.len());
+
+    cmd()
+        .arg("-l")
+        .arg("--ignore-config")
+        .arg("--collapse-repeats")
+        .arg("--blocks")
+        .arg("permission,user,name")
+        .arg(dir.path())
+        .assert()
+        .stdout(format!(
+            ".rw-r--r-- {} a\n           {} b\n.rw------- {} c\n",
+            user, blank, blank
+        ));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_permission_octal() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir();
+    for (name, mode) in &[("plain", 0o644), ("setuid", 0o4755), ("sticky", 0o1777)] {
+        dir.child(name).touch().unwrap();
+        std::fs::set_permissions(dir.path().join(name), PermissionsExt::from_mode(*mode)).unwrap();
+    }
+
+    cmd()
+        .arg("-l")
+        .arg("--ignore-config")
+        .arg("--permission")
+        .arg("octal")
+        .arg("--blocks")
+        .arg("permission,name")
+        .arg(dir.path())
+        .assert()
+        .stdout(". 0644 plain\n. 4755 setuid\n. 1777 sticky\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_permission_octal_directory() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir();
+    dir.child("shared").create_dir_all().unwrap();
+    std::fs::set_permissions(dir.path().join("shared"), PermissionsExt::from_mode(0o2775)).unwrap();
+
+    cmd()
+        .arg("-l")
+        .arg("--ignore-config")
+        .arg("--permission")
+        .arg("octal")
+        .arg("--blocks")
+        .arg("permission,name")
+        .arg(dir.path())
+        .assert()
+        .stdout("d 2775 shared\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_only_dirs_and_files() {
+    let dir = tempdir();
+    dir.child("sub/inner/deep").touch().unwrap();
+    dir.child("sub/file").touch().unwrap();
+    dir.child("file").touch().unwrap();
+    fs::symlink("sub", dir.path().join("link")).unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--only-dirs")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("link\nsub\n"));
+    cmd()
+        .arg("--ignore-config")
+        .arg("--only-dirs")
+        .arg("--no-symlink")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("sub\n"));
+    cmd()
+        .arg("--ignore-config")
+        .arg("--only-files")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("file\n"));
+    cmd()
+        .arg("--ignore-config")
+        .arg("--only-dirs")
+        .arg("--tree")
+        .arg("sub")
+        .current_dir(dir.path())
+        .assert()
+        .stdout(predicate::eq("sub\n└── inner\n"));
+}
+
+#[test]
+fn test_ignore_glob_at_every_level() {
+    let dir = tempdir();
+    dir.child("pkg/sub/module.pyc").touch().unwrap();
+    dir.child("pkg/sub/module.py").touch().unwrap();
+    dir.child("pkg/init.pyc").touch().unwrap();
+    dir.child("main.pyc").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--tree")
+        .arg("--ignore-glob")
+        .arg("*.pyc")
+        .arg("pkg")
+        .current_dir(dir.path())
+        .assert()
+        .stdout(predicate::eq("pkg\n└── sub\n    └── module.py\n"));
+    cmd()
+        .arg("--ignore-config")
+        .arg("--tree")
+        .arg("--ignore-glob")
+        .arg("sub/*.pyc")
+        .arg("pkg")
+        .current_dir(dir.path())
+        .assert()
+        .stdout(predicate::eq(
+            "pkg\n├── init.pyc\n└── sub\n    └── module.py\n",
+        ));
+}
+
+#[test]
+fn test_ignore_glob_path_relative_to_listed_dir() {
+    let dir = tempdir();
+    dir.child("proj/build/main.o").touch().unwrap();
+    dir.child("proj/src/build/main.o").touch().unwrap();
+
+    // the paths of the globs are relative to the listed directory, not to the current one
+    cmd()
+        .arg("--ignore-config")
+        .arg("--tree")
+        .arg("--ignore-glob")
+        .arg("build/*.o")
+        .arg("proj")
+        .current_dir(dir.path())
+        .assert()
+        .stdout(predicate::eq(
+            "proj\n├── build\n└── src\n    └── build\n        └── main.o\n",
+        ));
+    cmd()
+        .arg("--ignore-config")
+        .arg("--tree")
+        .arg("--ignore-glob")
+        .arg("build/*.o")
+        .arg(".")
+        .current_dir(dir.path().join("proj"))
+        .assert()
+        .stdout(predicate::eq(
+            ".\n├── build\n└── src\n    └── build\n        └── main.o\n",
+        ));
+}
+
+#[test]
+fn test_top_largest_files() {
+    let dir = tempdir();
+    dir.child("small").write_str("1").unwrap();
+    dir.child("sub/large").write_str("1234567").unwrap();
+    dir.child("sub/deep/medium").write_str("1234").unwrap();
+    dir.child("sub/deep/tiny").write_str("").unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--top")
+        .arg("3")
+        .arg("--blocks")
+        .arg("size,name")
+        .arg("--size")
+        .arg("bytes")
+        .arg(".")
+        .current_dir(dir.path())
+        .assert()
+        .stdout(predicate::eq(
+            "7 ./sub/large\n4 ./sub/deep/medium\n1 ./small\n",
+        ));
+}
+
+#[test]
+fn test_top_largest_files_within_depth() {
+    let dir = tempdir();
+    dir.child("small").write_str("1").unwrap();
+    dir.child("sub/large").write_str("1234567").unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--top")
+        .arg("3")
+        .arg("--depth")
+        .arg("1")
+        .arg("--blocks")
+        .arg("size,name")
+        .arg("--size")
+        .arg("bytes")
+        .arg(".")
+        .current_dir(dir.path())
+        .assert()
+        .stdout(predicate::eq("1 ./small\n"));
+}
+
+#[test]
+fn test_file_count_block() {
+    let dir = tempdir();
+    dir.child("full/one").touch().unwrap();
+    dir.child("full/two").touch().unwrap();
+    dir.child("full/nested/three").touch().unwrap();
+    dir.child("empty").create_dir_all().unwrap();
+    dir.child("file").touch().unwrap();
+
+    cmd()
+        .arg("-l")
+        .arg("--ignore-config")
+        .arg("--blocks")
+        .arg("file_count,name")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("0 empty\n  file\n3 full\n"));
+}
+
+#[test]
+fn test_group_by_date() {
+    use std::time::{Duration, SystemTime};
+
+    let dir = tempdir();
+    let day = Duration::from_secs(24 * 60 * 60);
+    let now = SystemTime::now();
+    for (name, mtime) in &[
+        ("ahead", now + 2 * day),
+        ("fresh", now),
+        ("last", now - day),
+        ("recent", now - 4 * day),
+        ("stale", now - 30 * day),
+        ("ancient", now - 400 * day),
+    ] {
+        dir.child(name).touch().unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(dir.path().join(name))
+            .unwrap()
+            .set_modified(*mtime)
+            .unwrap();
+    }
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--group-by-date")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(
+            "Future:\nahead\n\nToday:\nfresh\n\nYesterday:\nlast\n\nThis week:\nrecent\n\nOlder:\nancient\nstale\n",
+        ));
+}
+
+#[test]
+fn test_bytes_sizes() {
+    let dir = tempdir();
+    dir.child("small").write_str("1").unwrap();
+    dir.child("large").write_binary(&[0; 1536]).unwrap();
+
+    cmd()
+        .arg("-l")
+        .arg("--ignore-config")
+        .arg("--bytes")
+        .arg("--blocks")
+        .arg("size,name")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("1536 large\n   1 small\n"));
+}
+
+#[test]
+fn test_size_width() {
+    let dir = tempdir();
+    dir.child("small").write_str("1").unwrap();
+    dir.child("large").write_binary(&[0; 1536]).unwrap();
+
+    cmd()
+        .arg("-l")
+        .arg("--ignore-config")
+        .arg("--bytes")
+        .arg("--size-width")
+        .arg("8")
+        .arg("--blocks")
+        .arg("size,name")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("    1536 large\n       1 small\n"));
+
+    cmd()
+        .arg("-l")
+        .arg("--ignore-config")
+        .arg("--bytes")
+        .arg("--size-width")
+        .arg("2")
+        .arg("--blocks")
+        .arg("size,name")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("1536 large\n   1 small\n"));
+}
+
+#[test]
+fn test_icon_spacing() {
+    let dir = tempdir();
+    dir.child("file").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--icon")
+        .arg("always")
+        .arg("--icon-theme")
+        .arg("unicode")
+        .arg("--icon-spacing")
+        .arg("3")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("\u{1f5cb}\u{fe0f}   file\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_null_separated_names() {
+    let dir = tempdir();
+    dir.child("with space").touch().unwrap();
+    dir.child("with\nnewline").touch().unwrap();
+    dir.child("sub/inner").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("-0")
+        .arg("--recursive")
+        .arg(".")
+        .current_dir(dir.path())
+        .assert()
+        .stdout(predicate::eq("sub\0sub/inner\0with\nnewline\0with space\0"));
+    cmd()
+        .arg("--ignore-config")
+        .arg("-0")
+        .arg("--full-path")
+        .arg("sub")
+        .current_dir(dir.path())
+        .assert()
+        .stdout(predicate::eq("sub/inner\0"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_total_allocated_blocks() {
+    use std::os::unix::fs::MetadataExt;
+
+    let dir = tempdir();
+    dir.child("sub").create_dir_all().unwrap();
+    dir.child("sub/large").write_binary(&[1; 5000]).unwrap();
+    dir.child("sub/small").write_str("1").unwrap();
+
+    let blocks: u64 = ["sub/large", "sub/small"]
+        .iter()
+        .map(|file| dir.path().join(file).metadata().unwrap().blocks())
+        .sum();
+    let total = format!("total {}\n", (blocks * 512).div_ceil(1024));
+
+    cmd()
+        .arg("-l")
+        .arg("--ignore-config")
+        .arg("--total")
+        .arg("--blocks")
+        .arg("name")
+        .arg(dir.path().join("sub"))
+        .assert()
+        .stdout(predicate::eq(format!("{}large\nsmall\n", total).as_str()));
+
+    cmd()
+        .arg("-l")
+        .arg("--ignore-config")
+        .arg("--blocks")
+        .arg("name")
+        .arg(dir.path().join("sub"))
+        .assert()
+        .stdout(predicate::eq("large\nsmall\n"));
+}
+
+#[test]
+fn test_dir_child_count() {
+    let dir = tempdir();
+    dir.child("src/main.rs").touch().unwrap();
+    dir.child("src/lib.rs").touch().unwrap();
+    dir.child("src/.hidden").touch().unwrap();
+    dir.child("file").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--dir-child-count")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("file\nsrc (2)\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--dir-child-count")
+        .arg("--almost-all")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("file\nsrc (3)\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_realpath_block() {
+    let dir = tempdir();
+    dir.child("target").touch().unwrap();
+    fs::symlink("target", dir.path().join("link")).unwrap();
+    let target = dir.path().canonicalize().unwrap().join("target");
+
+    cmd()
+        .arg("-l")
+        .arg("--ignore-config")
+        .arg("--blocks")
+        .arg("name,realpath")
+        .arg("./link")
+        .current_dir(dir.path())
+        .assert()
+        .stdout(predicate::str::contains(target.to_str().unwrap()));
+}
+
+#[test]
+fn test_theme_file_rgb_colors() {
+    let dir = tempdir();
+    dir.child("sub").create_dir_all().unwrap();
+    dir.child("theme.yaml")
+        .write_str("dir: [38, 139, 210]\nneon: [1, 2, 3]\n")
+        .unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--color")
+        .arg("always")
+        .arg("--icon")
+        .arg("never")
+        .arg("--theme")
+        .arg("theme.yaml")
+        .arg("sub")
+        .arg("-d")
+        .current_dir(dir.path())
+        .assert()
+        .stdout(predicate::str::contains("\u{1b}[38;2;38;139;210msub"))
+        .stderr(predicate::str::contains("neon is unknown"));
+}
+
+#[test]
+fn test_tree_parallel_scan_output() {
+    let dir = tempdir();
+    for i in 0..8 {
+        for j in 0..4 {
+            dir.child(format!("dir{}/sub{}/file{}", i, j, j))
+                .write_str("some content")
+                .unwrap();
+        }
+    }
+
+    let output = |args: &[&str]| {
+        cmd()
+            .arg("--ignore-config")
+            .arg("--tree")
+            .args(args)
+            .arg(dir.path())
+            .output()
+            .unwrap()
+            .stdout
+    };
+
+    // a scan budget keeps the scan on a single thread
+    let serial = output(&["--scan-budget", "1GiB"]);
+    assert!(String::from_utf8_lossy(&serial).contains("file3"));
+    assert_eq!(serial, output(&[]));
+}
+
 #[cfg(unix)]
 #[test]
 fn test_dereference_link_right_type_and_no_link() {
@@ -323,6 +992,73 @@ fn test_show_folder_of_symlink_for_long_multi() {
         .stdout(predicate::str::contains("link/:"));
 }
 
+#[cfg(unix)]
+#[test]
+fn test_dereference_command_line_symlink_to_dir() {
+    let dir = tempdir();
+    dir.child("target").child("inside").touch().unwrap();
+    dir.child("file").touch().unwrap();
+    let link = dir.path().join("link");
+    fs::symlink("target", &link).unwrap();
+
+    cmd()
+        .arg("-l")
+        .arg("--ignore-config")
+        .arg("--dereference-command-line-symlink-to-dir")
+        .arg(dir.path().join("file"))
+        .arg(&link)
+        .assert()
+        .stdout(predicate::str::contains(format!("{}:", link.display())))
+        .stdout(predicate::str::contains("inside"))
+        .stdout(predicate::str::contains("⇒").not());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_expand_paths() {
+    let dir = tempdir();
+    dir.child("foo").child("inside").touch().unwrap();
+
+    for input in &["~/foo", "$HOME/foo", "${HOME}/foo"] {
+        cmd()
+            .env("HOME", dir.path())
+            .arg("--ignore-config")
+            .arg("--expand-paths")
+            .arg(input)
+            .assert()
+            .stdout(predicate::eq("inside\n"));
+    }
+}
+
+#[test]
+fn test_content_on_trailing_slash() {
+    let dir = tempdir();
+    dir.child("sub").child("inside").touch().unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .arg("--ignore-config")
+        .arg("--content-on-trailing-slash")
+        .arg("sub")
+        .assert()
+        .stdout(predicate::eq("sub\n"));
+
+    cmd()
+        .current_dir(dir.path())
+        .arg("--ignore-config")
+        .arg("--content-on-trailing-slash")
+        .arg("sub/")
+        .assert()
+        .stdout(predicate::eq("inside\n"));
+
+    cmd()
+        .current_dir(dir.path().join("sub"))
+        .arg("--ignore-config")
+        .arg("--content-on-trailing-slash")
+        .assert()
+        .stdout(predicate::eq("inside\n"));
+}
+
 #[test]
 fn test_version_sort() {
     let dir = tempdir();
@@ -345,6 +1081,85 @@ fn test_version_sort() {
         );
 }
 
+#[test]
+fn test_extension_sort() {
+    let dir = tempdir();
+    for name in &[
+        "main.rs",
+        "Makefile",
+        "Cargo.toml",
+        "lib.rs",
+        "LICENSE",
+        "build.rs",
+    ] {
+        dir.child(name).touch().unwrap();
+    }
+    cmd()
+        .arg("-X")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(
+            "LICENSE\nMakefile\nbuild.rs\nlib.rs\nmain.rs\nCargo.toml\n",
+        ));
+}
+
+#[test]
+fn test_raw_output() {
+    let dir = tempdir();
+    dir.child("plain.txt").touch().unwrap();
+    dir.child("tab\tname").touch().unwrap();
+    dir.child("with space").touch().unwrap();
+
+    cmd()
+        .arg("--raw")
+        .arg("--ignore-config")
+        .arg("--color")
+        .arg("always")
+        .arg("--icon")
+        .arg("always")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("plain.txt\ntab\tname\nwith space\n"));
+}
+
+#[test]
+fn test_version_sort_numbered_files() {
+    let dir = tempdir();
+    for name in &["img10.png", "img2.png", "img1.png"] {
+        dir.child(name).touch().unwrap();
+    }
+    cmd()
+        .arg("-v")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("img1.png\nimg2.png\nimg10.png\n"));
+}
+
+#[test]
+fn test_group_directories_last() {
+    let dir = tempdir();
+    dir.child("big").write_str("some content").unwrap();
+    dir.child("small").touch().unwrap();
+    dir.child("adir").create_dir_all().unwrap();
+    dir.child("zdir").create_dir_all().unwrap();
+
+    cmd()
+        .arg("--group-directories-last")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("big\nsmall\nadir\nzdir\n"));
+    cmd()
+        .arg("--group-directories-last")
+        .arg("--ignore-config")
+        .arg("--reverse")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("small\nbig\nzdir\nadir\n"));
+}
+
 #[test]
 fn test_version_sort_overwrite_by_timesort() {
     let dir = tempdir();
@@ -480,6 +1295,87 @@ fn test_tree_d() {
         .stdout(predicate::str::is_match("├── one.d\n│   └── one.d\n└── two.d\n$").unwrap());
 }
 
+#[test]
+fn test_tree_depth() {
+    let tmp = tempdir();
+    tmp.child("one.d/two.d/three.d/four").touch().unwrap();
+
+    cmd()
+        .arg(tmp.path())
+        .arg("--tree")
+        .arg("--depth")
+        .arg("2")
+        .assert()
+        .stdout(predicate::str::is_match("└── one.d\n    └── two.d\n$").unwrap());
+}
+
+#[test]
+fn test_recursive_depth() {
+    let tmp = tempdir();
+    tmp.child("one.d/two.d/three.d/four").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--recursive")
+        .arg("--depth")
+        .arg("2")
+        .arg("one.d")
+        .current_dir(tmp.path())
+        .assert()
+        .stdout(predicate::eq("two.d\n\none.d/two.d:\nthree.d\n"));
+}
+
+#[test]
+fn test_debug_layout() {
+    let dir = tempdir();
+    dir.child("one").touch().unwrap();
+    dir.child("two").touch().unwrap();
+
+    cmd()
+        .arg("--debug-layout")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("one\ntwo\n"))
+        .stderr(predicate::str::is_match("grid layout: [0-9]+ column").unwrap());
+
+    cmd()
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stderr(predicate::eq(""));
+}
+
+#[test]
+fn test_font_check() {
+    let dir = tempdir();
+    dir.child("one").touch().unwrap();
+
+    cmd()
+        .arg("--font-check")
+        .arg("--icon")
+        .arg("always")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .env("TERM", "linux")
+        .assert()
+        .stdout(predicate::str::contains("one"))
+        .stderr(predicate::str::contains("Nerd Font"));
+
+    cmd()
+        .arg("--font-check")
+        .arg("--icon")
+        .arg("always")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .env("TERM", "xterm-256color")
+        .env("LANG", "en_US.UTF-8")
+        .env_remove("LC_ALL")
+        .env_remove("LC_CTYPE")
+        .assert()
+        .stderr(predicate::eq(""));
+}
+
 fn cmd() -> Command {
     Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap()
 }